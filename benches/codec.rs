@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use hashids::{HashidBuilder, alphabet};
+
+fn encode(c: &mut Criterion) {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  c.bench_function("encode single", |b| b.iter(|| codec.encode(black_box(12345u64))));
+  c.bench_function("encode tuple", |b| b.iter(|| codec.encode_tuple(black_box((683u64, 94108u64, 123u64, 5u64)))));
+
+  let ids: Vec<u64> = (0..1000).collect();
+  c.bench_function("encode batch of 1000", |b| b.iter(|| codec.encode_many(black_box(&ids))));
+}
+
+fn decode(c: &mut Criterion) {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  c.bench_function("decode single", |b| b.iter(|| codec.decode_single(black_box("NkK9"))));
+  c.bench_function("decode tuple", |b| b.iter(|| codec.decode(black_box("aBMswoO2UB3Sj"))));
+}
+
+fn build(c: &mut Criterion) {
+  c.bench_function("build default", |b| b.iter(|| HashidBuilder::new().with_salt(black_box("this is my salt")).build()));
+}
+
+fn min_length(c: &mut Criterion) {
+  let mut group = c.benchmark_group("min length");
+  for length in [0, 8, 32, 128] {
+    let codec = HashidBuilder::new().with_salt("this is my salt").with_length(length).build().unwrap();
+    let hash = codec.encode(12345u64).unwrap();
+    group.bench_with_input(BenchmarkId::new("encode", length), &codec, |b, codec| b.iter(|| codec.encode(black_box(12345u64))));
+    group.bench_with_input(BenchmarkId::new("decode", length), &hash, |b, hash| b.iter(|| codec.decode_single(black_box(hash))));
+  }
+  group.finish();
+}
+
+fn alphabets(c: &mut Criterion) {
+  let mut group = c.benchmark_group("alphabet");
+  for preset in alphabet::Preset::ALL {
+    let codec = HashidBuilder::new().with_salt("this is my salt").with_preset(preset).build().unwrap();
+    let hash = codec.encode(u32::MAX as u64).unwrap();
+    group.bench_with_input(BenchmarkId::new("encode", format!("{:?}", preset)), &codec, |b, codec| b.iter(|| codec.encode(black_box(u32::MAX as u64))));
+    group.bench_with_input(BenchmarkId::new("decode", format!("{:?}", preset)), &hash, |b, hash| b.iter(|| codec.decode_single(black_box(hash))));
+  }
+  group.finish();
+}
+
+criterion_group!(benches, encode, decode, build, min_length, alphabets);
+criterion_main!(benches);
@@ -0,0 +1,212 @@
+//! Derive macros for the `hashids` crate, use them through its `derive` feature.
+use proc_macro::TokenStream;
+use quote::quote;
+use proc_macro2::TokenTree;
+use quote::format_ident;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Expr, Fields, LitStr};
+
+/// Generates `encode`/`decode` helpers, `Display` and `TryFrom<&str>` for a single field tuple struct wrapping an integer ID.
+///
+/// The codec is given as an expression in the `hashid` attribute, which must evaluate to something dereferencing to a `HashidCodec`,
+/// such as a function returning `&'static HashidCodec`:
+/// ```ignore
+/// #[derive(HashidId)]
+/// #[hashid(codec = "user_codec()")]
+/// struct UserId(i64);
+/// ```
+#[proc_macro_derive(HashidId, attributes(hashid))]
+pub fn derive_hashid_id(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  match expand_hashid_id(input) {
+    Ok(tokens) => tokens.into(),
+    Err(err) => err.to_compile_error().into()
+  }
+}
+
+fn expand_hashid_id(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+  let name = &input.ident;
+  let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+  let inner = match &input.data {
+    Data::Struct(data) => match &data.fields {
+      Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+      _ => return Err(syn::Error::new_spanned(&input.ident, "HashidId can only be derived for tuple structs with a single field, like `struct UserId(i64);`"))
+    },
+    _ => return Err(syn::Error::new_spanned(&input.ident, "HashidId can only be derived for structs"))
+  };
+
+  let codec = codec_attribute(&input.attrs)?
+    .ok_or_else(|| syn::Error::new_spanned(&input.ident, "missing `#[hashid(codec = \"...\")]` attribute naming the codec to use"))?;
+
+  Ok(quote! {
+    impl #impl_generics #name #type_generics #where_clause {
+      /// Encodes the ID with the codec given in the `hashid` attribute.
+      pub fn encode(&self) -> ::std::result::Result<::std::string::String, ::hashids::Error> {
+        let codec: &::hashids::HashidCodec = &#codec;
+        codec.encode(self.0)
+      }
+
+      /// Decodes a hash made of a single ID with the codec given in the `hashid` attribute.
+      pub fn decode(hash: &str) -> ::std::result::Result<Self, ::hashids::Error> {
+        let codec: &::hashids::HashidCodec = &#codec;
+        let ids = codec.decode_as::<#inner>(hash)?;
+        match ids.as_slice() {
+          [id] => ::std::result::Result::Ok(#name(*id)),
+          _ => ::std::result::Result::Err(::hashids::Error::UnexpectedNumberCount { expected: 1, got: ids.len() })
+        }
+      }
+    }
+
+    impl #impl_generics ::std::fmt::Display for #name #type_generics #where_clause {
+      fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        match self.encode() {
+          ::std::result::Result::Ok(hash) => f.write_str(&hash),
+          ::std::result::Result::Err(_) => ::std::result::Result::Err(::std::fmt::Error)
+        }
+      }
+    }
+
+    impl #impl_generics ::std::convert::TryFrom<&str> for #name #type_generics #where_clause {
+      type Error = ::hashids::Error;
+
+      fn try_from(hash: &str) -> ::std::result::Result<Self, Self::Error> {
+        Self::decode(hash)
+      }
+    }
+  })
+}
+
+/// Implements serde's `Serialize` and `Deserialize` for a struct, serializing the fields marked with `#[hashid(codec = "...")]` as hashes.
+///
+/// Marked fields must be `u32`, `u64`, `u128`, `i32`, `i64` or options of them, and the codec is an expression as with `HashidId`.
+/// The other fields are serialized as usual, and `#[serde(...)]` attributes on the struct and its fields are kept,
+/// so don't also derive `Serialize` and `Deserialize`. Add `#[serde(default)]` to optional IDs that may be missing.
+/// ```ignore
+/// #[derive(HashidSerde)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Order {
+///   #[hashid(codec = "ORDER_CODEC")]
+///   id: i64,
+///   #[hashid(codec = "USER_CODEC")]
+///   customer_id: i64,
+///   total: u32
+/// }
+/// ```
+#[proc_macro_derive(HashidSerde, attributes(hashid, serde))]
+pub fn derive_hashid_serde(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  match expand_hashid_serde(input) {
+    Ok(tokens) => tokens.into(),
+    Err(err) => err.to_compile_error().into()
+  }
+}
+
+fn expand_hashid_serde(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+  let name = &input.ident;
+  if !input.generics.params.is_empty() {
+    return Err(syn::Error::new_spanned(&input.generics, "HashidSerde can't be derived for generic structs"))
+  }
+  let fields = match &input.data {
+    Data::Struct(data) => match &data.fields {
+      Fields::Named(fields) => &fields.named,
+      _ => return Err(syn::Error::new_spanned(&input.ident, "HashidSerde can only be derived for structs with named fields"))
+    },
+    _ => return Err(syn::Error::new_spanned(&input.ident, "HashidSerde can only be derived for structs"))
+  };
+
+  // The copy keeps the name of the struct, for the formats that use it.
+  let container_attrs: Vec<&Attribute> = input.attrs.iter().filter(|attr| attr.path().is_ident("serde")).collect();
+  let rename = if container_attrs.iter().any(|attr| renames(attr)) {
+    None
+  } else {
+    let name = name.to_string();
+    Some(quote! { #[serde(rename = #name)] })
+  };
+
+  let mut shadow_fields = Vec::new();
+  let mut functions = Vec::new();
+  for field in fields {
+    let ident = field.ident.as_ref().expect("named field");
+    let ty = &field.ty;
+    let attrs: Vec<&Attribute> = field.attrs.iter().filter(|attr| attr.path().is_ident("serde")).collect();
+    match codec_attribute(&field.attrs)? {
+      Some(codec) => {
+        let serialize = format_ident!("__serialize_{}", ident);
+        let deserialize = format_ident!("__deserialize_{}", ident);
+        let (serialize_path, deserialize_path) = (serialize.to_string(), deserialize.to_string());
+        shadow_fields.push(quote! {
+          #(#attrs)*
+          #[serde(serialize_with = #serialize_path, deserialize_with = #deserialize_path)]
+          #ident: #ty
+        });
+        functions.push(quote! {
+          fn #serialize<S: serde::Serializer>(id: &#ty, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+            let codec: &::hashids::HashidCodec = &#codec;
+            HashidField::serialize_hashid(id, codec, serializer)
+          }
+
+          fn #deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<#ty, D::Error> {
+            let codec: &::hashids::HashidCodec = &#codec;
+            <#ty as HashidField>::deserialize_hashid(codec, deserializer)
+          }
+        });
+      },
+      None => shadow_fields.push(quote! { #(#attrs)* #ident: #ty })
+    }
+  }
+
+  // serde's remote derive generates the (de)serialization of the struct's fields through a copy of it.
+  let remote = name.to_string();
+  Ok(quote! {
+    const _: () = {
+      use ::hashids::__private::{serde, HashidField};
+
+      #(#functions)*
+
+      #[derive(serde::Serialize, serde::Deserialize)]
+      #[serde(crate = "::hashids::__private::serde", remote = #remote)]
+      #rename
+      #(#container_attrs)*
+      struct Shadow {
+        #(#shadow_fields),*
+      }
+
+      impl serde::Serialize for #name {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+          Shadow::serialize(self, serializer)
+        }
+      }
+
+      impl<'de> serde::Deserialize<'de> for #name {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+          Shadow::deserialize(deserializer)
+        }
+      }
+    };
+  })
+}
+
+/// Whether a `#[serde(...)]` attribute sets `rename`, which can only be set once.
+fn renames(attr: &Attribute) -> bool {
+  match attr.meta.require_list() {
+    Ok(list) => list.tokens.clone().into_iter().any(|token| matches!(token, TokenTree::Ident(ident) if ident == "rename")),
+    Err(_) => false
+  }
+}
+
+/// Reads the expression in `#[hashid(codec = "...")]`.
+fn codec_attribute(attrs: &[Attribute]) -> syn::Result<Option<Expr>> {
+  let mut codec = None;
+  for attr in attrs.iter().filter(|attr| attr.path().is_ident("hashid")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("codec") {
+        let value: LitStr = meta.value()?.parse()?;
+        codec = Some(value.parse::<Expr>()?);
+        Ok(())
+      } else {
+        Err(meta.error("unsupported hashid attribute, expected `codec`"))
+      }
+    })?;
+  }
+  Ok(codec)
+}
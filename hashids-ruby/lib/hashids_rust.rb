@@ -0,0 +1 @@
+require "hashids_rust/hashids_rust"
@@ -0,0 +1,68 @@
+//! A Ruby native extension wrapping the codec, so Ruby applications produce the same hashes as Rust services.
+//!
+//! Building it needs Ruby and its headers, which is why this crate isn't part of the workspace. It depends on the
+//! crate through its path, so the `hashids_rust` gem is installed from the repository, with Bundler:
+//! ```ruby
+//! gem "hashids_rust", git: "https://github.com/charsyam/hashids_rust", glob: "hashids-ruby/*.gemspec"
+//! ```
+//! Then:
+//! ```ruby
+//! require "hashids_rust"
+//! codec = HashidsRust::Codec.new("this is my salt")
+//! codec.encode(12345) # => "NkK9"
+//! codec.decode("NkK9") # => [12345]
+//! HashidsRust::Codec.new("this is my salt", alphabet: "0123456789abcdef", min_length: 8)
+//! ```
+//! Failures raise `HashidsRust::Error`, with the message of the `hashids::Error`.
+use hashids::HashidBuilder;
+use magnus::scan_args::{get_kwargs, scan_args};
+use magnus::value::Lazy;
+use magnus::{function, method, prelude::*, Error, ExceptionClass, RHash, Ruby, Value};
+
+static ERROR: Lazy<ExceptionClass> = Lazy::new(|ruby| {
+  ruby.define_module("HashidsRust").unwrap().const_get("Error").unwrap()
+});
+
+/// A codec, exported as `HashidsRust::Codec`.
+#[magnus::wrap(class = "HashidsRust::Codec", free_immediately, size)]
+struct Codec(hashids::HashidCodec);
+
+impl Codec {
+  /// `Codec.new(salt, alphabet: nil, min_length: nil)`, with the default alphabet and minimum length when they are `nil`.
+  fn new(ruby: &Ruby, args: &[Value]) -> Result<Codec, Error> {
+    let args = scan_args::<(String,), (), (), (), RHash, ()>(args)?;
+    let (salt,) = args.required;
+    let kwargs = get_kwargs::<_, (), (Option<Option<String>>, Option<Option<usize>>), ()>(args.keywords, &[], &["alphabet", "min_length"])?;
+    let (alphabet, min_length) = kwargs.optional;
+
+    let mut builder = HashidBuilder::new().with_string_salt(salt);
+    if let Some(alphabet) = alphabet.flatten() { builder = builder.with_alphabet(alphabet) }
+    if let Some(min_length) = min_length.flatten() { builder = builder.with_length(min_length) }
+    builder.build().map(Codec).map_err(|err| error(ruby, err))
+  }
+
+  /// Encodes an ID, which must be a non-negative Integer fitting in 64 bits.
+  fn encode(ruby: &Ruby, rb_self: &Codec, id: u64) -> Result<String, Error> {
+    rb_self.0.encode(id).map_err(|err| error(ruby, err))
+  }
+
+  /// Decodes a hash to an Array of its numbers.
+  fn decode(ruby: &Ruby, rb_self: &Codec, hash: String) -> Result<Vec<u64>, Error> {
+    rb_self.0.decode_as::<u64>(&hash).map_err(|err| error(ruby, err))
+  }
+}
+
+fn error(ruby: &Ruby, err: hashids::Error) -> Error {
+  Error::new(ruby.get_inner(&ERROR), err.to_string())
+}
+
+#[magnus::init]
+fn init(ruby: &Ruby) -> Result<(), Error> {
+  let module = ruby.define_module("HashidsRust")?;
+  module.define_error("Error", ruby.exception_standard_error())?;
+  let class = module.define_class("Codec", ruby.class_object())?;
+  class.define_singleton_method("new", function!(Codec::new, -1))?;
+  class.define_method("encode", method!(Codec::encode, 1))?;
+  class.define_method("decode", method!(Codec::decode, 1))?;
+  Ok(())
+}
@@ -0,0 +1,72 @@
+#ifndef HASHIDS_H
+#define HASHIDS_H
+
+/* Generated by cbindgen from src/ffi.rs, don't edit it by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * A codec, only handled through pointers.
+ */
+typedef struct HashidsCodec HashidsCodec;
+
+/**
+ * Builds a codec, returning `NULL` if the settings are invalid.
+ *
+ * `alphabet` may be `NULL` for the default one, and `min_length` is 4 by default in other interfaces.
+ *
+ * # Safety
+ * `salt` must be a NUL-terminated string, and `alphabet` one too or `NULL`.
+ */
+struct HashidsCodec *hashids_new(const char *salt,
+                                 const char *alphabet,
+                                 size_t min_length);
+
+/**
+ * Encodes an ID, returning a string to free with `hashids_string_free`, or `NULL` on failure.
+ *
+ * # Safety
+ * `codec` must come from `hashids_new`, and not be freed yet.
+ */
+char *hashids_encode(const struct HashidsCodec *codec, uint64_t id);
+
+/**
+ * Decodes a hash, writing up to `capacity` of its numbers to `numbers`.
+ *
+ * Returns how many numbers the hash holds, which may be more than `capacity` in which case only the first ones
+ * are written, or `-1` on failure.
+ *
+ * # Safety
+ * `codec` must come from `hashids_new`, and not be freed yet. `hash` must be a NUL-terminated string, and
+ * `numbers` must point to `capacity` writable integers, or may be `NULL` if `capacity` is 0.
+ */
+ptrdiff_t hashids_decode(const struct HashidsCodec *codec,
+                         const char *hash,
+                         uint64_t *numbers,
+                         size_t capacity);
+
+/**
+ * The message of the last failure on this thread, or `NULL` if nothing failed yet.
+ *
+ * The string belongs to the library, and stays valid until the next failure on this thread.
+ */
+const char *hashids_last_error(void);
+
+/**
+ * Frees a string returned by `hashids_encode`. Does nothing with `NULL`.
+ *
+ * # Safety
+ * `hash` must come from `hashids_encode`, and not be freed yet.
+ */
+void hashids_string_free(char *hash);
+
+/**
+ * Frees a codec. Does nothing with `NULL`.
+ *
+ * # Safety
+ * `codec` must come from `hashids_new`, and not be freed yet.
+ */
+void hashids_free(struct HashidsCodec *codec);
+
+#endif  /* HASHIDS_H */
@@ -0,0 +1,64 @@
+//! Ready-made alphabets, to use with `HashidBuilder::with_alphabet` or `HashidBuilder::with_preset`.
+//!
+//! All of them are at least 16 unique ASCII characters, so they build with the default separators and guards.
+//! Those that lack some of the default separators (`cfhistuCFHISTU`) get separators taken from the alphabet instead.
+
+/// The default alphabet of this crate and of the reference implementations.
+pub const DEFAULT: &str = crate::DEFAULT_ALPHABET;
+/// Digits, uppercase then lowercase letters.
+pub const BASE62: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+/// Digits and lowercase letters.
+pub const BASE36_LOWER: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+/// The Bitcoin base58 alphabet, without `0`, `O`, `I` and `l`, which are easily mistaken for one another.
+pub const BASE58_NO_LOOKALIKES: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// Douglas Crockford's base32, without `I`, `L`, `O` and `U`, for codes read out loud or typed by people.
+pub const CROCKFORD32: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// The "extended hex" base32 alphabet of RFC 4648, in lowercase.
+pub const LOWER_HEX_EXTENDED: &str = "0123456789abcdefghijklmnopqrstuv";
+/// The URL and filename safe base64 alphabet of RFC 4648.
+pub const URL_SAFE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// One of the alphabets of this module, to pass to `HashidBuilder::with_preset`.
+/// ```
+/// use hashids::{HashidBuilder, alphabet::Preset};
+/// let codec = HashidBuilder::new().with_salt("my salt").with_preset(Preset::Crockford32).build().unwrap();
+/// let hash = codec.encode(123456789).unwrap();
+/// assert!(hash.chars().all(|c| hashids::alphabet::CROCKFORD32.contains(c)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Preset {
+  Default,
+  Base62,
+  Base36Lower,
+  Base58NoLookalikes,
+  Crockford32,
+  LowerHexExtended,
+  UrlSafe
+}
+
+impl Preset {
+  /// Every preset, for iterating over them.
+  pub const ALL: [Preset; 7] = [
+    Preset::Default,
+    Preset::Base62,
+    Preset::Base36Lower,
+    Preset::Base58NoLookalikes,
+    Preset::Crockford32,
+    Preset::LowerHexExtended,
+    Preset::UrlSafe
+  ];
+
+  /// The characters of the alphabet.
+  pub fn alphabet(self) -> &'static str {
+    match self {
+      Preset::Default => DEFAULT,
+      Preset::Base62 => BASE62,
+      Preset::Base36Lower => BASE36_LOWER,
+      Preset::Base58NoLookalikes => BASE58_NO_LOOKALIKES,
+      Preset::Crockford32 => CROCKFORD32,
+      Preset::LowerHexExtended => LOWER_HEX_EXTENDED,
+      Preset::UrlSafe => URL_SAFE
+    }
+  }
+}
@@ -0,0 +1,56 @@
+//! Measuring the codec on the local machine, to compare configurations.
+use std::error::Error;
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use hashids::HashidCodec;
+
+use crate::vectors::SplitMix64;
+
+/// How many different IDs are encoded and decoded, over and over.
+const SAMPLE_SIZE: usize = 1024;
+
+#[derive(Args)]
+pub struct BenchArgs {
+  /// How long to measure encoding, then decoding, in milliseconds
+  #[arg(long, value_name = "MS", default_value_t = 1000)]
+  duration_ms: u64,
+  /// The largest ID encoded
+  #[arg(long, default_value_t = u32::MAX as u64)]
+  max: u64
+}
+
+/// Prints the operations per second and the time per operation of `encode`, then `decode`.
+pub fn run(codec: &HashidCodec, args: &BenchArgs) -> Result<(), Box<dyn Error>> {
+  let mut random = SplitMix64(0);
+  let ids: Vec<u64> = (0..SAMPLE_SIZE).map(|_| random.below(args.max)).collect();
+  let hashes = codec.encode_many(&ids)?;
+  let duration = Duration::from_millis(args.duration_ms);
+
+  let mut out = io::stdout().lock();
+  let (ops, elapsed) = measure(duration, || for id in &ids { black_box(codec.encode(black_box(*id)).ok()); });
+  report(&mut out, "encode", ops, elapsed)?;
+  let (ops, elapsed) = measure(duration, || for hash in &hashes { black_box(codec.decode_u128(black_box(hash)).ok()); });
+  report(&mut out, "decode", ops, elapsed)?;
+  Ok(())
+}
+
+/// Runs batches of `SAMPLE_SIZE` operations until the duration is over, at least once.
+fn measure(duration: Duration, mut batch: impl FnMut()) -> (u64, Duration) {
+  let start = Instant::now();
+  let mut ops = 0;
+  loop {
+    batch();
+    ops += SAMPLE_SIZE as u64;
+    let elapsed = start.elapsed();
+    if elapsed >= duration { return (ops, elapsed) }
+  }
+}
+
+fn report(out: &mut impl Write, operation: &str, ops: u64, elapsed: Duration) -> io::Result<()> {
+  let per_second = ops as f64 / elapsed.as_secs_f64();
+  let nanos_per_op = elapsed.as_nanos() as f64 / ops as f64;
+  writeln!(out, "{}  {:>12.0} ops/s  {:>8.1} ns/op", operation, per_second, nanos_per_op)
+}
@@ -0,0 +1,209 @@
+//! Converting a column or field of whole files at once, for data exports and shell pipelines.
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use hashids::HashidCodec;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::Value;
+
+use crate::Direction;
+
+/// Converting a CSV file instead of the arguments.
+#[derive(Args)]
+pub struct CsvArgs {
+  /// Converts a column of this CSV file instead of the arguments
+  #[arg(long, value_name = "FILE", requires = "column", conflicts_with_all = ["inputs", "jsonl"])]
+  pub csv: Option<PathBuf>,
+  /// The header of the column to convert
+  #[arg(long, requires = "csv", conflicts_with = "inputs")]
+  column: Option<String>,
+  /// Writes the CSV to this file instead of the standard output
+  #[arg(long, value_name = "FILE", requires = "csv")]
+  out: Option<PathBuf>,
+  /// Adds the converted values as a new column with this header, instead of replacing the column
+  #[arg(long, value_name = "HEADER", requires = "csv")]
+  append: Option<String>
+}
+
+/// How bulk conversions deal with invalid values, and report on their progress.
+#[derive(Args)]
+pub struct JobArgs {
+  /// Leaves the rows or records that can't be converted out of the output, reporting them, instead of stopping
+  #[arg(long, conflicts_with = "inputs")]
+  skip_invalid: bool,
+  /// Prints the rows processed, the failures and the throughput once done
+  #[arg(long, conflicts_with = "inputs")]
+  stats: bool
+}
+
+/// Rows are only counted on the progress bar every so often, to keep it cheap.
+const PROGRESS_INTERVAL: u64 = 1024;
+
+/// The state of a bulk conversion, with a progress bar drawn on the standard error when it's a terminal.
+struct Job<'a> {
+  args: &'a JobArgs,
+  bar: ProgressBar,
+  rows: u64,
+  failures: u64,
+  start: Instant
+}
+
+impl<'a> Job<'a> {
+  /// A job over `len` bytes, or an unknown amount of input.
+  fn new(args: &'a JobArgs, len: Option<u64>) -> Job<'a> {
+    let bar = match len {
+      Some(len) => ProgressBar::new(len).with_style(ProgressStyle::with_template(
+        "{bar:40} {bytes}/{total_bytes} {msg} ({eta})").expect("the template is valid")),
+      None => ProgressBar::new_spinner().with_style(ProgressStyle::with_template(
+        "{spinner} {msg} {elapsed}").expect("the template is valid"))
+    };
+    bar.enable_steady_tick(Duration::from_millis(200));
+    Job { args, bar, rows: 0, failures: 0, start: Instant::now() }
+  }
+
+  /// Counts a row, `position` being how many bytes were read so far.
+  fn row(&mut self, position: u64) {
+    self.rows += 1;
+    if self.rows % PROGRESS_INTERVAL == 0 {
+      self.bar.set_position(position);
+      self.bar.set_message(format!("{} rows", self.rows));
+    }
+  }
+
+  /// Reports a row that couldn't be converted, failing the job unless invalid rows are skipped.
+  fn failed(&mut self, line: u64, err: Box<dyn Error>) -> Result<(), Box<dyn Error>> {
+    if !self.args.skip_invalid {
+      self.bar.finish_and_clear();
+      return Err(format!("line {}: {}", line, err).into())
+    }
+    self.failures += 1;
+    self.bar.suspend(|| eprintln!("hashids: line {}: {}", line, err));
+    Ok(())
+  }
+
+  /// Clears the progress bar and prints the stats if asked to, returning `false` if rows were skipped.
+  fn finish(self) -> bool {
+    self.bar.finish_and_clear();
+    if self.args.stats {
+      let elapsed = self.start.elapsed();
+      eprintln!("{} rows processed, {} failed, in {:.2}s ({:.0} rows/s)",
+        self.rows, self.failures, elapsed.as_secs_f64(), self.rows as f64 / elapsed.as_secs_f64());
+    }
+    self.failures == 0
+  }
+}
+
+/// Streams the rows of the CSV file to the output, converting the column of each row.
+///
+/// Stops at the first value that can't be converted, unless invalid rows are skipped.
+/// Returns `false` if some were.
+pub fn csv(codec: &HashidCodec, direction: Direction, args: &CsvArgs, job: &JobArgs) -> Result<bool, Box<dyn Error>> {
+  let (Some(input), Some(column)) = (&args.csv, &args.column) else { unreachable!("required by clap") };
+  let mut job = Job::new(job, Some(std::fs::metadata(input)?.len()));
+  let mut reader = csv::Reader::from_path(input)?;
+  let out: Box<dyn Write> = match &args.out {
+    Some(path) => Box::new(File::create(path)?),
+    None => Box::new(io::stdout().lock())
+  };
+  let mut writer = csv::Writer::from_writer(out);
+
+  let mut headers = reader.headers()?.clone();
+  let index = headers.iter().position(|header| header == column)
+    .ok_or_else(|| format!("no column named `{}` in {}", column, input.display()))?;
+  if let Some(header) = &args.append { headers.push_field(header) }
+  writer.write_record(&headers)?;
+
+  let mut record = csv::StringRecord::new();
+  let mut converted = csv::StringRecord::new();
+  while reader.read_record(&mut record)? {
+    job.row(reader.position().byte());
+    let value = match direction.convert(codec, &record[index]) {
+      Ok(value) => value,
+      Err(err) => {
+        job.failed(record.position().map_or(0, |p| p.line()), err)?;
+        continue
+      }
+    };
+    converted.clear();
+    match args.append {
+      Some(_) => {
+        converted.extend(&record);
+        converted.push_field(&value);
+      }
+      None => converted.extend(record.iter().enumerate().map(|(i, field)| if i == index { &value } else { field }))
+    }
+    writer.write_record(&converted)?;
+  }
+  writer.flush()?;
+  Ok(job.finish())
+}
+
+/// Converting newline-delimited JSON from the standard input instead of the arguments.
+#[derive(Args)]
+pub struct JsonlArgs {
+  /// Converts a field of the JSON records read from the standard input, one per line, instead of the arguments
+  #[arg(long, requires = "field", conflicts_with = "inputs")]
+  pub jsonl: bool,
+  /// The top-level field to convert in each record
+  #[arg(long, requires = "jsonl", conflicts_with = "inputs")]
+  field: Option<String>
+}
+
+/// Streams the records of the standard input to the standard output, converting the field of each record.
+///
+/// Blank lines are skipped, and null fields are left as they are. Stops at the first value that can't be converted,
+/// unless invalid records are skipped. Returns `false` if some were.
+pub fn jsonl(codec: &HashidCodec, direction: Direction, args: &JsonlArgs, job: &JobArgs) -> Result<bool, Box<dyn Error>> {
+  let Some(field) = &args.field else { unreachable!("required by clap") };
+  let mut job = Job::new(job, None);
+  let mut out = BufWriter::new(io::stdout().lock());
+  let mut position = 0;
+  for (number, line) in io::stdin().lock().lines().enumerate() {
+    let line = line?;
+    position += line.len() as u64 + 1;
+    if line.trim().is_empty() { continue }
+    job.row(position);
+    let convert_line = || -> Result<Value, Box<dyn Error>> {
+      let mut record: Value = serde_json::from_str(&line)?;
+      let value = record.as_object_mut().ok_or("the record isn't a JSON object")?
+        .get_mut(field).ok_or_else(|| format!("no field named `{}`", field))?;
+      if !value.is_null() { *value = convert_json(codec, direction, value)? }
+      Ok(record)
+    };
+    match convert_line() {
+      Ok(record) => {
+        serde_json::to_writer(&mut out, &record)?;
+        out.write_all(b"\n")?;
+      }
+      Err(err) => job.failed(number as u64 + 1, err)?
+    }
+  }
+  out.flush()?;
+  Ok(job.finish())
+}
+
+/// Encodes IDs given as numbers or strings, and decodes hashes to a number, or an array when they hold several.
+///
+/// Numbers that don't fit in a `u64` are written as strings, as JSON parsers often lose their precision.
+fn convert_json(codec: &HashidCodec, direction: Direction, value: &Value) -> Result<Value, Box<dyn Error>> {
+  match (direction, value) {
+    (Direction::Encode, Value::Number(id)) => {
+      let id = id.as_u64().ok_or_else(|| format!("`{}` is not a valid ID", id))?;
+      Ok(codec.encode(id)?.into())
+    }
+    (Direction::Encode, Value::String(id)) => Ok(direction.convert(codec, id)?.into()),
+    (Direction::Decode, Value::String(hash)) => {
+      let mut numbers: Vec<Value> = codec.decode_u128(hash)?.into_iter()
+        .map(|number| u64::try_from(number).map_or_else(|_| number.to_string().into(), Value::from))
+        .collect();
+      Ok(if numbers.len() == 1 { numbers.remove(0) } else { numbers.into() })
+    }
+    (Direction::Encode, _) => Err(format!("`{}` is not a valid ID", value).into()),
+    (Direction::Decode, _) => Err(format!("`{}` is not a hash", value).into())
+  }
+}
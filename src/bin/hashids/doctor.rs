@@ -0,0 +1,54 @@
+//! A report on the configuration, to check it before deploying it.
+use std::error::Error;
+use std::io::{self, Write};
+
+use hashids::{DiagnosticKind, HashidBuilder, Severity};
+
+/// What the report is made of, with the diagnostics each check is about.
+const CHECKS: [(&str, &[DiagnosticKind]); 3] = [
+  ("salt", &[DiagnosticKind::MissingSalt, DiagnosticKind::NonAsciiSalt, DiagnosticKind::ShortSalt, DiagnosticKind::WeakSalt]),
+  ("alphabet", &[DiagnosticKind::NonAsciiAlphabet, DiagnosticKind::AlphabetTooShort, DiagnosticKind::SmallEffectiveAlphabet]),
+  ("min length", &[DiagnosticKind::LargeMinLength])
+];
+
+/// Prints a pass, warn or fail line for each check, then whether the codec can be built.
+///
+/// Returns `false` if anything failed, warnings alone don't make the report fail.
+pub fn run(builder: HashidBuilder) -> Result<bool, Box<dyn Error>> {
+  let diagnostics = builder.validate();
+  let mut out = io::stdout().lock();
+  let mut passed = true;
+
+  for (check, kinds) in CHECKS.iter() {
+    let found: Vec<_> = diagnostics.iter().filter(|diagnostic| kinds.contains(&diagnostic.kind)).collect();
+    if found.is_empty() {
+      writeln!(out, "pass  {}", check)?;
+    }
+    for diagnostic in found {
+      writeln!(out, "{}  {}: {}", status(diagnostic.severity), check, diagnostic.message)?;
+      passed &= diagnostic.severity != Severity::Error;
+    }
+  }
+  // Diagnostics added to the library after this report was written
+  for diagnostic in diagnostics.iter().filter(|diagnostic| !CHECKS.iter().any(|(_, kinds)| kinds.contains(&diagnostic.kind))) {
+    writeln!(out, "{}  {}", status(diagnostic.severity), diagnostic.message)?;
+    passed &= diagnostic.severity != Severity::Error;
+  }
+
+  // Some settings, such as the maximum length, are only checked when building
+  match builder.build() {
+    Ok(_) => writeln!(out, "pass  codec")?,
+    Err(err) => {
+      writeln!(out, "fail  codec: {}", err)?;
+      passed = false;
+    }
+  }
+  Ok(passed)
+}
+
+fn status(severity: Severity) -> &'static str {
+  match severity {
+    Severity::Warning => "warn",
+    Severity::Error => "fail"
+  }
+}
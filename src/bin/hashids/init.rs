@@ -0,0 +1,115 @@
+//! A wizard writing a new configuration, with a strong random salt.
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use hashids::alphabet::Preset;
+use hashids::{HashidBuilder, HashidSalt};
+
+const DEFAULT_MIN_LENGTH: usize = 8;
+
+#[derive(Args)]
+pub struct InitArgs {
+  /// Where to write the configuration, without the salt
+  #[arg(long, value_name = "FILE", default_value = "hashids.toml")]
+  out: PathBuf,
+  /// Where to write the `.env` snippet setting the salt
+  #[arg(long, value_name = "FILE", default_value = "hashids.env")]
+  env_out: PathBuf,
+  /// The alphabet preset, by name or number, instead of asking for it
+  #[arg(long)]
+  preset: Option<String>,
+  /// The minimum length of the hashes, instead of asking for it
+  #[arg(long)]
+  min_length: Option<usize>,
+  /// How many characters the generated salt has
+  #[arg(long, default_value_t = 32)]
+  salt_length: usize,
+  /// Overwrites the files if they exist
+  #[arg(long)]
+  force: bool
+}
+
+/// Asks for what wasn't given as options, then writes the configuration and the `.env` snippet.
+///
+/// The salt only goes to the snippet, so the configuration can be committed along with the code.
+pub fn run(args: &InitArgs) -> Result<(), Box<dyn Error>> {
+  // Checked before asking anything, and so that neither file is written if one of them exists
+  if let Some(existing) = [&args.out, &args.env_out].iter().find(|path| !args.force && path.exists()) {
+    return Err(format!("{} already exists, use --force to overwrite it", existing.display()).into())
+  }
+  let mut input = io::stdin().lock().lines();
+  let mut out = io::stdout().lock();
+
+  let preset = match &args.preset {
+    Some(preset) => parse_preset(preset)?,
+    None => {
+      writeln!(out, "Alphabet presets:")?;
+      for (i, preset) in Preset::ALL.iter().enumerate() {
+        writeln!(out, "  {}. {:?} ({})", i + 1, preset, preset.alphabet())?;
+      }
+      loop {
+        let answer = ask(&mut input, &mut out, "Alphabet preset", "1")?;
+        match parse_preset(&answer) {
+          Ok(preset) => break preset,
+          Err(err) => writeln!(out, "{}", err)?
+        }
+      }
+    }
+  };
+  let min_length = match args.min_length {
+    Some(min_length) => min_length,
+    None => loop {
+      let answer = ask(&mut input, &mut out, "Minimum length of the hashes", &DEFAULT_MIN_LENGTH.to_string())?;
+      match answer.parse() {
+        Ok(min_length) => break min_length,
+        Err(_) => writeln!(out, "`{}` is not a number", answer)?
+      }
+    }
+  };
+
+  let salt = HashidSalt::generate(args.salt_length);
+  let dotenv = salt.to_dotenv();
+  // Catches settings the codec refuses before writing anything
+  HashidBuilder::new().with_hashid_salt(salt).with_preset(preset).with_length(min_length).build()?;
+
+  let config = format!("# The salt is read from the HASHID_SALT environnment variable\nalphabet = {}\nmin_length = {}\n",
+    toml::Value::from(preset.alphabet()), min_length);
+  write_new(&args.out, &config, args.force, false)?;
+  write_new(&args.env_out, &format!("{}\n", dotenv), args.force, true)?;
+  writeln!(out, "Wrote {}, and the salt to {}: add it to your environnment, and keep it secret.",
+    args.out.display(), args.env_out.display())?;
+  Ok(())
+}
+
+/// Prints the question and reads the answer, the default being taken for empty answers and at the end of the input.
+fn ask(input: &mut impl Iterator<Item = io::Result<String>>, out: &mut impl Write, question: &str, default: &str) -> io::Result<String> {
+  write!(out, "{} [{}]: ", question, default)?;
+  out.flush()?;
+  let answer = input.next().transpose()?.unwrap_or_default();
+  let answer = answer.trim();
+  Ok(if answer.is_empty() { default.to_string() } else { answer.to_string() })
+}
+
+fn parse_preset(name: &str) -> Result<Preset, String> {
+  Preset::ALL.iter().enumerate()
+    .find(|(i, preset)| name == (i + 1).to_string() || name.eq_ignore_ascii_case(&format!("{:?}", preset)))
+    .map(|(_, preset)| *preset)
+    .ok_or_else(|| format!("no alphabet preset named `{}`", name))
+}
+
+/// Writes the file, only readable by its owner on Unix if it's `secret`.
+fn write_new(path: &Path, contents: &str, overwrite: bool, secret: bool) -> Result<(), Box<dyn Error>> {
+  let mut options = OpenOptions::new();
+  options.write(true);
+  if overwrite { options.create(true).truncate(true); } else { options.create_new(true); }
+  #[cfg(unix)]
+  if secret { std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600); }
+  #[cfg(not(unix))]
+  let _ = secret;
+  let mut file = options.open(path).map_err(|err| format!("can't write {}: {}", path.display(), err))?;
+  file.write_all(contents.as_bytes())?;
+  Ok(())
+}
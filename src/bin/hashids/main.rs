@@ -0,0 +1,250 @@
+//! The `hashids` command line tool, to encode and decode IDs without writing code.
+//!
+//! Requires the `cli` feature.
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::{env, fs};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use hashids::{HashidBuilder, HashidCodec};
+
+mod bench;
+mod bulk;
+mod doctor;
+mod init;
+mod migrate;
+mod repl;
+#[cfg(feature = "serve")]
+mod serve;
+mod vectors;
+
+#[derive(Parser)]
+#[command(name = "hashids", version, about = "Encodes and decodes hashids")]
+struct Cli {
+  #[command(flatten)]
+  codec: CodecArgs,
+  #[command(subcommand)]
+  command: Command
+}
+
+/// The settings of the codec, shared by every subcommand.
+#[derive(Args)]
+struct CodecArgs {
+  /// Reads the settings from a TOML or JSON file, which the other options override
+  #[arg(long, global = true, value_name = "FILE")]
+  config: Option<PathBuf>,
+  /// `-` to read the salt from the first line of the standard input. Without any salt option, it's read from the
+  /// `HASHID_SALT` environnment variable
+  #[arg(long, global = true, conflicts_with_all = ["salt_file", "salt_env"])]
+  salt: Option<String>,
+  /// Reads the salt from the first line of this file
+  #[arg(long, global = true, value_name = "FILE", conflicts_with = "salt_env")]
+  salt_file: Option<PathBuf>,
+  /// Reads the salt from this environnment variable instead of `HASHID_SALT`
+  #[arg(long, global = true, value_name = "NAME")]
+  salt_env: Option<String>,
+  /// Accepts the salt itself as the value of `--salt`, which leaks it to the shell history and process listings
+  #[arg(long, global = true, requires = "salt")]
+  allow_salt_arg: bool,
+  /// A custom alphabet, of at least 16 unique characters
+  #[arg(long, global = true)]
+  alphabet: Option<String>,
+  /// The minimum length of the hashes
+  #[arg(long, global = true)]
+  min_length: Option<usize>,
+  /// A prefix added to every hash
+  #[arg(long, global = true)]
+  prefix: Option<String>,
+  /// A suffix added to every hash
+  #[arg(long, global = true)]
+  suffix: Option<String>
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Encodes each ID to a hash, one per line
+  Encode {
+    #[arg(id = "inputs", value_name = "IDS", required_unless_present_any = ["csv", "jsonl"])]
+    ids: Vec<u128>,
+    #[command(flatten)]
+    csv: bulk::CsvArgs,
+    #[command(flatten)]
+    jsonl: bulk::JsonlArgs,
+    #[command(flatten)]
+    job: bulk::JobArgs
+  },
+  /// Decodes each hash to its numbers, one hash per line
+  Decode {
+    #[arg(id = "inputs", value_name = "HASHES", required_unless_present_any = ["csv", "jsonl"])]
+    hashes: Vec<String>,
+    #[command(flatten)]
+    csv: bulk::CsvArgs,
+    #[command(flatten)]
+    jsonl: bulk::JsonlArgs,
+    #[command(flatten)]
+    job: bulk::JobArgs
+  },
+  /// Starts an interactive session, encoding the numbers and decoding the hashes typed
+  Repl,
+  /// Writes a new configuration file and a `.env` snippet with a random salt, asking for the other settings
+  Init(init::InitArgs),
+  /// Checks the configuration, reporting weak or surprising settings
+  Doctor,
+  /// Re-encodes hashes made with a configuration file with another one, writing the mapping as CSV
+  Migrate(migrate::MigrateArgs),
+  /// Generates JSON test vectors for the configuration, to check other implementations against
+  Vectors(vectors::VectorsArgs),
+  /// Measures the encoding and decoding throughput of the configuration on this machine
+  Bench(bench::BenchArgs),
+  /// Serves `/encode/{id}` and `/decode/{hash}` JSON endpoints over HTTP
+  #[cfg(feature = "serve")]
+  Serve(serve::ServeArgs),
+  /// Inspects the configuration of the codec
+  Config {
+    #[command(subcommand)]
+    command: ConfigCommand
+  }
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+  /// Prints the settings of the codec as JSON, with the salt redacted
+  Show {
+    /// Prints the salt instead of redacting it
+    #[arg(long)]
+    show_salt: bool
+  }
+}
+
+/// Whether values are encoded or decoded.
+#[derive(Clone, Copy)]
+enum Direction {
+  Encode,
+  Decode
+}
+
+impl Direction {
+  /// Converts a single value read from a file, the numbers of a hash being joined with commas.
+  fn convert(self, codec: &HashidCodec, value: &str) -> Result<String, Box<dyn Error>> {
+    match self {
+      Direction::Encode => {
+        let id: u128 = value.trim().parse().map_err(|_| format!("`{}` is not a valid ID", value))?;
+        Ok(codec.encode(id)?)
+      }
+      Direction::Decode => Ok(join(&codec.decode_u128(value.trim())?))
+    }
+  }
+}
+
+impl CodecArgs {
+  /// The salt given through the options, if any.
+  fn salt(&self) -> Result<Option<String>, Box<dyn Error>> {
+    if let Some(path) = &self.salt_file {
+      let contents = fs::read_to_string(path).map_err(|err| format!("can't read {}: {}", path.display(), err))?;
+      return Ok(Some(contents.lines().next().unwrap_or_default().to_string()))
+    }
+    if let Some(name) = &self.salt_env {
+      return Ok(Some(env::var(name).map_err(|_| format!("the `{}` environnment variable isn't set", name))?))
+    }
+    match self.salt.as_deref() {
+      // The rest of the standard input is left for the command
+      Some("-") => {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+      }
+      Some(salt) if self.allow_salt_arg => Ok(Some(salt.to_string())),
+      Some(_) => Err("the salt would leak to the shell history and process listings as an argument, \
+        use `--salt -`, `--salt-file` or `--salt-env` instead, or `--allow-salt-arg` to pass it anyway".into()),
+      None => Ok(None)
+    }
+  }
+
+  fn builder(&self) -> Result<HashidBuilder, Box<dyn Error>> {
+    // Without a salt, the builder falls back to the environnment variable itself
+    let mut builder = match &self.config {
+      Some(path) => HashidBuilder::from_file(path)?,
+      None => HashidBuilder::new()
+    };
+    if let Some(salt) = self.salt()? { builder = builder.with_string_salt(salt) }
+    if let Some(alphabet) = &self.alphabet { builder = builder.with_alphabet(alphabet.clone()) }
+    if let Some(min_length) = self.min_length { builder = builder.with_length(min_length) }
+    if let Some(prefix) = &self.prefix { builder = builder.with_prefix(prefix) }
+    if let Some(suffix) = &self.suffix { builder = builder.with_suffix(suffix) }
+    Ok(builder)
+  }
+
+  fn codec(&self) -> Result<HashidCodec, Box<dyn Error>> {
+    Ok(self.builder()?.build()?)
+  }
+}
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+  match run(cli) {
+    Ok(true) => ExitCode::SUCCESS,
+    Ok(false) => ExitCode::FAILURE,
+    Err(err) => {
+      eprintln!("hashids: {}", err);
+      ExitCode::FAILURE
+    }
+  }
+}
+
+/// Runs the command, returning `Ok(false)` when some inputs couldn't be converted but the others were.
+fn run(cli: Cli) -> Result<bool, Box<dyn Error>> {
+  match &cli.command {
+    // The codec may not build, which the doctor reports instead of stopping at
+    Command::Doctor => return doctor::run(cli.codec.builder()?),
+    Command::Init(args) => return init::run(args).map(|_| true),
+    // Both codecs come from their own files
+    Command::Migrate(args) => return migrate::run(args),
+    _ => {}
+  }
+  let codec = cli.codec.codec()?;
+  let mut out = io::stdout().lock();
+  let mut success = true;
+  match cli.command {
+    Command::Encode { csv, job, .. } if csv.csv.is_some() => success = bulk::csv(&codec, Direction::Encode, &csv, &job)?,
+    Command::Decode { csv, job, .. } if csv.csv.is_some() => success = bulk::csv(&codec, Direction::Decode, &csv, &job)?,
+    Command::Encode { jsonl, job, .. } if jsonl.jsonl => success = bulk::jsonl(&codec, Direction::Encode, &jsonl, &job)?,
+    Command::Decode { jsonl, job, .. } if jsonl.jsonl => success = bulk::jsonl(&codec, Direction::Decode, &jsonl, &job)?,
+    Command::Encode { ids, .. } => for id in ids {
+      match codec.encode(id) {
+        Ok(hash) => writeln!(out, "{}", hash)?,
+        Err(err) => {
+          eprintln!("hashids: {}: {}", id, err);
+          success = false;
+        }
+      }
+    },
+    // Bad hashes copied from logs are reported, without stopping at them
+    Command::Decode { hashes, .. } => for hash in hashes {
+      match codec.decode_u128(&hash) {
+        Ok(numbers) => writeln!(out, "{}", join(&numbers))?,
+        Err(err) => {
+          eprintln!("hashids: {}: {}", hash, err);
+          success = false;
+        }
+      }
+    },
+    Command::Repl => repl::run(&codec)?,
+    Command::Vectors(args) => vectors::run(&codec, &args)?,
+    Command::Bench(args) => bench::run(&codec, &args)?,
+    #[cfg(feature = "serve")]
+    Command::Serve(args) => serve::run(codec, &args)?,
+    Command::Init(_) | Command::Doctor | Command::Migrate(_) => unreachable!("handled without building the codec"),
+    Command::Config { command: ConfigCommand::Show { show_salt } } => {
+      let mut config = serde_json::to_value(codec.to_config())?;
+      if !show_salt { config["salt"] = "<redacted>".into() }
+      writeln!(out, "{}", serde_json::to_string_pretty(&config)?)?;
+    }
+  }
+  Ok(success)
+}
+
+fn join(numbers: &[u128]) -> String {
+  numbers.iter().map(u128::to_string).collect::<Vec<_>>().join(",")
+}
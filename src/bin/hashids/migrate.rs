@@ -0,0 +1,60 @@
+//! Re-encoding hashes with new settings, for rotating a salt.
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use clap::Args;
+use hashids::HashidBuilder;
+
+/// The settings before and after the migration.
+#[derive(Args)]
+pub struct MigrateArgs {
+  /// The configuration file the hashes were made with
+  #[arg(long, value_name = "FILE")]
+  from: PathBuf,
+  /// The configuration file to re-encode the hashes with
+  #[arg(long, value_name = "FILE")]
+  to: PathBuf,
+  /// Reads the hashes from this file instead of the standard input, one per line
+  #[arg(long, value_name = "FILE")]
+  input: Option<PathBuf>,
+  /// Writes the mapping to this file instead of the standard output
+  #[arg(long, value_name = "FILE")]
+  out: Option<PathBuf>
+}
+
+/// Writes a CSV mapping each old hash to the new one.
+///
+/// Every number of a hash is re-encoded in the new one, so hashes holding several numbers keep them all.
+/// Hashes that can't be migrated are reported and left out of the mapping, without stopping at them. Returns `false` if there were any.
+pub fn run(args: &MigrateArgs) -> Result<bool, Box<dyn Error>> {
+  let old = HashidBuilder::from_file(&args.from)?.build().map_err(|err| format!("{}: {}", args.from.display(), err))?;
+  let new = HashidBuilder::from_file(&args.to)?.build().map_err(|err| format!("{}: {}", args.to.display(), err))?;
+  let input: Box<dyn BufRead> = match &args.input {
+    Some(path) => Box::new(BufReader::new(File::open(path)?)),
+    None => Box::new(io::stdin().lock())
+  };
+  let out: Box<dyn Write> = match &args.out {
+    Some(path) => Box::new(File::create(path)?),
+    None => Box::new(io::stdout().lock())
+  };
+  let mut writer = csv::Writer::from_writer(out);
+  writer.write_record(["old", "new"])?;
+
+  let mut success = true;
+  for (number, line) in input.lines().enumerate() {
+    let line = line?;
+    let hash = line.trim();
+    if hash.is_empty() { continue }
+    match old.decode_u128(hash).and_then(|numbers| new.encode_slice(&numbers)) {
+      Ok(migrated) => writer.write_record([hash, &migrated])?,
+      Err(err) => {
+        eprintln!("hashids: line {}: {}: {}", number + 1, hash, err);
+        success = false;
+      }
+    }
+  }
+  writer.flush()?;
+  Ok(success)
+}
@@ -0,0 +1,52 @@
+//! An interactive session converting whatever is typed, for decoding IDs one after the other.
+use std::error::Error;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use hashids::HashidCodec;
+
+use crate::Direction;
+
+/// Reads lines until `exit`, `quit` or the end of the input, converting each word of them.
+///
+/// Numbers are encoded, anything else is decoded. Numbers that are also valid hashes, which happens with
+/// alphabets containing digits, are shown both ways.
+pub fn run(codec: &HashidCodec) -> Result<(), Box<dyn Error>> {
+  let interactive = io::stdin().is_terminal();
+  let mut out = io::stdout().lock();
+  if interactive {
+    writeln!(out, "Type IDs to encode or hashes to decode, `exit` to quit.")?;
+  }
+
+  let mut lines = io::stdin().lock().lines();
+  loop {
+    if interactive {
+      write!(out, "> ")?;
+      out.flush()?;
+    }
+    let Some(line) = lines.next() else { break };
+    let line = line?;
+    if matches!(line.trim(), "exit" | "quit") { break }
+    for word in line.split_whitespace() {
+      for conversion in convert(codec, word) {
+        writeln!(out, "{}", conversion)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Every way the word can be read, as lines to print.
+fn convert(codec: &HashidCodec, word: &str) -> Vec<String> {
+  let show = |arrow, result: Result<String, Box<dyn Error>>| match result {
+    Ok(converted) => format!("{} {} {}", word, arrow, converted),
+    Err(err) => format!("{}: {}", word, err)
+  };
+  if word.parse::<u128>().is_err() {
+    return vec![show("<=", Direction::Decode.convert(codec, word))]
+  }
+  let mut conversions = vec![show("=>", Direction::Encode.convert(codec, word))];
+  if let Ok(numbers) = Direction::Decode.convert(codec, word) {
+    conversions.push(show("<=", Ok(numbers)));
+  }
+  conversions
+}
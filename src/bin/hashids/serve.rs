@@ -0,0 +1,87 @@
+//! A tiny HTTP service, for services written in other languages to share the same codec.
+//!
+//! Requires the `serve` feature.
+use std::convert::TryFrom;
+use std::error::Error;
+use std::sync::Arc;
+use std::thread;
+
+use clap::Args;
+use hashids::HashidCodec;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(Args)]
+pub struct ServeArgs {
+  /// The address to listen on
+  #[arg(long, default_value = "127.0.0.1")]
+  host: String,
+  /// The port to listen on, 0 picking a free one
+  #[arg(long, default_value_t = 8080)]
+  port: u16,
+  /// How many requests are handled at the same time, defaults to the number of CPUs
+  #[arg(long)]
+  threads: Option<usize>
+}
+
+/// Serves `GET /encode/{id}` and `GET /decode/{hash}` until the process is stopped.
+///
+/// Encoding responds with `{"hash": "..."}`, decoding with `{"numbers": [...]}`, numbers too large for a `u64`
+/// being written as strings. Errors respond with `{"error": "...", "code": "..."}`: a 404 Not Found for hashes
+/// that don't decode, as with the axum integration, and a 400 Bad Request for invalid IDs.
+pub fn run(codec: HashidCodec, args: &ServeArgs) -> Result<(), Box<dyn Error>> {
+  let server = Server::http((args.host.as_str(), args.port))
+    .map_err(|err| format!("can't listen on {}:{}: {}", args.host, args.port, err))?;
+  let server = Arc::new(server);
+  // Printed once listening, so scripts starting the service with port 0 know where to reach it
+  println!("listening on http://{}", server.server_addr());
+
+  let codec = Arc::new(codec);
+  let threads = args.threads.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get())).max(1);
+  let workers: Vec<_> = (0..threads).map(|_| {
+    let (server, codec) = (server.clone(), codec.clone());
+    thread::spawn(move || for request in server.incoming_requests() {
+      let (status, body) = respond(&codec, &request);
+      let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+      let response = Response::from_string(body.to_string()).with_status_code(status).with_header(header);
+      // The client hanging up early isn't the service's problem
+      let _ = request.respond(response);
+    })
+  }).collect();
+  for worker in workers {
+    worker.join().map_err(|_| "a worker thread panicked")?;
+  }
+  Ok(())
+}
+
+fn respond(codec: &HashidCodec, request: &Request) -> (u16, Value) {
+  if *request.method() != Method::Get {
+    return (405, json!({ "error": "only GET requests are served" }))
+  }
+  let path = request.url().split('?').next().unwrap_or_default();
+  let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+  match segments[..] {
+    ["encode", id] => match id.parse::<u128>() {
+      Ok(id) => match codec.encode(id) {
+        Ok(hash) => (200, json!({ "hash": hash })),
+        Err(err) => (400, error(&err))
+      },
+      Err(_) => (400, json!({ "error": format!("`{}` is not a valid ID", id) }))
+    },
+    ["decode", hash] => match codec.decode_u128(hash) {
+      Ok(numbers) => {
+        let numbers: Vec<Value> = numbers.into_iter()
+          .map(|number| u64::try_from(number).map_or_else(|_| number.to_string().into(), Value::from))
+          .collect();
+        (200, json!({ "numbers": numbers }))
+      }
+      Err(err) if err.is_invalid_hash() => (404, error(&err)),
+      Err(err) => (500, error(&err))
+    },
+    _ => (404, json!({ "error": "not found, use /encode/{id} or /decode/{hash}" }))
+  }
+}
+
+fn error(err: &hashids::Error) -> Value {
+  json!({ "error": err.to_string(), "code": err.code() })
+}
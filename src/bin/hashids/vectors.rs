@@ -0,0 +1,78 @@
+//! Generating test vectors, for implementations of the scheme in other languages to check themselves against.
+use std::error::Error;
+use std::io::{self, Write};
+
+use clap::Args;
+use hashids::compat::Compat;
+use hashids::{alphabet, Case, HashidBuilder, HashidCodec};
+
+/// Largest integer the JavaScript implementations handle exactly, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+#[derive(Args)]
+pub struct VectorsArgs {
+  /// How many vectors to generate
+  #[arg(long, default_value_t = 100)]
+  count: usize,
+  /// The seed of the generated numbers, the same seed always giving the same vectors
+  #[arg(long, default_value_t = 0)]
+  seed: u64,
+  /// The largest number to generate
+  #[arg(long, default_value_t = MAX_SAFE_INTEGER)]
+  max: u64
+}
+
+/// Prints a JSON array of vectors in the format of `hashids::test_vectors::TestVector`.
+///
+/// Vectors hold from one to three numbers, and are made in `Compat::Reference` mode, which is what other
+/// implementations follow. Settings the reference implementations don't have, such as prefixes, are refused.
+pub fn run(codec: &HashidCodec, args: &VectorsArgs) -> Result<(), Box<dyn Error>> {
+  let config = codec.to_config();
+  if !config.prefix.is_empty() || !config.suffix.is_empty() || config.case != Case::Preserve || !config.blocklist.is_empty() {
+    return Err("test vectors only cover the salt, the alphabet and the minimum length".into())
+  }
+  let alphabet = config.alphabet.unwrap_or_else(|| alphabet::DEFAULT.to_string());
+  let codec = HashidBuilder::new().with_string_salt(config.salt.clone()).with_alphabet(alphabet.clone())
+    .with_length(config.min_length).with_compat(Compat::Reference).build()?;
+
+  let mut random = SplitMix64(args.seed);
+  let mut out = io::stdout().lock();
+  writeln!(out, "[")?;
+  for i in 0..args.count {
+    let len = random.next() % 3 + 1;
+    let numbers: Vec<u64> = (0..len).map(|_| random.below(args.max)).collect();
+    let hash = match numbers[..] {
+      [a] => codec.encode(a)?,
+      [a, b] => codec.encode_tuple((a, b))?,
+      [a, b, c] => codec.encode_tuple((a, b, c))?,
+      _ => unreachable!()
+    };
+    let vector = serde_json::json!({
+      "salt": config.salt, "alphabet": alphabet, "min_length": config.min_length, "numbers": numbers, "hash": hash
+    });
+    let separator = if i + 1 < args.count { "," } else { "" };
+    writeln!(out, "  {}{}", vector, separator)?;
+  }
+  writeln!(out, "]")?;
+  Ok(())
+}
+
+/// A tiny deterministic generator, so vectors are reproducible without depending on a random crate.
+pub struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+  pub fn next(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+  }
+
+  /// A number from 0 to `max` included, spread over every magnitude so small IDs are covered too.
+  pub fn below(&mut self, max: u64) -> u64 {
+    let shift = self.next() % 64;
+    let number = self.next() >> shift;
+    if max == u64::MAX { number } else { number % (max + 1) }
+  }
+}
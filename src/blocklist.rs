@@ -0,0 +1,14 @@
+//! A default list of words to keep out of hashes, for `HashidBuilder::with_default_blocklist`.
+//!
+//! Common English profanities and slurs, in lowercase. Blocking ignores case, so uppercase variants are covered too.
+//! It is far from exhaustive: pass your own words to `HashidBuilder::with_blocklist` for anything more specific.
+
+/// The words blocked by `HashidBuilder::with_default_blocklist`.
+pub const DEFAULT_BLOCKLIST: &[&str] = &[
+  "anal", "anus", "arse", "ass", "bastard", "bitch", "bollock", "boner", "boob", "bugger",
+  "butt", "chink", "clit", "cock", "coon", "crap", "cum", "cunt", "damn", "dick",
+  "dildo", "dyke", "fag", "fuck", "fuk", "gook", "homo", "jizz", "kike", "kkk",
+  "nazi", "nigga", "nigger", "orgasm", "penis", "piss", "poop", "porn", "prick", "pube",
+  "pussy", "rape", "retard", "scrotum", "sex", "shit", "slut", "spic", "tit", "twat",
+  "vagina", "wank", "whore", "wtf"
+];
@@ -0,0 +1,157 @@
+//! Working space of encoding and decoding.
+//!
+//! With the `thread-local` feature, buffers that can't be on the stack are kept between calls in each thread,
+//! so servers sharing a codec between their threads don't allocate them for every request.
+#[cfg(feature = "thread-local")]
+use std::cell::RefCell;
+#[cfg(feature = "thread-local")]
+use std::thread::LocalKey;
+
+/// Alphabets up to this length get their working space on the stack.
+const STACK_ALPHABET_LENGTH: usize = 128;
+
+#[cfg(feature = "thread-local")]
+thread_local! {
+  static SYMBOLS: RefCell<Vec<char>> = const { RefCell::new(Vec::new()) };
+  static STRING: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Calls `f` with working space twice as long as the alphabet, on the stack unless the alphabet is unusually long.
+pub(crate) fn with_symbols<R>(alphabet_len: usize, f: impl FnOnce(&mut [char]) -> R) -> R {
+  if alphabet_len <= STACK_ALPHABET_LENGTH {
+    let mut symbols = ['\0'; STACK_ALPHABET_LENGTH * 2];
+    return f(&mut symbols[..alphabet_len * 2])
+  }
+
+  let f = |symbols: &mut Vec<char>| {
+    symbols.resize(alphabet_len * 2, '\0');
+    f(symbols)
+  };
+  #[cfg(feature = "thread-local")]
+  let f = match with_local(&SYMBOLS, f) {
+    Ok(ret) => return ret,
+    Err(f) => f
+  };
+  f(&mut Vec::new())
+}
+
+/// Calls `f` with an empty `String`.
+pub(crate) fn with_string<R>(f: impl FnOnce(&mut String) -> R) -> R {
+  let f = |string: &mut String| {
+    string.clear();
+    f(string)
+  };
+  #[cfg(feature = "thread-local")]
+  let f = match with_local(&STRING, f) {
+    Ok(ret) => return ret,
+    Err(f) => f
+  };
+  f(&mut String::new())
+}
+
+/// Calls `f` with the thread's buffer, or gives `f` back if the buffer is already borrowed by a call up the stack.
+#[cfg(feature = "thread-local")]
+fn with_local<T, R, F: FnOnce(&mut T) -> R>(key: &'static LocalKey<RefCell<T>>, f: F) -> Result<R, F> {
+  key.with(|buffer| match buffer.try_borrow_mut() {
+    Ok(mut buffer) => Ok(f(&mut buffer)),
+    Err(_) => Err(f)
+  })
+}
+
+/// Where hashes are written while encoding: a `String`, or a fixed buffer with `SliceOut`.
+///
+/// Offsets are in bytes, as with `String`.
+pub(crate) trait HashOut {
+  fn as_str(&self) -> &str;
+  fn push(&mut self, c: char);
+  fn push_str(&mut self, s: &str);
+  fn insert(&mut self, offset: usize, c: char);
+  fn truncate(&mut self, len: usize);
+  fn reserve(&mut self, _additional: usize) {}
+
+  /// Whether something didn't fit, so the hash written is incomplete.
+  fn overflowed(&self) -> bool {
+    false
+  }
+
+  fn len(&self) -> usize {
+    self.as_str().len()
+  }
+}
+
+impl HashOut for String {
+  fn as_str(&self) -> &str { self }
+  fn push(&mut self, c: char) { String::push(self, c) }
+  fn push_str(&mut self, s: &str) { String::push_str(self, s) }
+  fn insert(&mut self, offset: usize, c: char) { String::insert(self, offset, c) }
+  fn truncate(&mut self, len: usize) { String::truncate(self, len) }
+  fn reserve(&mut self, additional: usize) { String::reserve(self, additional) }
+  fn len(&self) -> usize { String::len(self) }
+}
+
+/// A caller's buffer, filled from its start.
+///
+/// Characters that don't fit are dropped and mark it as overflowed, which makes the encoding fail in the end,
+/// so what's written stays valid UTF-8 without checking for room at every step.
+pub(crate) struct SliceOut<'a> {
+  buf: &'a mut [u8],
+  len: usize,
+  overflowed: bool
+}
+
+impl<'a> SliceOut<'a> {
+  pub(crate) fn new(buf: &'a mut [u8]) -> SliceOut<'a> {
+    SliceOut { buf, len: 0, overflowed: false }
+  }
+
+  /// The length written, or `None` if something didn't fit.
+  pub(crate) fn finish(self) -> Option<usize> {
+    if self.overflowed { None } else { Some(self.len) }
+  }
+
+  /// Makes room for `len` bytes at `offset`, unless the buffer is full.
+  fn open(&mut self, offset: usize, len: usize) -> bool {
+    if self.overflowed || self.len + len > self.buf.len() {
+      self.overflowed = true;
+      return false
+    }
+    self.buf.copy_within(offset..self.len, offset + len);
+    self.len += len;
+    true
+  }
+}
+
+impl HashOut for SliceOut<'_> {
+  fn as_str(&self) -> &str {
+    std::str::from_utf8(&self.buf[..self.len]).expect("only whole characters are written")
+  }
+
+  fn push(&mut self, c: char) {
+    self.insert(self.len, c)
+  }
+
+  fn push_str(&mut self, s: &str) {
+    let offset = self.len;
+    if self.open(offset, s.len()) {
+      self.buf[offset..offset + s.len()].copy_from_slice(s.as_bytes());
+    }
+  }
+
+  fn insert(&mut self, offset: usize, c: char) {
+    if self.open(offset, c.len_utf8()) {
+      c.encode_utf8(&mut self.buf[offset..]);
+    }
+  }
+
+  fn truncate(&mut self, len: usize) {
+    self.len = self.len.min(len);
+  }
+
+  fn len(&self) -> usize {
+    self.len
+  }
+
+  fn overflowed(&self) -> bool {
+    self.overflowed
+  }
+}
@@ -0,0 +1,75 @@
+//! Memoization of the hashes of hot IDs.
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use lru::LruCache;
+
+use crate::{Error, HashidCodec, PositiveInteger};
+
+/// Wraps a codec with LRU caches of the latest encoded IDs and decoded hashes.
+///
+/// The codec can't be changed once wrapped, so cached entries never go stale.
+/// Only successful results are cached. Requires the `cache` feature.
+/// ```
+/// use std::num::NonZeroUsize;
+/// use hashids::{HashidBuilder, CachedCodec};
+/// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+/// let cached = CachedCodec::new(codec, NonZeroUsize::new(1000).unwrap());
+/// assert_eq!(cached.encode(12345).unwrap(), "NkK9");
+/// assert_eq!(cached.decode_single("NkK9"), Ok(12345));
+/// ```
+#[derive(Debug)]
+pub struct CachedCodec {
+  codec: HashidCodec,
+  hashes: Mutex<LruCache<u128, String>>,
+  ids: Mutex<LruCache<String, u64>>
+}
+
+impl CachedCodec {
+  /// Keeps up to `capacity` hashes, and as many decoded IDs.
+  pub fn new(codec: HashidCodec, capacity: NonZeroUsize) -> CachedCodec {
+    CachedCodec { codec, hashes: Mutex::new(LruCache::new(capacity)), ids: Mutex::new(LruCache::new(capacity)) }
+  }
+
+  /// The wrapped codec, to use it without the caches.
+  pub fn codec(&self) -> &HashidCodec {
+    &self.codec
+  }
+
+  /// Same as `HashidCodec::encode`, returning the cached hash of the ID if it was encoded recently.
+  pub fn encode<T: PositiveInteger>(&self, id: T) -> Result<String, Error> {
+    let number = self.codec.id_to_number(id)?;
+    if let Some(hash) = lock(&self.hashes).get(&number) {
+      self.codec.metrics.cache_hit();
+      return Ok(hash.clone())
+    }
+    self.codec.metrics.cache_miss();
+    let mut hash = String::new();
+    self.codec.encode_vec_into(&[number], &mut hash)?;
+    lock(&self.hashes).put(number, hash.clone());
+    Ok(hash)
+  }
+
+  /// Same as `HashidCodec::decode_single`, returning the cached ID of the hash if it was decoded recently.
+  pub fn decode_single(&self, hash: &str) -> Result<u64, Error> {
+    if let Some(id) = lock(&self.ids).get(hash) {
+      self.codec.metrics.cache_hit();
+      return Ok(*id)
+    }
+    self.codec.metrics.cache_miss();
+    let id = self.codec.decode_single(hash)?;
+    lock(&self.ids).put(hash.to_string(), id);
+    Ok(id)
+  }
+
+  /// Empties both caches.
+  pub fn clear(&self) {
+    lock(&self.hashes).clear();
+    lock(&self.ids).clear();
+  }
+}
+
+/// A panic while holding the lock can't leave a cache inconsistent, so poisoning is ignored.
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+  mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,39 @@
+//! Forcing the case of hashes, for codes that are read out loud or typed by people.
+
+use std::borrow::Cow;
+
+/// The case of the hashes produced by the codec, set with `HashidBuilder::with_output_case`.
+///
+/// With `Upper` or `Lower`, the alphabet, separators and guards are folded to that case when building the codec,
+/// so hashes differ from the ones of a `Preserve` codec with the same settings.
+/// Decoding accepts hashes in any case, and reads them as if they were in the codec's case.
+/// ```
+/// use hashids::{HashidBuilder, Case};
+/// let codec = HashidBuilder::new().with_salt("my salt").with_output_case(Case::Upper).build().unwrap();
+/// let hash = codec.encode(123456789).unwrap();
+/// assert_eq!(hash, hash.to_uppercase());
+/// assert_eq!(codec.decode(hash.to_lowercase()), Ok(vec![123456789]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Case {
+  /// Keep the alphabet as it is.
+  #[default]
+  Preserve,
+  /// Only produce uppercase hashes.
+  Upper,
+  /// Only produce lowercase hashes.
+  Lower
+}
+
+impl Case {
+  /// Folds the ASCII letters of `s` to this case, borrowing when there is nothing to change.
+  pub(crate) fn apply(self, s: &str) -> Cow<'_, str> {
+    match self {
+      Case::Preserve => Cow::Borrowed(s),
+      Case::Upper if s.bytes().any(|b| b.is_ascii_lowercase()) => Cow::Owned(s.to_ascii_uppercase()),
+      Case::Lower if s.bytes().any(|b| b.is_ascii_uppercase()) => Cow::Owned(s.to_ascii_lowercase()),
+      _ => Cow::Borrowed(s)
+    }
+  }
+}
@@ -0,0 +1,30 @@
+//! Compatibility with the reference hashids implementations (JavaScript, Go, PHP...).
+//!
+//! This crate historically derived separators and guards from the alphabet slightly differently than the reference implementations:
+//! integer division where they use floating point, and rounding down where they round up.
+//! The outputs are identical for the default alphabet and most custom alphabets, but differ for some of them.
+//! `Compat::Legacy` stays the default so existing hashes keep decoding,
+//! use `Compat::Reference` to decode hashes produced by other implementations, or produce hashes they can decode.
+
+/// Selects how the builder derives separators and guards from the alphabet, see the module documentation.
+/// ```
+/// use hashids::{HashidBuilder, compat};
+/// let codec = HashidBuilder::new()
+///     .with_salt("this is my salt")
+///     .with_alphabet("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ".to_string())
+///     .with_compat(compat::Reference)
+///     .build().unwrap();
+/// // Same as `new Hashids("this is my salt", 0, "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ").encode(1234567)` in hashids.js
+/// assert_eq!(codec.encode(1234567).unwrap(), "4QML8L");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Compat {
+  /// The derivation this crate always used.
+  #[default]
+  Legacy,
+  /// The derivation of the reference implementations.
+  Reference
+}
+
+pub use self::Compat::{Legacy, Reference};
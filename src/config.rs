@@ -0,0 +1,165 @@
+//! The settings of a codec as plain data, to persist and exchange them.
+use std::time::SystemTime;
+
+use crate::compat::Compat;
+use crate::{Case, Error, HashidBuilder, HashidCodec, DEFAULT_MIN_LENGTH};
+
+/// Everything a codec is built from, without what the builder derives from it.
+///
+/// With the `serde` feature, it can be serialized, and missing fields are deserialized to their defaults.
+/// The keys of `with_mac_key` and `with_fpe_key` aren't part of it, and must be set again on the builder.
+/// ```
+/// use hashids::{HashidBuilder, HashidCodec, HashidConfig};
+/// let codec = HashidBuilder::new().with_salt("this is my salt").with_length(8).build().unwrap();
+/// let config = codec.to_config();
+/// assert_eq!(config.min_length, 8);
+///
+/// let restored = HashidCodec::from_config(config).unwrap();
+/// assert_eq!(restored.encode(1).unwrap(), "gB0NV05e");
+///
+/// let config = HashidConfig { salt: "this is my salt".to_string(), ..HashidConfig::default() };
+/// assert_eq!(HashidCodec::from_config(config).unwrap().encode(12345).unwrap(), "NkK9");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct HashidConfig {
+  pub salt: String,
+  /// Allows an empty salt, see `HashidBuilder::without_salt`.
+  pub unsalted: bool,
+  pub alphabet: Option<String>,
+  pub separators: Option<String>,
+  pub guards: Option<String>,
+  pub min_length: usize,
+  pub max_length: Option<usize>,
+  pub allow_negative: bool,
+  pub compat: Compat,
+  pub case: Case,
+  pub blocklist: Vec<String>,
+  pub prefix: String,
+  pub suffix: String,
+  pub timestamp_epoch: SystemTime
+}
+
+impl Default for HashidConfig {
+  fn default() -> HashidConfig {
+    HashidConfig {
+      salt: String::new(),
+      unsalted: false,
+      alphabet: None,
+      separators: None,
+      guards: None,
+      min_length: DEFAULT_MIN_LENGTH,
+      max_length: None,
+      allow_negative: false,
+      compat: Compat::default(),
+      case: Case::default(),
+      blocklist: Vec::new(),
+      prefix: String::new(),
+      suffix: String::new(),
+      timestamp_epoch: SystemTime::UNIX_EPOCH
+    }
+  }
+}
+
+impl HashidBuilder {
+  /// A builder with the settings of the config, to add the keys that aren't part of it.
+  pub fn from_config(config: HashidConfig) -> HashidBuilder {
+    let mut builder = HashidBuilder::new().with_string_salt(config.salt);
+    builder.unsalted = config.unsalted;
+    builder.alphabet = config.alphabet;
+    builder.separators = config.separators;
+    builder.guards = config.guards;
+    builder.min_length = Some(config.min_length);
+    builder.max_length = config.max_length;
+    builder.allow_negative = config.allow_negative;
+    builder.compat = config.compat;
+    builder.case = config.case;
+    builder.blocklist = config.blocklist;
+    builder.prefix = config.prefix;
+    builder.suffix = config.suffix;
+    builder.timestamp_epoch = config.timestamp_epoch;
+    builder
+  }
+
+  /// Same as `from_config`, but without a salt, `build` reads it from the environnment, unless the config asks for none.
+  #[cfg(any(feature = "config-file", feature = "figment"))]
+  pub(crate) fn from_config_or_env(config: HashidConfig) -> HashidBuilder {
+    let read_env = config.salt.is_empty() && !config.unsalted;
+    let mut builder = HashidBuilder::from_config(config);
+    if read_env { builder.salt = None }
+    builder
+  }
+}
+
+impl HashidCodec {
+  /// Builds a codec from its settings, same as `HashidBuilder::from_config(config).build()`.
+  pub fn from_config(config: HashidConfig) -> Result<HashidCodec, Error> {
+    HashidBuilder::from_config(config).build()
+  }
+
+  /// The settings the codec was built from, with the salt it was given or read from the environnment.
+  pub fn to_config(&self) -> HashidConfig {
+    self.config.clone()
+  }
+}
+
+#[cfg(feature = "config-file")]
+impl HashidBuilder {
+  /// A builder with the settings of a TOML file, or of a JSON one if its extension is `.json`, so they live with
+  /// the rest of a service's configuration.
+  ///
+  /// The keys are the fields of `HashidConfig`, all optional. The salt is either written in the file, or taken from
+  /// an environnment variable with `salt = { env = "NAME" }`. Without one, the builder falls back to `HASHID_SALT`.
+  /// ```
+  /// # let path = std::env::temp_dir().join(format!("hashids-doc-{}.toml", std::process::id()));
+  /// # std::fs::write(&path, "salt = { env = \"USERS_SALT\" }\nmin_length = 8\nprefix = \"u_\"\n").unwrap();
+  /// # std::env::set_var("USERS_SALT", "this is my salt");
+  /// use hashids::HashidBuilder;
+  /// // salt = { env = "USERS_SALT" }
+  /// // min_length = 8
+  /// // prefix = "u_"
+  /// let codec = HashidBuilder::from_file(&path).unwrap().build().unwrap();
+  /// assert_eq!(codec.encode(1).unwrap(), "u_gB0NV05e");
+  /// # std::fs::remove_file(path).unwrap();
+  /// ```
+  /// Returns an `Error::InvalidConfigFile` if the file can't be read or parsed, has unknown keys or invalid values,
+  /// or if the salt's variable isn't set.
+  ///
+  /// Requires the `config-file` feature.
+  pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<HashidBuilder, Error> {
+    use serde_json::Value;
+
+    let path = path.as_ref();
+    let invalid = |reason: String| Error::InvalidConfigFile { path: path.to_path_buf(), reason };
+    let contents = std::fs::read_to_string(path).map_err(|err| invalid(format!("can't be read: {}", err)))?;
+    let mut settings: serde_json::Map<String, Value> = if path.extension().is_some_and(|extension| extension == "json") {
+      serde_json::from_str(&contents).map_err(|err| invalid(err.to_string()))?
+    } else {
+      toml::from_str(&contents).map_err(|err| invalid(err.to_string().trim_end().to_string()))?
+    };
+
+    let salt = match settings.remove("salt") {
+      None => None,
+      Some(Value::String(salt)) => Some(salt),
+      Some(Value::Object(source)) => match (source.get("env"), source.len()) {
+        (Some(Value::String(name)), 1) => Some(std::env::var(name)
+          .map_err(|_| invalid(format!("the salt is read from the `{}` environnment variable, which isn't set", name)))?),
+        _ => return Err(invalid("the `salt` table must only have an `env` key, naming the variable holding the salt".to_string()))
+      },
+      Some(_) => return Err(invalid("the `salt` must be a string, or a table naming the environnment variable holding it".to_string()))
+    };
+
+    // Checked one by one, as serde_json's errors don't tell which key is wrong
+    let known = serde_json::to_value(HashidConfig::default()).expect("the config serializes");
+    for (key, value) in &settings {
+      if known.get(key).is_none() {
+        return Err(invalid(format!("unknown key `{}`", key)))
+      }
+      let single = std::iter::once((key.clone(), value.clone())).collect();
+      serde_json::from_value::<HashidConfig>(Value::Object(single)).map_err(|err| invalid(format!("invalid `{}`: {}", key, err)))?;
+    }
+    let mut config: HashidConfig = serde_json::from_value(Value::Object(settings)).map_err(|err| invalid(err.to_string()))?;
+    config.salt = salt.unwrap_or_default();
+    Ok(HashidBuilder::from_config_or_env(config))
+  }
+}
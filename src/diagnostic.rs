@@ -0,0 +1,121 @@
+//! Non-consuming validation of a `HashidBuilder`, for configuration tooling.
+use std::fmt;
+
+use crate::{HashidBuilder, HashidSalt, DEFAULT_ALPHABET, DEFAULT_MIN_LENGTH, DEFAULT_SEPARATORS, MIN_ALPHABET_LENGTH};
+
+/// Salts shorter than this are reported as easy to guess.
+const RECOMMENDED_SALT_LENGTH: usize = 8;
+/// Minimum lengths above this are reported as unusual, and likely a mistake.
+const MAX_SENSIBLE_MIN_LENGTH: usize = 64;
+
+/// How bad a `Diagnostic` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+  /// The codec can be built, but the configuration is probably not what you want.
+  Warning,
+  /// Building the codec will fail.
+  Error
+}
+
+/// What a `Diagnostic` is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+  MissingSalt,
+  NonAsciiSalt,
+  ShortSalt,
+  /// The salt doesn't meet the strength required with `require_salt_strength`.
+  WeakSalt,
+  NonAsciiAlphabet,
+  AlphabetTooShort,
+  /// Few characters are left in the alphabet once separators are taken out of it, so hashes will be long.
+  SmallEffectiveAlphabet,
+  LargeMinLength
+}
+
+/// A problem found by `HashidBuilder::validate`, with a human-readable message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+  pub severity: Severity,
+  pub kind: DiagnosticKind,
+  pub message: String
+}
+
+impl Diagnostic {
+  fn new(severity: Severity, kind: DiagnosticKind, message: String) -> Diagnostic {
+    Diagnostic { severity, kind, message }
+  }
+}
+
+impl fmt::Display for Diagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let severity = match self.severity {
+      Severity::Warning => "warning",
+      Severity::Error => "error"
+    };
+    write!(f, "{}: {}", severity, self.message)
+  }
+}
+
+impl HashidBuilder {
+  /// Reports problems with the current configuration, without consuming the builder.
+  ///
+  /// Diagnostics with `Severity::Error` mean `build()` will fail, warnings point at weak or surprising settings.
+  /// An empty `Vec` means there is nothing to report.
+  /// ```
+  /// use hashids::{HashidBuilder, DiagnosticKind, Severity};
+  /// let builder = HashidBuilder::new().with_salt("salt").with_alphabet("abcdefghij".to_string());
+  /// let diagnostics = builder.validate();
+  /// assert!(diagnostics.iter().any(|d| d.kind == DiagnosticKind::ShortSalt && d.severity == Severity::Warning));
+  /// assert!(diagnostics.iter().any(|d| d.kind == DiagnosticKind::AlphabetTooShort && d.severity == Severity::Error));
+  /// ```
+  pub fn validate(&self) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let env_salt;
+    let salt = match &self.salt {
+      Some(salt) => Some(salt.0.as_str()),
+      None => {
+        env_salt = self.env_salt();
+        env_salt.as_deref()
+      }
+    };
+    match salt {
+      None => diagnostics.push(Diagnostic::new(Severity::Error, DiagnosticKind::MissingSalt,
+        format!("no salt is set, through the builder nor the `{}` environnment variable", self.env_key))),
+      Some("") if !self.unsalted => diagnostics.push(Diagnostic::new(Severity::Error, DiagnosticKind::MissingSalt,
+        "the salt is empty, use `without_salt` to build a codec without one".to_string())),
+      Some(salt) if !salt.is_ascii() => diagnostics.push(Diagnostic::new(Severity::Error, DiagnosticKind::NonAsciiSalt,
+        "the salt contains non-ASCII characters".to_string())),
+      Some(salt) if self.salt_strength.is_some_and(|(min_len, min_unique_chars)| HashidSalt::from(salt).is_weak(min_len, min_unique_chars)) =>
+        diagnostics.push(Diagnostic::new(Severity::Error, DiagnosticKind::WeakSalt,
+          "the salt is shorter or has fewer distinct characters than required".to_string())),
+      // Going without a salt is a deliberate choice
+      Some(_) if self.unsalted => {}
+      Some(salt) if salt.len() < RECOMMENDED_SALT_LENGTH => diagnostics.push(Diagnostic::new(Severity::Warning, DiagnosticKind::ShortSalt,
+        format!("the salt is {} characters long, at least {} are recommended", salt.len(), RECOMMENDED_SALT_LENGTH))),
+      Some(_) => {}
+    }
+
+    let alphabet = &*self.case.apply(self.alphabet.as_deref().unwrap_or(DEFAULT_ALPHABET));
+    let unique = crate::get_unique_alphabet(alphabet);
+    let separators = self.separators.as_deref().unwrap_or(DEFAULT_SEPARATORS);
+    let guards = self.guards.as_deref().unwrap_or("");
+    let effective = unique.iter().filter(|c| !separators.contains(**c) && !guards.contains(**c)).count();
+    if unique.len() < MIN_ALPHABET_LENGTH {
+      diagnostics.push(Diagnostic::new(Severity::Error, DiagnosticKind::AlphabetTooShort,
+        format!("the alphabet has {} unique characters, at least {} are required", unique.len(), MIN_ALPHABET_LENGTH)));
+    } else if effective < MIN_ALPHABET_LENGTH {
+      diagnostics.push(Diagnostic::new(Severity::Warning, DiagnosticKind::SmallEffectiveAlphabet,
+        format!("only {} characters of the alphabet are left once separators and guards are removed, hashes will be long", effective)));
+    }
+
+    let min_length = self.min_length.unwrap_or(DEFAULT_MIN_LENGTH);
+    if min_length > MAX_SENSIBLE_MIN_LENGTH {
+      diagnostics.push(Diagnostic::new(Severity::Warning, DiagnosticKind::LargeMinLength,
+        format!("a minimum length of {} is unusually large", min_length)));
+    }
+
+    diagnostics
+  }
+}
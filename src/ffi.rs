@@ -0,0 +1,148 @@
+//! A C interface, so C, C++ and Go (through cgo) programs can embed the codec.
+//!
+//! The crate only declares a Rust library, so the C library is built by overriding its type, which needs Cargo 1.64
+//! or later: `cargo rustc --lib --release --features ffi --crate-type cdylib` (or `staticlib`). Crates depending on
+//! this one don't get a C library from `cargo build`. The `include/hashids.h` header comes with the sources:
+//! ```c
+//! HashidsCodec *codec = hashids_new("this is my salt", NULL, 4);
+//! char *hash = hashids_encode(codec, 12345); // "NkK9"
+//! uint64_t numbers[4];
+//! ptrdiff_t count = hashids_decode(codec, hash, numbers, 4); // 1, numbers[0] == 12345
+//! hashids_string_free(hash);
+//! hashids_free(codec);
+//! ```
+//! Functions failing return `NULL` or `-1`, and `hashids_last_error` tells why.
+//!
+//! Requires the `ffi` feature.
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::HashidBuilder;
+
+thread_local! {
+  static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// A codec, only handled through pointers.
+pub struct HashidsCodec(crate::HashidCodec);
+
+fn set_last_error(message: String) {
+  // Messages never contain NUL bytes, except from invalid inputs which are reported before that
+  let message = CString::new(message).unwrap_or_else(|_| CString::new("invalid error message").unwrap());
+  LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Reads a C string, reporting it if it isn't UTF-8.
+unsafe fn read_str<'a>(s: *const c_char, name: &str) -> Option<&'a str> {
+  if s.is_null() {
+    set_last_error(format!("{} is NULL", name));
+    return None
+  }
+  match CStr::from_ptr(s).to_str() {
+    Ok(s) => Some(s),
+    Err(_) => {
+      set_last_error(format!("{} isn't valid UTF-8", name));
+      None
+    }
+  }
+}
+
+/// Builds a codec, returning `NULL` if the settings are invalid.
+///
+/// `alphabet` may be `NULL` for the default one, and `min_length` is 4 by default in other interfaces.
+///
+/// # Safety
+/// `salt` must be a NUL-terminated string, and `alphabet` one too or `NULL`.
+#[no_mangle]
+pub unsafe extern "C" fn hashids_new(salt: *const c_char, alphabet: *const c_char, min_length: usize) -> *mut HashidsCodec {
+  let Some(salt) = read_str(salt, "the salt") else { return ptr::null_mut() };
+  let mut builder = HashidBuilder::new().with_salt(salt).with_length(min_length);
+  if !alphabet.is_null() {
+    let Some(alphabet) = read_str(alphabet, "the alphabet") else { return ptr::null_mut() };
+    builder = builder.with_alphabet(alphabet.to_string());
+  }
+  match builder.build() {
+    Ok(codec) => Box::into_raw(Box::new(HashidsCodec(codec))),
+    Err(err) => {
+      set_last_error(err.to_string());
+      ptr::null_mut()
+    }
+  }
+}
+
+/// Encodes an ID, returning a string to free with `hashids_string_free`, or `NULL` on failure.
+///
+/// # Safety
+/// `codec` must come from `hashids_new`, and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hashids_encode(codec: *const HashidsCodec, id: u64) -> *mut c_char {
+  let Some(codec) = codec.as_ref() else {
+    set_last_error("the codec is NULL".to_string());
+    return ptr::null_mut()
+  };
+  match codec.0.encode(id) {
+    // Hashes are made of the alphabet's characters, which can't be NUL as the alphabet is a C string
+    Ok(hash) => CString::new(hash).map_or(ptr::null_mut(), CString::into_raw),
+    Err(err) => {
+      set_last_error(err.to_string());
+      ptr::null_mut()
+    }
+  }
+}
+
+/// Decodes a hash, writing up to `capacity` of its numbers to `numbers`.
+///
+/// Returns how many numbers the hash holds, which may be more than `capacity` in which case only the first ones
+/// are written, or `-1` on failure.
+///
+/// # Safety
+/// `codec` must come from `hashids_new`, and not be freed yet. `hash` must be a NUL-terminated string, and
+/// `numbers` must point to `capacity` writable integers, or may be `NULL` if `capacity` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn hashids_decode(codec: *const HashidsCodec, hash: *const c_char, numbers: *mut u64, capacity: usize) -> isize {
+  let Some(codec) = codec.as_ref() else {
+    set_last_error("the codec is NULL".to_string());
+    return -1
+  };
+  let Some(hash) = read_str(hash, "the hash") else { return -1 };
+  match codec.0.decode_as::<u64>(hash) {
+    Ok(decoded) => {
+      for (i, number) in decoded.iter().take(capacity).enumerate() {
+        *numbers.add(i) = *number;
+      }
+      decoded.len() as isize
+    }
+    Err(err) => {
+      set_last_error(err.to_string());
+      -1
+    }
+  }
+}
+
+/// The message of the last failure on this thread, or `NULL` if nothing failed yet.
+///
+/// The string belongs to the library, and stays valid until the next failure on this thread.
+#[no_mangle]
+pub extern "C" fn hashids_last_error() -> *const c_char {
+  LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Frees a string returned by `hashids_encode`. Does nothing with `NULL`.
+///
+/// # Safety
+/// `hash` must come from `hashids_encode`, and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hashids_string_free(hash: *mut c_char) {
+  if !hash.is_null() { drop(CString::from_raw(hash)) }
+}
+
+/// Frees a codec. Does nothing with `NULL`.
+///
+/// # Safety
+/// `codec` must come from `hashids_new`, and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hashids_free(codec: *mut HashidsCodec) {
+  if !codec.is_null() { drop(Box::from_raw(codec)) }
+}
@@ -0,0 +1,65 @@
+//! Format-preserving encryption of numbers before they are hashed, for hashes that don't leak the salt.
+//!
+//! Hashids only shuffle the alphabet with the salt, so the salt can be recovered from enough hashes of known numbers.
+//! With a key set, numbers are first encrypted with a Feistel network over 64 bits, using HMAC-SHA256 as its round function.
+//! The encryption is a permutation of `u64`, so every number still gets a unique hash and decodes back to itself,
+//! but consecutive numbers get unrelated hashes, and recovering the salt doesn't reveal the numbers.
+use std::convert::TryInto;
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::Error;
+
+const ROUNDS: u8 = 8;
+
+/// The secret key of the Feistel network.
+#[derive(Clone, PartialEq)]
+pub(crate) struct FpeKey {
+  key: Vec<u8>
+}
+
+// Keeps the key out of logs
+impl fmt::Debug for FpeKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("FpeKey").field("key", &"<redacted>").finish()
+  }
+}
+
+impl FpeKey {
+  pub(crate) fn new(key: Vec<u8>) -> Result<FpeKey, Error> {
+    if key.is_empty() {
+      return Err(Error::InvalidFpeKey)
+    }
+    Ok(FpeKey { key })
+  }
+
+  fn round(&self, round: u8, half: u32) -> u32 {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+    mac.update(&[round]);
+    mac.update(&half.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+    u32::from_be_bytes(digest[..4].try_into().unwrap())
+  }
+
+  pub(crate) fn encrypt(&self, number: u64) -> u64 {
+    let (mut left, mut right) = ((number >> 32) as u32, number as u32);
+    for round in 0..ROUNDS {
+      let next = left ^ self.round(round, right);
+      left = right;
+      right = next;
+    }
+    (left as u64) << 32 | right as u64
+  }
+
+  pub(crate) fn decrypt(&self, number: u64) -> u64 {
+    let (mut left, mut right) = ((number >> 32) as u32, number as u32);
+    for round in (0..ROUNDS).rev() {
+      let previous = right ^ self.round(round, left);
+      right = left;
+      left = previous;
+    }
+    (left as u64) << 32 | right as u64
+  }
+}
@@ -0,0 +1,47 @@
+//! A process-wide codec, for applications that use a single one everywhere.
+use std::sync::OnceLock;
+
+use crate::{Error, HashidBuilder, HashidCodec, PositiveInteger};
+
+static GLOBAL: OnceLock<HashidCodec> = OnceLock::new();
+
+/// Builds the global codec used by `hashids::encode` and `hashids::decode`.
+///
+/// Returns the builder's error if the codec can't be built,
+/// or an `Error::AlreadyInitialized` if the global codec was already set, by `init` or a first use.
+/// ```
+/// use hashids::HashidBuilder;
+/// hashids::init(HashidBuilder::new().with_salt("this is my salt")).unwrap();
+/// assert_eq!(hashids::encode(12345).unwrap(), "NkK9");
+/// assert_eq!(hashids::decode("NkK9"), Ok(vec![12345]));
+/// ```
+pub fn init(builder: HashidBuilder) -> Result<(), Error> {
+  let codec = builder.build()?;
+  GLOBAL.set(codec).map_err(|_| Error::AlreadyInitialized)
+}
+
+/// The global codec, built from the defaults and the `HASHID_SALT` environnment variable if `init` wasn't called.
+pub fn global() -> Result<&'static HashidCodec, Error> {
+  if let Some(codec) = GLOBAL.get() {
+    return Ok(codec)
+  }
+  let codec = HashidBuilder::new().build()?;
+  Ok(GLOBAL.get_or_init(|| codec))
+}
+
+/// The global codec if it's already set, without building it from the defaults,
+/// for callers that must not prevent a later `init`.
+#[cfg(feature = "schemars")]
+pub(crate) fn initialized() -> Option<&'static HashidCodec> {
+  GLOBAL.get()
+}
+
+/// Same as `HashidCodec::encode`, with the global codec.
+pub fn encode<T: PositiveInteger>(id: T) -> Result<String, Error> {
+  global()?.encode(id)
+}
+
+/// Same as `HashidCodec::decode`, with the global codec.
+pub fn decode(hash: impl AsRef<str>) -> Result<Vec<usize>, Error> {
+  global()?.decode(hash)
+}
@@ -0,0 +1,164 @@
+//! Typed hashids, so IDs of different entities can't be mixed up.
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::{Error, HashidCodec};
+
+/// An ID along with its hash, tagged with a marker type for the entity it identifies.
+///
+/// The marker is only used at compile time, so a `Hashid<User>` can't be passed where a `Hashid<Order>` is expected.
+/// It can be any type, and doesn't need to implement anything.
+/// `Display` writes the hash, and the ID is available through `id()`.
+/// ```
+/// use hashids::{HashidBuilder, Hashid};
+/// struct User;
+///
+/// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+/// let user_id: Hashid<User> = Hashid::new(12345, &codec).unwrap();
+/// assert_eq!(user_id.to_string(), "NkK9");
+///
+/// let parsed = Hashid::<User>::parse("NkK9", &codec).unwrap();
+/// assert_eq!(parsed.id(), 12345);
+/// assert_eq!(parsed, user_id);
+/// ```
+pub struct Hashid<T> {
+  id: u64,
+  hash: String,
+  marker: PhantomData<fn() -> T>
+}
+
+impl<T> Hashid<T> {
+  /// Encodes the ID with the codec, keeping both.
+  pub fn new(id: u64, codec: &HashidCodec) -> Result<Self, Error> {
+    let hash = codec.encode(id)?;
+    Ok(Hashid { id, hash, marker: PhantomData })
+  }
+
+  /// Decodes a hash made of a single number with the codec, which is how `FromStr` would work given a codec.
+  ///
+  /// The hash is kept as the codec writes it, as decoding ignores case, so it compares equal to the same ID from `new`.
+  pub fn parse(hash: &str, codec: &HashidCodec) -> Result<Self, Error> {
+    let id = codec.decode_single(hash)?;
+    Ok(Hashid { id, hash: codec.canonical_hash(hash), marker: PhantomData })
+  }
+
+  /// The plain ID.
+  pub fn id(&self) -> u64 {
+    self.id
+  }
+
+  /// The obfuscated ID.
+  pub fn as_str(&self) -> &str {
+    &self.hash
+  }
+
+  pub fn into_string(self) -> String {
+    self.hash
+  }
+}
+
+impl<T> fmt::Display for Hashid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.hash)
+  }
+}
+
+// The following are implemented by hand, as deriving them would require the marker to implement them too.
+
+impl<T> fmt::Debug for Hashid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Hashid").field("id", &self.id).field("hash", &self.hash).finish()
+  }
+}
+
+impl<T> Clone for Hashid<T> {
+  fn clone(&self) -> Self {
+    Hashid { id: self.id, hash: self.hash.clone(), marker: PhantomData }
+  }
+}
+
+impl<T> PartialEq for Hashid<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id && self.hash == other.hash
+  }
+}
+
+impl<T> Eq for Hashid<T> {}
+
+impl<T> Hash for Hashid<T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.id.hash(state);
+    self.hash.hash(state);
+  }
+}
+
+impl<T> AsRef<str> for Hashid<T> {
+  fn as_ref(&self) -> &str {
+    &self.hash
+  }
+}
+
+impl<T> From<Hashid<T>> for u64 {
+  fn from(hashid: Hashid<T>) -> u64 {
+    hashid.id
+  }
+}
+
+/// A signed 64 bits ID, such as a `BIGINT` primary key, stored as is and written as its hash with the global codec, see `hashids::init`.
+///
+/// Database integrations store and read the plain ID, so models can use it in place of an `i64`,
+/// while `encode`, `FromStr` and serde work with the hash.
+///
+/// There is no `Display`, as encoding fails for negative IDs, or before `hashids::init` is called.
+/// ```
+/// use hashids::{HashidBuilder, HashidI64};
+/// hashids::init(HashidBuilder::new().with_salt("this is my salt")).unwrap();
+///
+/// let id = HashidI64(12345);
+/// assert_eq!(id.encode().unwrap(), "NkK9");
+/// assert_eq!("NkK9".parse::<HashidI64>(), Ok(id));
+/// ```
+#[cfg_attr(feature = "diesel", derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow), diesel(sql_type = diesel::sql_types::BigInt))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct HashidI64(pub i64);
+
+impl HashidI64 {
+  /// The plain ID.
+  pub fn id(self) -> i64 {
+    self.0
+  }
+
+  /// Encodes the ID with the global codec.
+  ///
+  /// Returns an `Error::InvalidInputId` if the ID is negative, unless the global codec was built with `allow_negative()`.
+  pub fn encode(self) -> Result<String, Error> {
+    crate::global()?.encode(self.0)
+  }
+
+  /// Decodes a hash made of a single ID with the global codec.
+  pub fn decode(hash: &str) -> Result<Self, Error> {
+    crate::global()?.decode_id(hash).map(HashidI64)
+  }
+}
+
+impl FromStr for HashidI64 {
+  type Err = Error;
+
+  fn from_str(hash: &str) -> Result<Self, Error> {
+    HashidI64::decode(hash)
+  }
+}
+
+impl From<i64> for HashidI64 {
+  fn from(id: i64) -> HashidI64 {
+    HashidI64(id)
+  }
+}
+
+impl From<HashidI64> for i64 {
+  fn from(hashid: HashidI64) -> i64 {
+    hashid.0
+  }
+}
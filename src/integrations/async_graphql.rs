@@ -0,0 +1,40 @@
+use ::async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+use crate::Hashid;
+
+/// A `Hashid` GraphQL scalar, the hash of an ID as a string, decoded with the global codec, see `hashids::init`.
+///
+/// Requires the `async-graphql` feature.
+/// ```
+/// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+/// use hashids::{HashidBuilder, Hashid};
+/// struct User;
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///   async fn user_name(&self, id: Hashid<User>) -> String {
+///     format!("user {}", id.id())
+///   }
+/// }
+///
+/// hashids::init(HashidBuilder::new().with_salt("this is my salt")).unwrap();
+/// let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+/// assert!(schema.sdl().contains("scalar Hashid"));
+/// ```
+#[Scalar(name = "Hashid")]
+impl<T: Send + Sync> ScalarType for Hashid<T> {
+  fn parse(value: Value) -> InputValueResult<Self> {
+    match &value {
+      Value::String(hash) => {
+        let codec = crate::global().map_err(InputValueError::custom)?;
+        Hashid::parse(hash, codec).map_err(InputValueError::custom)
+      },
+      _ => Err(InputValueError::expected_type(value))
+    }
+  }
+
+  fn to_value(&self) -> Value {
+    Value::String(self.as_str().to_string())
+  }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use ::axum::extract::{FromRequestParts, Path};
+use ::axum::http::request::Parts;
+use ::axum::http::StatusCode;
+use ::axum::response::{IntoResponse, Response};
+
+use crate::{Error, Hashid, HashidCodec};
+
+/// Extracts the route's only path parameter, decoded with the codec added to the request extensions as an `Arc<HashidCodec>`,
+/// or with the global codec if there's none.
+///
+/// `Path<Hashid<T>>`, and `Query` structs with `Hashid<T>` fields, work too but always use the global codec, through serde.
+///
+/// Requires the `axum` feature.
+/// ```
+/// use std::sync::Arc;
+/// use axum::{Extension, Router, routing::get};
+/// use hashids::{HashidBuilder, Hashid};
+/// struct User;
+///
+/// async fn show_user(id: Hashid<User>) -> String {
+///   format!("user {}", id.id())
+/// }
+///
+/// let codec = HashidBuilder::new().with_salt("users").build().unwrap();
+/// let app: Router = Router::new().route("/users/{id}", get(show_user)).layer(Extension(Arc::new(codec)));
+/// ```
+impl<S: Send + Sync, T> FromRequestParts<S> for Hashid<T> {
+  type Rejection = Response;
+
+  async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Response> {
+    let Path(hash) = Path::<String>::from_request_parts(parts, state).await.map_err(IntoResponse::into_response)?;
+    match parts.extensions.get::<Arc<HashidCodec>>() {
+      Some(codec) => Hashid::parse(&hash, codec),
+      None => crate::global().and_then(|codec| Hashid::parse(&hash, codec))
+    }.map_err(IntoResponse::into_response)
+  }
+}
+
+/// Responds with a 404 Not Found for hashes that don't decode, as no resource can have this ID,
+/// and a 500 Internal Server Error for codecs that can't be built or IDs that can't be encoded.
+///
+/// Requires the `axum` feature.
+impl IntoResponse for Error {
+  fn into_response(self) -> Response {
+    if self.is_invalid_hash() {
+      (StatusCode::NOT_FOUND, self.to_string()).into_response()
+    } else {
+      StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    }
+  }
+}
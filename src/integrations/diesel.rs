@@ -0,0 +1,86 @@
+//! Diesel columns of `HashidI64`, and lookups by hashid.
+//!
+//! Requires the `diesel` feature.
+use ::diesel::backend::Backend;
+use ::diesel::deserialize::{self, FromSql};
+use ::diesel::dsl::Find;
+use ::diesel::expression::Expression;
+use ::diesel::query_dsl::methods::FindDsl;
+use ::diesel::query_dsl::{LoadQuery, RunQueryDsl};
+use ::diesel::result::{Error as DieselError, QueryResult};
+use ::diesel::serialize::{self, Output, ToSql};
+use ::diesel::sql_types::BigInt;
+use ::diesel::Table;
+
+use crate::HashidI64;
+
+/// Stored as the plain ID, `AsExpression` and `Queryable` are derived on `HashidI64`.
+/// ```no_run
+/// # use diesel::prelude::*;
+/// use hashids::HashidI64;
+/// # diesel::table! {
+/// #   users (id) {
+/// #     id -> BigInt,
+/// #     name -> Text,
+/// #   }
+/// # }
+///
+/// #[derive(Queryable)]
+/// struct User {
+///   id: HashidI64,
+///   name: String
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let conn = &mut SqliteConnection::establish(":memory:")?;
+/// let user = users::table.filter(users::id.eq(HashidI64::decode("NkK9")?)).first::<User>(conn)?;
+/// # Ok(())
+/// # }
+/// ```
+impl<DB> ToSql<BigInt, DB> for HashidI64 where DB: Backend, i64: ToSql<BigInt, DB> {
+  fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+    self.0.to_sql(out)
+  }
+}
+
+impl<DB> FromSql<BigInt, DB> for HashidI64 where DB: Backend, i64: FromSql<BigInt, DB> {
+  fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+    i64::from_sql(bytes).map(HashidI64)
+  }
+}
+
+/// Looks rows up by the hash of their `BIGINT` primary key, implemented for every Diesel table with one.
+/// ```no_run
+/// # use diesel::prelude::*;
+/// use hashids::{FindByHashid, HashidI64};
+/// # diesel::table! {
+/// #   users (id) {
+/// #     id -> BigInt,
+/// #     name -> Text,
+/// #   }
+/// # }
+/// # #[derive(Queryable)]
+/// # struct User {
+/// #   id: HashidI64,
+/// #   name: String
+/// # }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let conn = &mut SqliteConnection::establish(":memory:")?;
+/// let user: User = users::table.find_by_hashid(conn, "NkK9")?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait FindByHashid: Table + Sized where Self::PrimaryKey: Expression<SqlType = BigInt> {
+  /// Decodes the hash with the global codec, and loads the row with this primary key.
+  ///
+  /// A hash that doesn't decode to a single `i64` can't match any row,
+  /// so it returns a `diesel::result::Error::NotFound` like a missing row, without querying the database.
+  fn find_by_hashid<'query, Conn, U>(self, conn: &mut Conn, hash: &str) -> QueryResult<U>
+  where Self: FindDsl<i64>, Find<Self, i64>: LoadQuery<'query, Conn, U> {
+    let id = HashidI64::decode(hash).map_err(|_| DieselError::NotFound)?;
+    self.find(id.0).get_result(conn)
+  }
+}
+
+impl<T: Table> FindByHashid for T where T::PrimaryKey: Expression<SqlType = BigInt> {}
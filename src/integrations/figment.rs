@@ -0,0 +1,50 @@
+use ::figment::providers::Serialized;
+use ::figment::value::{Dict, Map};
+use ::figment::{Figment, Metadata, Profile, Provider};
+
+use crate::{HashidBuilder, HashidConfig};
+
+/// The key the settings are nested under.
+const KEY: &str = "hashids";
+
+/// Provides the config under the `hashids` key, as the defaults of a figment which files and environnment
+/// variables then override.
+///
+/// Requires the `figment` feature.
+/// ```
+/// use figment::Figment;
+/// use figment::providers::{Env, Format, Serialized, Toml};
+/// use hashids::{HashidBuilder, HashidConfig};
+///
+/// let figment = Figment::from(HashidConfig { min_length: 8, ..HashidConfig::default() })
+///   .merge(Toml::file("App.toml"))
+///   // APP_HASHIDS__SALT sets the salt, APP_HASHIDS__PREFIX the prefix...
+///   .merge(Env::prefixed("APP_").split("__"))
+/// # .merge(Serialized::default("hashids.salt", "this is my salt"))
+///   ;
+/// let codec = HashidBuilder::from_figment(&figment).unwrap().build().unwrap();
+/// assert_eq!(codec.encode(1).unwrap(), "gB0NV05e");
+/// ```
+impl Provider for HashidConfig {
+  fn metadata(&self) -> Metadata {
+    Metadata::named("hashids config")
+  }
+
+  fn data(&self) -> Result<Map<Profile, Dict>, ::figment::Error> {
+    Serialized::default(KEY, self).data()
+  }
+}
+
+impl HashidBuilder {
+  /// A builder with the settings under the `hashids` key of the figment, the missing ones taking their defaults.
+  ///
+  /// Without a salt, the builder falls back to the `HASHID_SALT` environnment variable.
+  ///
+  /// Requires the `figment` feature.
+  // Large, but what figment's own `extract` returns
+  #[allow(clippy::result_large_err)]
+  pub fn from_figment(figment: &Figment) -> Result<HashidBuilder, ::figment::Error> {
+    let config = if figment.contains(KEY) { figment.extract_inner(KEY)? } else { HashidConfig::default() };
+    Ok(HashidBuilder::from_config_or_env(config))
+  }
+}
@@ -0,0 +1,21 @@
+use ::juniper::{graphql_scalar, InputValue, ScalarValue, Value};
+
+/// A `Hashid` GraphQL scalar, the hash of an ID as a string, decoded with the global codec, see `hashids::init`.
+///
+/// Requires the `juniper` feature.
+#[graphql_scalar(with = hashid_scalar, parse_token(String))]
+type Hashid<T> = crate::Hashid<T>;
+
+mod hashid_scalar {
+  use super::*;
+
+  pub(super) fn to_output<T, S: ScalarValue>(hashid: &Hashid<T>) -> Value<S> {
+    Value::scalar(hashid.as_str().to_string())
+  }
+
+  pub(super) fn from_input<T, S: ScalarValue>(input: &InputValue<S>) -> Result<Hashid<T>, String> {
+    let hash = input.as_string_value().ok_or_else(|| format!("Expected `String`, found: {}", input))?;
+    let codec = crate::global().map_err(|err| err.to_string())?;
+    Hashid::parse(hash, codec).map_err(|err| err.to_string())
+  }
+}
@@ -0,0 +1,64 @@
+use ::metrics::{counter, Counter, SharedString};
+
+use crate::{Error, HashidMetrics};
+
+/// Counts what a codec does with the `metrics` crate, with a `codec` label telling codecs apart.
+///
+/// The counters are `hashids_encoded_total`, `hashids_decoded_total`, `hashids_cache_hits_total`,
+/// `hashids_cache_misses_total`, and `hashids_decode_failures_total`, labelled with the `code` of the error too.
+/// They are registered when created, so the recorder must be installed before.
+///
+/// Requires the `metrics` feature.
+/// ```
+/// use std::sync::Arc;
+/// use hashids::{HashidBuilder, HashidCounters};
+///
+/// let codec = HashidBuilder::new()
+///   .with_salt("this is my salt")
+///   .with_metrics(Arc::new(HashidCounters::new("users")))
+///   .build().unwrap();
+/// ```
+pub struct HashidCounters {
+  codec: SharedString,
+  encoded: Counter,
+  decoded: Counter,
+  cache_hits: Counter,
+  cache_misses: Counter
+}
+
+impl HashidCounters {
+  /// Registers the counters, labelled with the name of the codec.
+  pub fn new(codec: impl Into<String>) -> HashidCounters {
+    let codec = SharedString::from(codec.into());
+    HashidCounters {
+      encoded: counter!("hashids_encoded_total", "codec" => codec.clone()),
+      decoded: counter!("hashids_decoded_total", "codec" => codec.clone()),
+      cache_hits: counter!("hashids_cache_hits_total", "codec" => codec.clone()),
+      cache_misses: counter!("hashids_cache_misses_total", "codec" => codec.clone()),
+      codec
+    }
+  }
+}
+
+impl HashidMetrics for HashidCounters {
+  fn encoded(&self) {
+    self.encoded.increment(1);
+  }
+
+  fn decoded(&self) {
+    self.decoded.increment(1);
+  }
+
+  fn decode_failed(&self, error: &Error) {
+    // Registered on failure, as there is one counter per error code
+    counter!("hashids_decode_failures_total", "codec" => self.codec.clone(), "code" => error.code()).increment(1);
+  }
+
+  fn cache_hit(&self) {
+    self.cache_hits.increment(1);
+  }
+
+  fn cache_miss(&self) {
+    self.cache_misses.increment(1);
+  }
+}
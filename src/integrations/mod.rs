@@ -0,0 +1,46 @@
+//! Integrations with other crates, each behind the feature of the same name.
+
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(all(feature = "derive", feature = "serde"))]
+mod serde_derive;
+#[cfg(all(feature = "derive", feature = "serde"))]
+pub use self::serde_derive::HashidField;
+#[cfg(feature = "serde_with")]
+mod serde_with;
+#[cfg(feature = "serde_with")]
+pub use self::serde_with::HashidAs;
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "diesel")]
+pub use self::diesel::FindByHashid;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "sea-orm")]
+mod sea_orm;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "axum")]
+mod axum;
+#[cfg(feature = "rocket")]
+mod rocket;
+#[cfg(feature = "poem")]
+mod poem;
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+#[cfg(feature = "juniper")]
+mod juniper;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "figment")]
+mod figment;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use self::metrics::HashidCounters;
@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use ::poem::error::ResponseError;
+use ::poem::http::StatusCode;
+use ::poem::web::Path;
+use ::poem::{FromRequest, IntoResponse, Request, RequestBody, Response};
+use ::poem_openapi::registry::{MetaSchema, MetaSchemaRef};
+use ::poem_openapi::types::{ParseError, ParseFromJSON, ParseFromParameter, ParseResult, ToJSON, Type};
+use ::serde_json::Value;
+
+use crate::{Error, Hashid, HashidCodec};
+
+/// Extracts the route's only path parameter, decoded with the codec added to the request data as an `Arc<HashidCodec>`,
+/// or with the global codec if there's none.
+///
+/// Requires the `poem` feature.
+/// ```
+/// use std::sync::Arc;
+/// use poem::{handler, EndpointExt, Route, get};
+/// use hashids::{HashidBuilder, Hashid};
+/// struct User;
+///
+/// #[handler]
+/// fn show_user(id: Hashid<User>) -> String {
+///   format!("user {}", id.id())
+/// }
+///
+/// let codec = HashidBuilder::new().with_salt("users").build().unwrap();
+/// let app = Route::new().at("/users/:id", get(show_user)).data(Arc::new(codec));
+/// ```
+impl<'a, T> FromRequest<'a> for Hashid<T> {
+  async fn from_request(req: &'a Request, body: &mut RequestBody) -> ::poem::Result<Self> {
+    let Path(hash) = Path::<String>::from_request(req, body).await?;
+    let hashid = match req.data::<Arc<HashidCodec>>() {
+      Some(codec) => Hashid::parse(&hash, codec),
+      None => crate::global().and_then(|codec| Hashid::parse(&hash, codec))
+    }?;
+    Ok(hashid)
+  }
+}
+
+/// Responds with a 404 Not Found for hashes that don't decode, and a 500 Internal Server Error otherwise, as with axum.
+///
+/// Requires the `poem` feature.
+impl ResponseError for Error {
+  fn status(&self) -> StatusCode {
+    if self.is_invalid_hash() { StatusCode::NOT_FOUND } else { StatusCode::INTERNAL_SERVER_ERROR }
+  }
+
+  fn as_response(&self) -> Response {
+    if self.is_invalid_hash() {
+      self.to_string().with_status(StatusCode::NOT_FOUND).into_response()
+    } else {
+      StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    }
+  }
+}
+
+/// Documented as a string in the `hashid` format, for poem-openapi parameters and bodies, which use the global codec.
+///
+/// Requires the `poem` feature.
+impl<T> Type for Hashid<T> {
+  const IS_REQUIRED: bool = true;
+
+  type RawValueType = Self;
+
+  type RawElementValueType = Self;
+
+  fn name() -> Cow<'static, str> {
+    "string_hashid".into()
+  }
+
+  fn schema_ref() -> MetaSchemaRef {
+    MetaSchemaRef::Inline(Box::new(MetaSchema::new_with_format("string", "hashid")))
+  }
+
+  fn as_raw_value(&self) -> Option<&Self> {
+    Some(self)
+  }
+
+  fn raw_element_iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self> + 'a> {
+    Box::new(self.as_raw_value().into_iter())
+  }
+}
+
+/// Requires the `poem` feature.
+impl<T> ParseFromParameter for Hashid<T> {
+  fn parse_from_parameter(value: &str) -> ParseResult<Self> {
+    crate::global().and_then(|codec| Hashid::parse(value, codec)).map_err(ParseError::custom)
+  }
+}
+
+/// Requires the `poem` feature.
+impl<T> ParseFromJSON for Hashid<T> {
+  fn parse_from_json(value: Option<Value>) -> ParseResult<Self> {
+    match value.unwrap_or_default() {
+      Value::String(hash) => Self::parse_from_parameter(&hash).map_err(ParseError::propagate),
+      value => Err(ParseError::expected_type(value))
+    }
+  }
+}
+
+/// Requires the `poem` feature.
+impl<T> ToJSON for Hashid<T> {
+  fn to_json(&self) -> Option<Value> {
+    Some(Value::String(self.as_str().to_string()))
+  }
+}
@@ -0,0 +1,41 @@
+//! tokio-postgres and postgres columns of `HashidI64`, through postgres-types.
+//!
+//! Requires the `postgres` feature.
+use std::error::Error;
+
+use ::bytes::BytesMut;
+use ::postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::HashidI64;
+
+/// Stored as the plain ID, in a `BIGINT` column.
+/// ```no_run
+/// use hashids::HashidI64;
+///
+/// # async fn find(client: tokio_postgres::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let row = client.query_one("SELECT id, name FROM users WHERE id = $1", &[&HashidI64::decode("NkK9")?]).await?;
+/// let id: HashidI64 = row.get("id");
+/// # Ok(())
+/// # }
+/// ```
+impl ToSql for HashidI64 {
+  fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+    self.0.to_sql(ty, out)
+  }
+
+  fn accepts(ty: &Type) -> bool {
+    <i64 as ToSql>::accepts(ty)
+  }
+
+  to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for HashidI64 {
+  fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+    i64::from_sql(ty, raw).map(HashidI64)
+  }
+
+  fn accepts(ty: &Type) -> bool {
+    <i64 as FromSql>::accepts(ty)
+  }
+}
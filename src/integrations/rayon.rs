@@ -0,0 +1,42 @@
+use ::rayon::prelude::*;
+
+use crate::{Error, HashidCodec, PositiveInteger, Scratch};
+
+impl HashidCodec {
+  /// Same as `encode_many`, but encodes the IDs in parallel on the rayon thread pool.
+  ///
+  /// Requires the `rayon` feature.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let ids: Vec<u64> = (0..10_000).collect();
+  /// let hashes = codec.par_encode_many(&ids).unwrap();
+  /// assert_eq!(hashes[12], codec.encode(12).unwrap());
+  /// ```
+  pub fn par_encode_many<T: PositiveInteger + Copy + Sync>(&self, ids: &[T]) -> Result<Vec<String>, Error> {
+    ids.par_iter()
+      .map_init(Scratch::default, |scratch, id| {
+        let number = self.id_to_number(*id)?;
+        let mut hash = String::new();
+        self.encode_vec_with(&[number], &mut hash, scratch.symbols(self.alphabet.len()))?;
+        Ok(hash)
+      })
+      .collect()
+  }
+
+  /// Decodes hashes made of a single ID each, such as those of `par_encode_many`, in parallel on the rayon thread pool.
+  ///
+  /// Returns the first error in the order of the hashes, if any hash is invalid, like `decode_single` would.
+  ///
+  /// Requires the `rayon` feature.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// assert_eq!(codec.par_decode_many(&["NkK9", "0rDd"]), Ok(vec![12345, 5]));
+  /// ```
+  pub fn par_decode_many<S: AsRef<str> + Sync>(&self, hashes: &[S]) -> Result<Vec<u64>, Error> {
+    hashes.par_iter()
+      .map(|hash| self.decode_single(hash.as_ref()))
+      .collect()
+  }
+}
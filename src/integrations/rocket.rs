@@ -0,0 +1,53 @@
+//! Rocket path segments, form values and URIs of `Hashid`, decoded and encoded with the global codec.
+//!
+//! Requires the `rocket` feature.
+use std::fmt;
+
+use ::rocket::form::{self, FromFormField, ValueField};
+use ::rocket::http::impl_from_uri_param_identity;
+use ::rocket::http::uri::fmt::{Formatter, Part, UriDisplay};
+use ::rocket::request::FromParam;
+
+use crate::{Error, Hashid};
+
+/// Decodes path segments with the global codec, see `hashids::init`, as Rocket's guards can't access managed state.
+/// ```no_run
+/// # use rocket::{get, uri};
+/// use hashids::{Hashid, HashidBuilder};
+/// struct User;
+///
+/// #[get("/users/<id>")]
+/// fn show_user(id: Hashid<User>) -> String {
+///   format!("user {}", id.id())
+/// }
+///
+/// # fn main() {
+/// hashids::init(HashidBuilder::new().with_salt("this is my salt")).unwrap();
+/// let user_id: Hashid<User> = Hashid::new(12345, hashids::global().unwrap()).unwrap();
+/// assert_eq!(uri!(show_user(user_id)).to_string(), "/users/NkK9");
+/// # }
+/// ```
+impl<'a, T> FromParam<'a> for Hashid<T> {
+  type Error = Error;
+
+  fn from_param(param: &'a str) -> Result<Self, Error> {
+    Hashid::parse(param, crate::global()?)
+  }
+}
+
+/// Decodes form and query values with the global codec.
+impl<'v, T> FromFormField<'v> for Hashid<T> {
+  fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+    crate::global().and_then(|codec| Hashid::parse(field.value, codec))
+      .map_err(|err| form::Error::validation(err.to_string()).into())
+  }
+}
+
+/// Writes the hash, percent-encoded in case the alphabet has characters that aren't allowed in URIs.
+impl<T, P: Part> UriDisplay<P> for Hashid<T> {
+  fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+    UriDisplay::<P>::fmt(self.as_str(), f)
+  }
+}
+
+impl_from_uri_param_identity!((T) Hashid<T>);
@@ -0,0 +1,29 @@
+//! rusqlite columns of `HashidI64`.
+//!
+//! Requires the `rusqlite` feature.
+use ::rusqlite::types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use ::rusqlite::Result;
+
+use crate::HashidI64;
+
+/// Stored as the plain ID, in an `INTEGER` column.
+/// ```no_run
+/// use hashids::HashidI64;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let conn = rusqlite::Connection::open_in_memory()?;
+/// let name: String = conn.query_row("SELECT name FROM users WHERE id = ?1", [HashidI64::decode("NkK9")?], |row| row.get(0))?;
+/// # Ok(())
+/// # }
+/// ```
+impl ToSql for HashidI64 {
+  fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+    self.0.to_sql()
+  }
+}
+
+impl FromSql for HashidI64 {
+  fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+    i64::column_result(value).map(HashidI64)
+  }
+}
@@ -0,0 +1,64 @@
+use std::borrow::Cow;
+
+use ::schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::{Hashid, HashidI64};
+
+/// A string in the `hashid` format, with the pattern of the global codec's hashes, see `HashidCodec::hash_pattern`.
+///
+/// The pattern is left out if the global codec isn't set yet.
+/// It isn't built from the defaults here, as generating a schema would then make a later `hashids::init` fail.
+fn hashid_schema() -> Schema {
+  let mut schema = json_schema!({
+    "type": "string",
+    "format": "hashid"
+  });
+  if let Some(codec) = crate::global::initialized() {
+    schema.insert("pattern".to_string(), codec.hash_pattern().into());
+  }
+  schema
+}
+
+/// Requires the `schemars` feature.
+/// ```
+/// use hashids::{HashidBuilder, Hashid};
+/// struct User;
+///
+/// // Before `init`, the schema has no pattern, and doesn't set the global codec
+/// std::env::set_var("HASHID_SALT", "this is my salt");
+/// let schema = schemars::schema_for!(Hashid<User>);
+/// assert!(schema.get("pattern").is_none());
+///
+/// hashids::init(HashidBuilder::new().with_salt("this is my salt").with_prefix("usr_")).unwrap();
+/// let schema = schemars::schema_for!(Hashid<User>);
+/// assert_eq!(schema.get("type").unwrap(), "string");
+/// assert!(schema.get("pattern").unwrap().as_str().unwrap().starts_with("^usr_["));
+/// ```
+impl<T> JsonSchema for Hashid<T> {
+  fn inline_schema() -> bool {
+    true
+  }
+
+  fn schema_name() -> Cow<'static, str> {
+    "Hashid".into()
+  }
+
+  fn json_schema(_: &mut SchemaGenerator) -> Schema {
+    hashid_schema()
+  }
+}
+
+/// Requires the `schemars` feature.
+impl JsonSchema for HashidI64 {
+  fn inline_schema() -> bool {
+    true
+  }
+
+  fn schema_name() -> Cow<'static, str> {
+    "HashidI64".into()
+  }
+
+  fn json_schema(_: &mut SchemaGenerator) -> Schema {
+    hashid_schema()
+  }
+}
@@ -0,0 +1,79 @@
+//! SeaORM columns of `HashidI64`.
+//!
+//! Requires the `sea-orm` feature.
+use ::sea_orm::sea_query::{ArrayType, ColumnType, Nullable, ValueType, ValueTypeErr};
+use ::sea_orm::{ActiveValue, ColIdx, DbErr, IntoActiveValue, QueryResult, TryFromU64, TryGetError, TryGetable, Value};
+
+use crate::HashidI64;
+
+/// Stored as the plain ID in a `BIGINT` column, so entities can use it as the type of their primary and foreign keys.
+/// ```no_run
+/// use hashids::HashidI64;
+/// use sea_orm::entity::prelude::*;
+/// # // The derive needs it in scope, which the 2021 prelude does
+/// # use std::convert::TryInto;
+///
+/// #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+/// #[sea_orm(table_name = "users")]
+/// pub struct Model {
+///   #[sea_orm(primary_key)]
+///   pub id: HashidI64,
+///   pub name: String
+/// }
+/// # #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+/// # pub enum Relation {}
+/// # impl ActiveModelBehavior for ActiveModel {}
+///
+/// # async fn find(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
+/// let user = Entity::find_by_id(HashidI64::decode("NkK9")?).one(&db).await?;
+/// # Ok(())
+/// # }
+/// ```
+impl From<HashidI64> for Value {
+  fn from(id: HashidI64) -> Value {
+    Value::BigInt(Some(id.0))
+  }
+}
+
+impl TryGetable for HashidI64 {
+  fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+    i64::try_get_by(res, index).map(HashidI64)
+  }
+}
+
+impl ValueType for HashidI64 {
+  fn try_from(value: Value) -> Result<Self, ValueTypeErr> {
+    <i64 as ValueType>::try_from(value).map(HashidI64)
+  }
+
+  fn type_name() -> String {
+    "HashidI64".to_string()
+  }
+
+  fn array_type() -> ArrayType {
+    ArrayType::BigInt
+  }
+
+  fn column_type() -> ColumnType {
+    ColumnType::BigInteger
+  }
+}
+
+impl Nullable for HashidI64 {
+  fn null() -> Value {
+    Value::BigInt(None)
+  }
+}
+
+impl IntoActiveValue<HashidI64> for HashidI64 {
+  fn into_active_value(self) -> ActiveValue<HashidI64> {
+    ActiveValue::Set(self)
+  }
+}
+
+/// Lets `HashidI64` be the type of auto-incremented primary keys.
+impl TryFromU64 for HashidI64 {
+  fn try_from_u64(id: u64) -> Result<Self, DbErr> {
+    i64::try_from_u64(id).map(HashidI64)
+  }
+}
@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+use ::serde::de::{self, Deserialize, Deserializer, Visitor};
+use ::serde::ser::{self, Serialize, Serializer};
+
+use crate::{Hashid, HashidI64};
+
+/// Serializes as the hash, so raw IDs never appear in the output.
+///
+/// Requires the `serde` feature.
+impl<T> Serialize for Hashid<T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(self.as_str())
+  }
+}
+
+/// Deserializes a hash with the global codec, see `hashids::init`.
+///
+/// Requires the `serde` feature.
+/// ```
+/// use hashids::{HashidBuilder, Hashid};
+/// use serde::{Serialize, Deserialize};
+/// struct User;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct UserDto {
+///   id: Hashid<User>,
+///   name: String
+/// }
+///
+/// hashids::init(HashidBuilder::new().with_salt("this is my salt")).unwrap();
+/// let user: UserDto = serde_json::from_str(r#"{"id":"NkK9","name":"Jane"}"#).unwrap();
+/// assert_eq!(user.id.id(), 12345);
+/// assert_eq!(serde_json::to_string(&user).unwrap(), r#"{"id":"NkK9","name":"Jane"}"#);
+/// ```
+impl<'de, T> Deserialize<'de> for Hashid<T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_str(HashidVisitor(PhantomData))
+  }
+}
+
+struct HashidVisitor<T>(PhantomData<fn() -> T>);
+
+impl<T> Visitor<'_> for HashidVisitor<T> {
+  type Value = Hashid<T>;
+
+  fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("a hashid")
+  }
+
+  fn visit_str<E: de::Error>(self, hash: &str) -> Result<Self::Value, E> {
+    let codec = crate::global().map_err(E::custom)?;
+    Hashid::parse(hash, codec).map_err(E::custom)
+  }
+}
+
+/// Serializes as the hash with the global codec, see `hashids::init`.
+///
+/// Requires the `serde` feature.
+impl Serialize for HashidI64 {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let hash = self.encode().map_err(ser::Error::custom)?;
+    serializer.serialize_str(&hash)
+  }
+}
+
+/// Requires the `serde` feature.
+impl<'de> Deserialize<'de> for HashidI64 {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let hash = <Cow<'de, str>>::deserialize(deserializer)?;
+    HashidI64::decode(&hash).map_err(de::Error::custom)
+  }
+}
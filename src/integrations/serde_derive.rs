@@ -0,0 +1,50 @@
+use std::borrow::Cow;
+
+use ::serde::de::{self, Deserialize, Deserializer};
+use ::serde::ser::{self, Serializer};
+
+use crate::HashidCodec;
+
+/// Field types supported by `derive(HashidSerde)`: the integers that can be both encoded and decoded, and options of them.
+#[doc(hidden)]
+pub trait HashidField: Sized {
+  fn serialize_hashid<S: Serializer>(&self, codec: &HashidCodec, serializer: S) -> Result<S::Ok, S::Error>;
+
+  fn deserialize_hashid<'de, D: Deserializer<'de>>(codec: &HashidCodec, deserializer: D) -> Result<Self, D::Error>;
+}
+
+macro_rules! impl_hashid_field {
+  ($($integer:ty),*) => {
+    $(
+      impl HashidField for $integer {
+        fn serialize_hashid<S: Serializer>(&self, codec: &HashidCodec, serializer: S) -> Result<S::Ok, S::Error> {
+          let hash = codec.encode(*self).map_err(ser::Error::custom)?;
+          serializer.serialize_str(&hash)
+        }
+
+        fn deserialize_hashid<'de, D: Deserializer<'de>>(codec: &HashidCodec, deserializer: D) -> Result<Self, D::Error> {
+          let hash = <Cow<'de, str>>::deserialize(deserializer)?;
+          codec.decode_id(&hash).map_err(de::Error::custom)
+        }
+      }
+
+      impl HashidField for Option<$integer> {
+        fn serialize_hashid<S: Serializer>(&self, codec: &HashidCodec, serializer: S) -> Result<S::Ok, S::Error> {
+          match self {
+            Some(id) => id.serialize_hashid(codec, serializer),
+            None => serializer.serialize_none()
+          }
+        }
+
+        fn deserialize_hashid<'de, D: Deserializer<'de>>(codec: &HashidCodec, deserializer: D) -> Result<Self, D::Error> {
+          match <Option<Cow<'de, str>>>::deserialize(deserializer)? {
+            Some(hash) => codec.decode_id(&hash).map(Some).map_err(de::Error::custom),
+            None => Ok(None)
+          }
+        }
+      }
+    )*
+  };
+}
+
+impl_hashid_field!(u32, u64, u128, i32, i64);
@@ -0,0 +1,48 @@
+use ::serde::de::{Deserialize, Deserializer, Error as _};
+use ::serde::ser::{Error as _, Serializer};
+use ::serde_with::{DeserializeAs, SerializeAs};
+
+use crate::{FromHashidNumber, PositiveInteger};
+
+/// Adapter for `serde_with`, to serialize plain integer fields as hashes with `#[serde_as(as = "HashidAs")]`.
+///
+/// Uses the global codec, see `hashids::init`. Also works in containers, such as `Option<HashidAs>` or `Vec<HashidAs>`.
+///
+/// Requires the `serde_with` feature.
+/// ```
+/// use hashids::{HashidBuilder, HashidAs};
+/// use serde::{Serialize, Deserialize};
+/// use serde_with::serde_as;
+///
+/// #[serde_as]
+/// #[derive(Serialize, Deserialize)]
+/// struct UserDto {
+///   #[serde_as(as = "HashidAs")]
+///   id: u64,
+///   #[serde_as(as = "Option<HashidAs>")]
+///   manager_id: Option<i64>
+/// }
+///
+/// hashids::init(HashidBuilder::new().with_salt("this is my salt")).unwrap();
+/// let user: UserDto = serde_json::from_str(r#"{"id":"NkK9","manager_id":"0rDd"}"#).unwrap();
+/// assert_eq!((user.id, user.manager_id), (12345, Some(5)));
+/// assert_eq!(serde_json::to_string(&user).unwrap(), r#"{"id":"NkK9","manager_id":"0rDd"}"#);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HashidAs;
+
+impl<T: PositiveInteger + Copy> SerializeAs<T> for HashidAs {
+  fn serialize_as<S: Serializer>(id: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    let codec = crate::global().map_err(S::Error::custom)?;
+    let hash = codec.encode(*id).map_err(S::Error::custom)?;
+    serializer.serialize_str(&hash)
+  }
+}
+
+impl<'de, T: FromHashidNumber> DeserializeAs<'de, T> for HashidAs {
+  fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    let hash = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+    let codec = crate::global().map_err(D::Error::custom)?;
+    codec.decode_id(&hash).map_err(D::Error::custom)
+  }
+}
@@ -0,0 +1,55 @@
+//! sqlx columns of `HashidI64`.
+//!
+//! Requires the `sqlx` feature.
+use ::sqlx::encode::IsNull;
+use ::sqlx::error::BoxDynError;
+use ::sqlx::{Database, Decode, Encode, Type};
+
+use crate::HashidI64;
+
+/// Stored as the plain ID, in any column an `i64` can be, such as `BIGINT` for Postgres, MySQL and SQLite.
+/// ```no_run
+/// use hashids::HashidI64;
+///
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///   id: HashidI64,
+///   name: String
+/// }
+///
+/// # async fn find(pool: sqlx::SqlitePool) -> Result<(), Box<dyn std::error::Error>> {
+/// let user = sqlx::query_as::<_, User>("SELECT id, name FROM users WHERE id = $1")
+///   .bind(HashidI64::decode("NkK9")?)
+///   .fetch_one(&pool).await?;
+/// # Ok(())
+/// # }
+/// ```
+impl<DB: Database> Type<DB> for HashidI64 where i64: Type<DB> {
+  fn type_info() -> DB::TypeInfo {
+    <i64 as Type<DB>>::type_info()
+  }
+
+  fn compatible(ty: &DB::TypeInfo) -> bool {
+    <i64 as Type<DB>>::compatible(ty)
+  }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for HashidI64 where i64: Encode<'q, DB> {
+  fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+    self.0.encode_by_ref(buf)
+  }
+
+  fn produces(&self) -> Option<DB::TypeInfo> {
+    self.0.produces()
+  }
+
+  fn size_hint(&self) -> usize {
+    self.0.size_hint()
+  }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for HashidI64 where i64: Decode<'r, DB> {
+  fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+    i64::decode(value).map(HashidI64)
+  }
+}
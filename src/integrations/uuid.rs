@@ -0,0 +1,39 @@
+use ::uuid::Uuid;
+use std::convert::TryFrom;
+
+use crate::{Error, HashidCodec};
+
+impl HashidCodec {
+  /// Converts a UUID to a Hashid String, by encoding its two 64 bits halves as two numbers.
+  ///
+  /// Can only fail with an `Error::HashTooLong` if the codec has a maximum length.
+  ///
+  /// Requires the `uuid` feature.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// use uuid::Uuid;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let uuid = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+  /// let hash = codec.encode_uuid(uuid).unwrap();
+  /// assert_eq!(codec.decode_uuid(&hash), Ok(uuid));
+  /// ```
+  pub fn encode_uuid(&self, uuid: Uuid) -> Result<String, Error> {
+    let (high, low) = uuid.as_u64_pair();
+    self.encode_vec(&[high as u128, low as u128])
+  }
+
+  /// Converts a Hashid created by `encode_uuid` back to the UUID.
+  ///
+  /// Returns an `Error::UnexpectedNumberCount` if the hash isn't made of exactly two numbers.
+  pub fn decode_uuid(&self, hash: &str) -> Result<Uuid, Error> {
+    let numbers = self.decode_numbers(hash)?;
+    match numbers.as_slice() {
+      [high, low] => {
+        let high = u64::try_from(*high).map_err(|_| Error::NumberOverflow)?;
+        let low = u64::try_from(*low).map_err(|_| Error::NumberOverflow)?;
+        Ok(Uuid::from_u64_pair(high, low))
+      },
+      _ => Err(Error::UnexpectedNumberCount { expected: 2, got: numbers.len() })
+    }
+  }
+}
@@ -0,0 +1,40 @@
+//! Lazy encoding of iterators of IDs, for bulk exports that shouldn't collect intermediate vectors.
+use crate::{Error, HashidCodec, PositiveInteger};
+
+/// Adds `encode_with` to any iterator of `PositiveInteger`, bring it in scope to use it.
+///
+/// ```
+/// use hashids::{HashidBuilder, EncodeIterExt};
+/// let codec = HashidBuilder::new().with_salt("this is my salt").with_length(2).build().unwrap();
+/// let ids: Vec<i64> = vec![1, 2, 3];
+/// let hashes: Result<Vec<String>, _> = ids.iter().encode_with(&codec).collect();
+/// assert_eq!(hashes.unwrap(), vec!["NV", "6m", "yD"]);
+/// ```
+pub trait EncodeIterExt: Iterator + Sized {
+  /// Encodes each ID only when the next item is requested.
+  fn encode_with(self, codec: &HashidCodec) -> EncodeIter<'_, Self>;
+}
+
+impl<I> EncodeIterExt for I where I: Iterator, I::Item: PositiveInteger {
+  fn encode_with(self, codec: &HashidCodec) -> EncodeIter<'_, Self> {
+    EncodeIter { iter: self, codec }
+  }
+}
+
+/// Iterator returned by `EncodeIterExt::encode_with`, yielding the result of encoding each ID.
+pub struct EncodeIter<'a, I> {
+  iter: I,
+  codec: &'a HashidCodec
+}
+
+impl<'a, I> Iterator for EncodeIter<'a, I> where I: Iterator, I::Item: PositiveInteger {
+  type Item = Result<String, Error>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(|id| self.codec.encode(id))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
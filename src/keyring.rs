@@ -0,0 +1,141 @@
+//! Decoding hashes made with previous salts, to rotate salts without breaking existing links.
+use crate::{Error, HashidBuilder, HashidCodec, PositiveInteger};
+
+/// Codecs sharing the same settings but different salts: encodes with the newest salt,
+/// and decodes with each salt in turn, from the newest to the oldest.
+/// ```
+/// use hashids::HashidKeyring;
+/// let old = HashidKeyring::new(&["this is my salt"], |builder| builder.with_length(6)).unwrap();
+/// let old_hash = old.encode(12345).unwrap();
+///
+/// let keyring = HashidKeyring::new(&["my new salt", "this is my salt"], |builder| builder.with_length(6)).unwrap();
+/// assert_ne!(keyring.encode(12345).unwrap(), old_hash);
+/// // Decoded with the salt at index 1
+/// assert_eq!(keyring.decode(&old_hash), Ok((vec![12345], 1)));
+/// ```
+#[derive(Debug)]
+pub struct HashidKeyring {
+  codecs: Vec<HashidCodec>
+}
+
+impl HashidKeyring {
+  /// Builds a codec for each salt, newest first, configuring each builder with `configure` once its salt is set.
+  ///
+  /// Returns an `Error::MissingSalt` if there are no salts, or the first error of building a codec.
+  pub fn new<F: Fn(HashidBuilder) -> HashidBuilder>(salts: &[&str], configure: F) -> Result<HashidKeyring, Error> {
+    if salts.is_empty() {
+      return Err(Error::MissingSalt)
+    }
+    let codecs = salts.iter()
+      .map(|salt| configure(HashidBuilder::new().with_salt(salt)).build())
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(HashidKeyring { codecs })
+  }
+
+  /// Uses already built codecs, newest first, which should only differ by their salt.
+  ///
+  /// Returns an `Error::MissingSalt` if there are no codecs.
+  pub fn from_codecs(codecs: Vec<HashidCodec>) -> Result<HashidKeyring, Error> {
+    if codecs.is_empty() {
+      return Err(Error::MissingSalt)
+    }
+    Ok(HashidKeyring { codecs })
+  }
+
+  /// The codec of the newest salt, used to encode.
+  pub fn current(&self) -> &HashidCodec {
+    &self.codecs[0]
+  }
+
+  /// The codecs of every salt, newest first.
+  pub fn codecs(&self) -> &[HashidCodec] {
+    &self.codecs
+  }
+
+  /// Encodes with the newest salt.
+  pub fn encode<T: PositiveInteger>(&self, id: T) -> Result<String, Error> {
+    self.current().encode(id)
+  }
+
+  /// Decodes with the first salt that produced the hash, trying the newest first,
+  /// and returns the numbers along with the index of that salt.
+  ///
+  /// If no salt matches, returns the error of the newest salt.
+  pub fn decode(&self, hash: &str) -> Result<(Vec<usize>, usize), Error> {
+    let mut first_error = None;
+    for (index, codec) in self.codecs.iter().enumerate() {
+      // Only failing with every salt is reported, not the older salts failing on newer hashes
+      match codec.decode_quietly(hash) {
+        Ok(numbers) => return codec.report_decode(Ok((numbers, index)), hash),
+        Err(err) => { first_error.get_or_insert(err); }
+      }
+    }
+    let err = first_error.expect("a keyring holds at least one codec");
+    self.current().report_decode(Err(err), hash)
+  }
+}
+
+/// Codecs identified by a version character, prepended to every hash so decoding goes straight to the right codec.
+///
+/// The version characters must not be part of any codec's alphabet, separators or guards.
+/// Unlike `HashidKeyring`, nothing is decoded by trial and error, at the cost of one character per hash.
+/// ```
+/// use hashids::{HashidBuilder, HashidVersions};
+/// let versions = HashidVersions::new()
+///     .with_version('-', HashidBuilder::new().with_salt("this is my salt").build().unwrap()).unwrap()
+///     .with_version('_', HashidBuilder::new().with_salt("my new salt").build().unwrap()).unwrap();
+/// // Encodes with the last version added
+/// let hash = versions.encode(12345).unwrap();
+/// assert!(hash.starts_with('_'));
+/// assert_eq!(versions.decode(&hash), Ok((vec![12345], '_')));
+/// assert_eq!(versions.decode("-NkK9"), Ok((vec![12345], '-')));
+/// ```
+#[derive(Debug, Default)]
+pub struct HashidVersions {
+  versions: Vec<(char, HashidCodec)>
+}
+
+impl HashidVersions {
+  pub fn new() -> HashidVersions {
+    HashidVersions { versions: Vec::new() }
+  }
+
+  /// Adds a version, which becomes the one used to encode.
+  ///
+  /// Returns an `Error::InvalidVersionCharacter` if the character is already a version,
+  /// or can be part of the hashes of this codec or another one.
+  pub fn with_version(mut self, version: char, codec: HashidCodec) -> Result<HashidVersions, Error> {
+    let taken = codec.uses_character(version) || self.versions.iter().any(|(other, codec)| *other == version || codec.uses_character(version))
+      || self.versions.iter().any(|(other, _)| codec.uses_character(*other));
+    if taken {
+      return Err(Error::InvalidVersionCharacter)
+    }
+    self.versions.push((version, codec));
+    Ok(self)
+  }
+
+  /// The version character and codec used to encode, `None` if no version was added.
+  pub fn current(&self) -> Option<(char, &HashidCodec)> {
+    self.versions.last().map(|(version, codec)| (*version, codec))
+  }
+
+  /// Encodes with the last version added, prepending its character.
+  ///
+  /// Returns an `Error::NoVersion` if no version was added.
+  pub fn encode<T: PositiveInteger>(&self, id: T) -> Result<String, Error> {
+    let (version, codec) = self.current().ok_or(Error::NoVersion)?;
+    let mut hash = String::new();
+    hash.push(version);
+    codec.encode_into(id, &mut hash)?;
+    Ok(hash)
+  }
+
+  /// Decodes with the codec of the version the hash starts with, and returns the numbers along with that version.
+  ///
+  /// Returns an `Error::UnknownVersion` if the hash doesn't start with a version character.
+  pub fn decode(&self, hash: &str) -> Result<(Vec<usize>, char), Error> {
+    let version = hash.chars().next().ok_or(Error::EmptyHash)?;
+    let (_, codec) = self.versions.iter().find(|(other, _)| *other == version).ok_or(Error::UnknownVersion)?;
+    Ok((codec.decode(&hash[version.len_utf8()..])?, version))
+  }
+}
@@ -10,36 +10,271 @@
 //! - Lazy performance hacks to prentend it's fast
 //! - An inconsistent amount of documentation to make sure you are confused.
 //! - Returns so many Errors for your pleasure to handle 
-//! - Integration with serde and diesel, "coming soon"
+//! - Integration with serde, serde_with, diesel, sqlx, SeaORM, rusqlite, postgres, axum, Rocket, poem, async-graphql, juniper, schemars and figment
+//! - Callbacks counting encoded and decoded hashes, and counters for the `metrics` crate with the `metrics` feature
+//! - A `hashids` command line tool, with the `cli` feature
+//! - WebAssembly bindings for JavaScript, with the `wasm` feature, and a C interface, with the `ffi` feature
+//! - Swift and Kotlin bindings, with the `uniffi` feature, and a Ruby extension in the `hashids-ruby` crate
+use std::borrow::Cow;
 use std::collections::{HashSet};
-use regex::Regex;
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+mod iter;
+pub use iter::{EncodeIter, EncodeIterExt};
+pub mod alphabet;
+#[cfg(feature = "blocklist")]
+pub mod blocklist;
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "cache")]
+pub use cache::CachedCodec;
+mod buffers;
+use buffers::{with_string, with_symbols, HashOut, SliceOut};
+mod case;
+pub use case::Case;
+pub mod compat;
+mod config;
+pub use config::HashidConfig;
+use compat::Compat;
+mod diagnostic;
+pub use diagnostic::{Diagnostic, DiagnosticKind, Severity};
+mod global;
+pub use global::{init, global, encode, decode};
+mod hashid;
+pub use hashid::{Hashid, HashidI64};
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fpe")]
+mod fpe;
+mod integrations;
+#[cfg(feature = "diesel")]
+pub use integrations::FindByHashid;
+#[cfg(feature = "serde_with")]
+pub use integrations::HashidAs;
+#[cfg(feature = "metrics")]
+pub use integrations::HashidCounters;
+mod keyring;
+mod macros;
+pub use keyring::{HashidKeyring, HashidVersions};
+#[cfg(feature = "mac")]
+mod mac;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+mod observer;
+pub use observer::HashidMetrics;
+use observer::Observer;
+mod pattern;
+mod relay;
+pub use relay::GlobalId;
+pub mod snowflake;
+mod timestamp;
+mod trace;
+mod tuple;
+pub use tuple::{CompositeId, HashidTuple};
+#[cfg(feature = "test-support")]
+pub mod test_support;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+#[cfg(feature = "derive")]
+pub use hashids_derive::HashidId;
+#[cfg(all(feature = "derive", feature = "serde"))]
+pub use hashids_derive::HashidSerde;
+
+/// Used by the code `derive(HashidSerde)` generates, not part of the public API.
+#[cfg(all(feature = "derive", feature = "serde"))]
+#[doc(hidden)]
+pub mod __private {
+  pub use ::serde;
+  pub use crate::integrations::HashidField;
+}
 
-const ENV_KEY: &'static str = "HASHID_SALT";
-const DEFAULT_ALPHABET: &'static str =  "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
+const ENV_KEY: &str = "HASHID_SALT";
+const DEFAULT_ALPHABET: &str =  "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
 const DEFAULT_MIN_LENGTH : usize = 4;
-const DEFAULT_SEPARATORS: &'static str = "cfhistuCFHISTU";
+const DEFAULT_SEPARATORS: &str = "cfhistuCFHISTU";
 const SEPARATOR_DIV: f32 = 3.5;
 const GUARD_DIV: usize = 12;
 const MIN_ALPHABET_LENGTH: usize = 16;
+const MIN_SEPARATORS_LENGTH: usize = 2;
+// What must be left of the alphabet once custom separators are taken out: a guard, and 2 symbols to encode numbers.
+const MIN_ALPHABET_WITHOUT_SEPARATORS_LENGTH: usize = 3;
 
-#[derive(Debug, PartialEq)]
+/// Reads environnment variables by name, see `HashidBuilder::from_env_source`.
+type EnvSource = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+#[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
 pub enum Error {
   /// A unique salt must be provided when building the HashidCodec. There are two ways to do so: 
   /// - using either or the `with_salt`, `with_string_salt`, `with_hashid_salt` API
-  /// - setting a `HASHID_SALT` environnment variable.
+  /// - setting a `HASHID_SALT` environnment variable, or the one given to `with_env_key`.
+  ///
   /// A salt is just a string, that has to be provided to provide a unique (compared to other packages using the same hashing algorithm)
   /// and repeatable (it must not change, so the encoding and decoding of a string/integer yields the same result.)
   MissingSalt,
-  NonAsciiSalt,
-  InvalidAlphabetLength,
+  /// The salt must be ASCII, the first character that isn't is given.
+  NonAsciiSalt { first_bad_char: char },
+  /// The alphabet has fewer unique characters than the minimum.
+  InvalidAlphabetLength { got: usize, min: usize },
+  /// No longer returned, alphabets can be made of any characters.
   NonAsciiAlphabet,
   InvalidInputId,
   NonHexString,
   EmptyHash,
-  InvalidHash
+  /// The hash only contains valid characters, but isn't one this codec could have produced:
+  /// re-encoding the decoded numbers doesn't give the same hash back, which usually means it was made with another salt or tampered with.
+  InvalidHash,
+  /// The hash contains a character that is neither in the alphabet, nor a separator or a guard,
+  /// found at the given byte offset.
+  InvalidCharacter { character: char, position: usize },
+  /// The hash decoded to a different amount of numbers than the decoding method expects,
+  /// for instance a hash made of several numbers given to `decode_single`.
+  UnexpectedNumberCount { expected: usize, got: usize },
+  /// A decoded number is too large for the integer type it is returned as,
+  /// for instance a hash made from a `u128` given to `decode`, which returns `usize`.
+  NumberOverflow,
+  /// Custom separators must be characters of the alphabet, at least 2 of them,
+  /// and leave at least 3 characters in the alphabet.
+  InvalidSeparators,
+  /// Custom guards must be characters of the alphabet, at least 1 of them, different from the custom separators,
+  /// and leave enough characters in the alphabet for separators and encoding.
+  InvalidGuards,
+  /// The salt is shorter, or made of fewer distinct characters, than required by `require_salt_strength`.
+  WeakSalt,
+  /// The hash doesn't start with the prefix set with `with_prefix`, so it is likely the ID of another type of entity.
+  InvalidPrefix,
+  /// The hash doesn't end with the suffix set with `with_suffix`.
+  InvalidSuffix,
+  /// The key given to `with_mac_key` is empty, or the tag length isn't between 4 and 16 characters.
+  InvalidMacSettings,
+  /// The authentication tag at the end of the hash doesn't match, so the hash was forged or tampered with.
+  InvalidMac,
+  /// The key given to `with_fpe_key` is empty.
+  InvalidFpeKey,
+  /// The maximum length is 0, or shorter than the minimum length.
+  InvalidMaxLength,
+  /// The hash is longer than the maximum length, the number is too large to fit in it.
+  HashTooLong { length: usize, max: usize },
+  /// A version character of `HashidVersions` is already used, or can be part of hashes.
+  InvalidVersionCharacter,
+  /// The hash doesn't start with any version character of `HashidVersions`.
+  UnknownVersion,
+  /// `HashidVersions` has no version to encode with.
+  NoVersion,
+  /// The timestamp given to `encode_with_timestamp` is before the codec's epoch, or the decoded one is out of range.
+  InvalidTimestamp,
+  /// `hashids::init` was called after the global codec was already set.
+  AlreadyInitialized,
+  /// The global ID isn't base64, doesn't hold a type name and a hash separated by `:`, or isn't of the expected type.
+  InvalidGlobalId,
+  /// The buffer given to `encode_to_slice`, of the given length in bytes, is too small for the hash.
+  BufferTooSmall { capacity: usize },
+  /// The file given to `HashidBuilder::from_file` can't be read, or its settings are invalid, for the given reason.
+  InvalidConfigFile { path: std::path::PathBuf, reason: String }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::MissingSalt => write!(f, "no salt was provided, through the builder nor its environnment variable, `{}` by default", ENV_KEY),
+      Error::NonAsciiSalt { first_bad_char } => write!(f, "the salt contains the non-ASCII character {:?}", first_bad_char),
+      Error::InvalidAlphabetLength { got, min } => write!(f, "the alphabet has {} unique characters, at least {} are required", got, min),
+      Error::NonAsciiAlphabet => f.write_str("the alphabet contains non-ASCII characters"),
+      Error::InvalidInputId => f.write_str("the ID can't be encoded"),
+      Error::NonHexString => f.write_str("the string is not hexadecimal"),
+      Error::EmptyHash => f.write_str("the hash is empty"),
+      Error::InvalidHash => f.write_str("the hash wasn't produced by this codec"),
+      Error::InvalidCharacter { character, position } => write!(f, "invalid character {:?} in the hash at position {}", character, position),
+      Error::UnexpectedNumberCount { expected, got } => write!(f, "the hash contains {} numbers, {} were expected", got, expected),
+      Error::NumberOverflow => f.write_str("a decoded number is too large for its type"),
+      Error::InvalidSeparators => f.write_str("the separators must be at least 2 characters of the alphabet, and leave at least 3 characters in it"),
+      Error::InvalidGuards => f.write_str("the guards must be characters of the alphabet that aren't separators, and leave enough characters in it"),
+      Error::WeakSalt => f.write_str("the salt is shorter or has fewer distinct characters than required"),
+      Error::InvalidPrefix => f.write_str("the hash doesn't start with the expected prefix"),
+      Error::InvalidSuffix => f.write_str("the hash doesn't end with the expected suffix"),
+      Error::InvalidMacSettings => f.write_str("the MAC key is empty, or the tag length isn't between 4 and 16"),
+      Error::InvalidMac => f.write_str("the authentication tag of the hash doesn't match"),
+      Error::InvalidFpeKey => f.write_str("the encryption key is empty"),
+      Error::InvalidMaxLength => f.write_str("the maximum length is 0, or shorter than the minimum length"),
+      Error::HashTooLong { length, max } => write!(f, "the hash would be {} characters long, longer than the maximum of {}", length, max),
+      Error::InvalidVersionCharacter => f.write_str("the version character is already used, or can be part of hashes"),
+      Error::UnknownVersion => f.write_str("the hash doesn't start with a known version character"),
+      Error::NoVersion => f.write_str("no version was added to encode with"),
+      Error::InvalidTimestamp => f.write_str("the timestamp is out of range"),
+      Error::AlreadyInitialized => f.write_str("the global codec is already initialized"),
+      Error::InvalidGlobalId => f.write_str("the global ID isn't a base64 type name and hash"),
+      Error::BufferTooSmall { capacity } => write!(f, "the hash doesn't fit in a buffer of {} bytes", capacity),
+      Error::InvalidConfigFile { path, reason } => write!(f, "{}: {}", path.display(), reason)
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+  /// A machine-readable code for the error, such as `"HASHIDS_MISSING_SALT"`, to expose in API error payloads.
+  ///
+  /// Unlike the `Display` messages, codes are stable across releases: they are never changed nor reused.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// assert_eq!(codec.decode("").unwrap_err().code(), "HASHIDS_EMPTY_HASH");
+  /// ```
+  pub fn code(&self) -> &'static str {
+    match self {
+      Error::MissingSalt => "HASHIDS_MISSING_SALT",
+      Error::NonAsciiSalt { .. } => "HASHIDS_NON_ASCII_SALT",
+      Error::InvalidAlphabetLength { .. } => "HASHIDS_INVALID_ALPHABET_LENGTH",
+      Error::NonAsciiAlphabet => "HASHIDS_NON_ASCII_ALPHABET",
+      Error::InvalidInputId => "HASHIDS_INVALID_INPUT_ID",
+      Error::NonHexString => "HASHIDS_NON_HEX_STRING",
+      Error::EmptyHash => "HASHIDS_EMPTY_HASH",
+      Error::InvalidHash => "HASHIDS_INVALID_HASH",
+      Error::InvalidCharacter { .. } => "HASHIDS_INVALID_CHARACTER",
+      Error::UnexpectedNumberCount { .. } => "HASHIDS_UNEXPECTED_NUMBER_COUNT",
+      Error::NumberOverflow => "HASHIDS_NUMBER_OVERFLOW",
+      Error::InvalidSeparators => "HASHIDS_INVALID_SEPARATORS",
+      Error::InvalidGuards => "HASHIDS_INVALID_GUARDS",
+      Error::WeakSalt => "HASHIDS_WEAK_SALT",
+      Error::InvalidPrefix => "HASHIDS_INVALID_PREFIX",
+      Error::InvalidSuffix => "HASHIDS_INVALID_SUFFIX",
+      Error::InvalidMacSettings => "HASHIDS_INVALID_MAC_SETTINGS",
+      Error::InvalidMac => "HASHIDS_INVALID_MAC",
+      Error::InvalidFpeKey => "HASHIDS_INVALID_FPE_KEY",
+      Error::InvalidMaxLength => "HASHIDS_INVALID_MAX_LENGTH",
+      Error::HashTooLong { .. } => "HASHIDS_HASH_TOO_LONG",
+      Error::InvalidVersionCharacter => "HASHIDS_INVALID_VERSION_CHARACTER",
+      Error::UnknownVersion => "HASHIDS_UNKNOWN_VERSION",
+      Error::NoVersion => "HASHIDS_NO_VERSION",
+      Error::InvalidTimestamp => "HASHIDS_INVALID_TIMESTAMP",
+      Error::AlreadyInitialized => "HASHIDS_ALREADY_INITIALIZED",
+      Error::InvalidGlobalId => "HASHIDS_INVALID_GLOBAL_ID",
+      Error::BufferTooSmall { .. } => "HASHIDS_BUFFER_TOO_SMALL",
+      Error::InvalidConfigFile { .. } => "HASHIDS_INVALID_CONFIG_FILE"
+    }
+  }
+
+  /// Whether the error comes from decoding a hash that isn't valid for the codec,
+  /// rather than from its settings or from an ID that can't be encoded.
+  ///
+  /// Web integrations respond with a 404 Not Found to those, as no resource can have this ID.
+  pub fn is_invalid_hash(&self) -> bool {
+    matches!(self, Error::NonHexString | Error::EmptyHash | Error::InvalidHash | Error::InvalidCharacter { .. }
+      | Error::UnexpectedNumberCount { .. } | Error::NumberOverflow | Error::InvalidPrefix | Error::InvalidSuffix
+      | Error::InvalidMac | Error::UnknownVersion | Error::InvalidTimestamp | Error::InvalidGlobalId)
+  }
 }
 
+/// Result of the fallible operations of this crate.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
 /// Represents the salt to use when encoding/decoding IDs.
 /// 
 /// It is of course recommended to keep that value in an environnment variable.
@@ -64,6 +299,65 @@ impl From<String> for HashidSalt {
   }
 }
 
+impl HashidSalt {
+  /// A salt of `len` random letters and digits, from the randomness of the operating system.
+  ///
+  /// Meant to be generated once, when setting up a project, then kept secret, for instance in a `.env` file with `to_dotenv`.
+  /// ```
+  /// use hashids::HashidSalt;
+  /// let salt = HashidSalt::generate(32);
+  /// assert_eq!(salt.to_dotenv().len(), "HASHID_SALT=".len() + 32);
+  /// ```
+  /// # Panics
+  /// If the operating system can't provide random bytes.
+  ///
+  /// Requires the `rand` feature.
+  #[cfg(feature = "rand")]
+  pub fn generate(len: usize) -> HashidSalt {
+    // Characters which need no quoting in `.env` files
+    const CHARACTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    // Bytes past the last multiple of the character count are skipped, so every character is as likely
+    const LIMIT: usize = 256 - 256 % CHARACTERS.len();
+
+    let mut salt = String::with_capacity(len);
+    let mut bytes = [0u8; 64];
+    while salt.len() < len {
+      getrandom::getrandom(&mut bytes).expect("the operating system failed to provide random bytes");
+      salt.extend(bytes.iter().filter(|b| (**b as usize) < LIMIT).map(|b| CHARACTERS[*b as usize % CHARACTERS.len()] as char));
+    }
+    salt.truncate(len);
+    HashidSalt(salt)
+  }
+
+  /// The salt as a line of a `.env` file, setting the `HASHID_SALT` variable the builder reads it from.
+  ///
+  /// Salts with other characters than letters, digits, `-`, `_` and `.` are double-quoted.
+  /// ```
+  /// use hashids::HashidSalt;
+  /// assert_eq!(HashidSalt::from("Ab12").to_dotenv(), "HASHID_SALT=Ab12");
+  /// assert_eq!(HashidSalt::from(r#"my "salt""#).to_dotenv(), r#"HASHID_SALT="my \"salt\"""#);
+  /// ```
+  pub fn to_dotenv(&self) -> String {
+    if !self.0.is_empty() && self.0.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)) {
+      return format!("{}={}", ENV_KEY, self.0)
+    }
+    let mut line = format!("{}=\"", ENV_KEY);
+    for c in self.0.chars() {
+      if matches!(c, '\\' | '"' | '$') { line.push('\\') }
+      line.push(c);
+    }
+    line.push('"');
+    line
+  }
+
+  pub(crate) fn is_weak(&self, min_len: usize, min_unique_chars: usize) -> bool {
+    let mut unique: Vec<char> = self.0.chars().collect();
+    unique.sort_unstable();
+    unique.dedup();
+    self.0.chars().count() < min_len || unique.len() < min_unique_chars
+  }
+}
+
   /// Use this builder to setup the hashid encoder/decoder [HashidCodec](struct.HashidCodec.html).
   /// 
   /// There are many options to customize the encoder, and by extension, hashing settings, 
@@ -74,30 +368,75 @@ impl From<String> for HashidSalt {
   /// ```
   /// # std::env::set_var("HASHID_SALT", "organic salt");
   /// use hashids::{HashidBuilder};
-  /// let builder = HashidBuilder::new().ok().expect("Failed building the hashid encoder");
+  /// let builder = HashidBuilder::new().build().expect("Failed building the hashid encoder");
   /// ```
-  /// Use the `with_` methods to configure custom settings through code, 
-  /// and finish with the `.ok()` to get a Result containing the HashidCoDec with your configuration.
+  /// Use the `with_` methods to configure custom settings through code, or the matching `set_` methods when the builder is borrowed,
+  /// and finish with the `.build()` to get a Result containing the HashidCoDec with your configuration.
   /// ```
   /// use hashids::{HashidBuilder};
   /// let builder_result = HashidBuilder::new()
   ///       .with_salt("my salt")
   ///       .with_alphabet("12345789abcedef~!@#$%^&*()_+".to_string())
   ///       .with_length(16)
-  ///       .ok().unwrap();
+  ///       .build().unwrap();
   /// ```
 pub struct HashidBuilder {
   salt: Option<HashidSalt>,
+  env_key: Cow<'static, str>,
+  env_source: Option<Box<EnvSource>>,
   alphabet: Option<String>,
-  min_length: Option<usize>
+  min_length: Option<usize>,
+  max_length: Option<usize>,
+  allow_negative: bool,
+  compat: Compat,
+  separators: Option<String>,
+  guards: Option<String>,
+  salt_strength: Option<(usize, usize)>,
+  unsalted: bool,
+  case: Case,
+  blocklist: Vec<String>,
+  prefix: String,
+  suffix: String,
+  timestamp_epoch: SystemTime,
+  metrics: Observer,
+  #[cfg(feature = "mac")]
+  mac: Option<(Vec<u8>, usize)>,
+  #[cfg(feature = "fpe")]
+  fpe: Option<Vec<u8>>
+}
+
+/// Same as `HashidBuilder::new`.
+impl Default for HashidBuilder {
+  fn default() -> Self {
+    HashidBuilder::new()
+  }
 }
 
 impl HashidBuilder {
   pub fn new() -> HashidBuilder {
     HashidBuilder {
       salt: None,
+      env_key: Cow::Borrowed(ENV_KEY),
+      env_source: None,
       alphabet: None,
-      min_length: None
+      min_length: None,
+      max_length: None,
+      allow_negative: false,
+      compat: Compat::Legacy,
+      separators: None,
+      guards: None,
+      salt_strength: None,
+      unsalted: false,
+      case: Case::Preserve,
+      blocklist: Vec::new(),
+      prefix: String::new(),
+      suffix: String::new(),
+      timestamp_epoch: SystemTime::UNIX_EPOCH,
+      metrics: Observer::default(),
+      #[cfg(feature = "mac")]
+      mac: None,
+      #[cfg(feature = "fpe")]
+      fpe: None
     }
   }
 
@@ -107,9 +446,15 @@ impl HashidBuilder {
   /// ```
   /// use hashids::{HashidSalt, HashidBuilder};
   /// let salt = HashidSalt::from("my_custom salt");
-  /// let builder_result = HashidBuilder::new().with_hashid_salt(salt).ok();
+  /// let builder_result = HashidBuilder::new().with_hashid_salt(salt).build();
   /// ```
   pub fn with_hashid_salt(mut self, salt: HashidSalt) -> HashidBuilder {
+    self.set_hashid_salt(salt);
+    self
+  }
+
+  /// Same as `with_hashid_salt`, on a builder held by mutable reference.
+  pub fn set_hashid_salt(&mut self, salt: HashidSalt) -> &mut HashidBuilder {
     self.salt = Some(salt);
     self
   }
@@ -120,12 +465,31 @@ impl HashidBuilder {
   /// The builder will use the `HASHID_SALT` environnment variable to build salt if it isn't defined with code.
   /// ```
   /// use hashids::{HashidBuilder};
-  /// let builder_result = HashidBuilder::new().with_salt("my salt").ok();
+  /// let builder_result = HashidBuilder::new().with_salt("my salt").build();
   /// ```
   pub fn with_salt(self, salt: &str) -> HashidBuilder {
     let hashid_salt = HashidSalt::from(salt);
     self.with_hashid_salt(hashid_salt)
   }
+
+  /// Same as `with_salt`, on a builder held by mutable reference, such as one in a config struct
+  /// built with `Default::default()`.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// #[derive(Default)]
+  /// struct Config {
+  ///   hashids: HashidBuilder
+  /// }
+  ///
+  /// let mut config = Config::default();
+  /// config.hashids.set_salt("this is my salt").set_length(8);
+  /// let codec = std::mem::take(&mut config.hashids).build().unwrap();
+  /// assert_eq!(codec.encode(1).unwrap(), "gB0NV05e");
+  /// ```
+  pub fn set_salt(&mut self, salt: &str) -> &mut HashidBuilder {
+    let hashid_salt = HashidSalt::from(salt);
+    self.set_hashid_salt(hashid_salt)
+  }
   
   /// Creates a salt from a String. Use this method to create a `HashidBuilder` with a custom hardcoded salt.
   /// Rather than String, &str should be easier to construct, you can use `with_salt` instead.
@@ -134,37 +498,451 @@ impl HashidBuilder {
   /// ```
   /// use hashids::{HashidBuilder};
   /// let string_salt = "my salt".to_string();
-  /// let builder_result = HashidBuilder::new().with_string_salt(string_salt).ok();
+  /// let builder_result = HashidBuilder::new().with_string_salt(string_salt).build();
   /// ```
   pub fn with_string_salt(self, salt: String) -> HashidBuilder {
     let hashid_salt = HashidSalt::from(salt);
     self.with_hashid_salt(hashid_salt)
   }
+
+  /// Same as `with_string_salt`, on a builder held by mutable reference.
+  pub fn set_string_salt(&mut self, salt: String) -> &mut HashidBuilder {
+    let hashid_salt = HashidSalt::from(salt);
+    self.set_hashid_salt(hashid_salt)
+  }
   
   
+  /// Build a codec without any salt, producing the same hashes as the reference implementations given an empty salt.
+  ///
+  /// Anyone can decode such hashes, so this is meant for tooling, debugging and cross-checking against other implementations.
+  /// An empty salt given through `with_salt` or the environnment is still refused with an `Error::MissingSalt`.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().without_salt().build().unwrap();
+  /// assert_eq!(codec.encode_tuple((1u64, 2u64, 3u64)).unwrap(), "o2fXhV");
+  /// ```
+  pub fn without_salt(mut self) -> HashidBuilder {
+    self.set_unsalted();
+    self
+  }
+
+  /// Same as `without_salt`, on a builder held by mutable reference.
+  pub fn set_unsalted(&mut self) -> &mut HashidBuilder {
+    self.salt = Some(HashidSalt::from(""));
+    self.unsalted = true;
+    self
+  }
+
+  /// Read the salt from the `key` environnment variable instead of `HASHID_SALT`, when it isn't defined with code,
+  /// so several codecs of a process each get their own salt.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// # std::env::set_var("USERS_HASHID_SALT", "this is my salt");
+  /// let users = HashidBuilder::new().with_env_key("USERS_HASHID_SALT").build().unwrap();
+  /// assert_eq!(users.encode(12345).unwrap(), "NkK9");
+  /// ```
+  pub fn with_env_key(mut self, key: impl Into<String>) -> HashidBuilder {
+    self.set_env_key(key);
+    self
+  }
+
+  /// Same as `with_env_key`, on a builder held by mutable reference.
+  pub fn set_env_key(&mut self, key: impl Into<String>) -> &mut HashidBuilder {
+    self.env_key = Cow::Owned(key.into());
+    self
+  }
+
+  /// Read the salt from the `{prefix}_HASHID_SALT` environnment variable, such as `MYAPP_HASHID_SALT` for `"MYAPP"`.
+  pub fn with_env_prefix(self, prefix: &str) -> HashidBuilder {
+    self.with_env_key(format!("{}_{}", prefix, ENV_KEY))
+  }
+
+  /// Same as `with_env_prefix`, on a builder held by mutable reference.
+  pub fn set_env_prefix(&mut self, prefix: &str) -> &mut HashidBuilder {
+    self.set_env_key(format!("{}_{}", prefix, ENV_KEY))
+  }
+
+  /// A builder reading the salt from `source` rather than from the process environnment, when it isn't defined with code.
+  ///
+  /// `source` is given the name of the variable, so tests and other configuration systems provide the salt deterministically.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::from_env_source(|key| (key == "HASHID_SALT").then(|| "this is my salt".to_string())).build().unwrap();
+  /// assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+  /// ```
+  pub fn from_env_source(source: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> HashidBuilder {
+    let mut builder = HashidBuilder::new();
+    builder.env_source = Some(Box::new(source));
+    builder
+  }
+
+  /// The salt of the environnment variable, read from the source given to `from_env_source` if any.
+  fn env_salt(&self) -> Option<String> {
+    match &self.env_source {
+      Some(source) => source(&self.env_key),
+      None => std::env::var(&*self.env_key).ok()
+    }
+  }
+
+  /// Reject salts shorter than `min_len` characters, or made of fewer than `min_unique_chars` distinct characters,
+  /// with an `Error::WeakSalt` when building.
+  /// This applies to the salt from the `HASHID_SALT` environnment variable too.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let weak = HashidBuilder::new().with_salt("aaaaaaaaaaaa").require_salt_strength(12, 6).build();
+  /// assert_eq!(weak.err(), Some(Error::WeakSalt));
+  /// let strong = HashidBuilder::new().with_salt("a pinch of pink salt").require_salt_strength(12, 6).build();
+  /// assert!(strong.is_ok());
+  /// ```
+  pub fn require_salt_strength(mut self, min_len: usize, min_unique_chars: usize) -> HashidBuilder {
+    self.set_salt_strength(min_len, min_unique_chars);
+    self
+  }
+
+  /// Same as `require_salt_strength`, on a builder held by mutable reference.
+  pub fn set_salt_strength(&mut self, min_len: usize, min_unique_chars: usize) -> &mut HashidBuilder {
+    self.salt_strength = Some((min_len, min_unique_chars));
+    self
+  }
+
   // Alphabet-related methods
   /// Add a custom alphabet. The default alphabet is "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890".
   /// Must be greater than 16 symbols long. 
-  /// Characters cannot be more than 1 byte length, so only ASCII characters are allowed.
+  /// Any characters can be used, such as kana or emoji, ASCII alphabets hash the same as they always did.
   /// ```
-  /// use hashids::{HashidBuilder, Error};
-  /// let builder = HashidBuilder::new()
-  ///     .with_salt("漢字注入注意")
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new()
+  ///     .with_salt("this is my salt")
   ///     .with_alphabet("あいうえおかきくけこたちつてとさしすせそ".to_string())
-  ///     .ok();
-  /// assert_eq!(builder, Err(Error::NonAsciiAlphabet));
+  ///     .build().unwrap();
+  /// let hash = codec.encode(1234567).unwrap();
+  /// assert!(hash.chars().all(|c| "あいうえおかきくけこたちつてとさしすせそ".contains(c)));
+  /// assert_eq!(codec.decode(&hash), Ok(vec![1234567]));
   /// ```
   pub fn with_alphabet(mut self, alphabet: String) -> HashidBuilder {
+    self.set_alphabet(alphabet);
+    self
+  }
+
+  /// Same as `with_alphabet`, on a builder held by mutable reference.
+  pub fn set_alphabet(&mut self, alphabet: String) -> &mut HashidBuilder {
     self.alphabet = Some(alphabet); 
     self
   }
 
+  /// Use one of the alphabets of the [alphabet](alphabet/index.html) module, replacing any alphabet set before.
+  pub fn with_preset(mut self, preset: alphabet::Preset) -> HashidBuilder {
+    self.set_preset(preset);
+    self
+  }
+
+  /// Same as `with_preset`, on a builder held by mutable reference.
+  pub fn set_preset(&mut self, preset: alphabet::Preset) -> &mut HashidBuilder {
+    self.alphabet = Some(preset.alphabet().to_string());
+    self
+  }
+
+  /// Pick the characters separating numbers in hashes made of several numbers, instead of the default "cfhistuCFHISTU".
+  ///
+  /// They must be characters taken from the alphabet, at least 2 of them, and leave at least 3 characters in the alphabet,
+  /// or building returns an `Error::InvalidSeparators`.
+  /// Unlike the default separators, they are used as given (in shuffled order): none are added from or returned to the alphabet.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new()
+  ///     .with_salt("my salt")
+  ///     .with_separators("xyzXYZ")
+  ///     .build().unwrap();
+  /// let hash = codec.encode_tuple((1u64, 2u64, 3u64)).unwrap();
+  /// assert_eq!(hash.chars().filter(|c| "xyzXYZ".contains(*c)).count(), 2);
+  /// ```
+  pub fn with_separators(mut self, separators: &str) -> HashidBuilder {
+    self.set_separators(separators);
+    self
+  }
+
+  /// Same as `with_separators`, on a builder held by mutable reference.
+  pub fn set_separators(&mut self, separators: &str) -> &mut HashidBuilder {
+    self.separators = Some(separators.to_string());
+    self
+  }
+
+  /// Pin the guard characters, which pad hashes shorter than the minimum length, instead of deriving them from the alphabet.
+  ///
+  /// They must be characters taken from the alphabet, at least 1 of them, and different from custom separators,
+  /// or building returns an `Error::InvalidGuards`.
+  /// They are taken out of the alphabet, so can be used to keep characters out of short hashes.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new()
+  ///     .with_salt("my salt")
+  ///     .with_guards("0O")
+  ///     .build().unwrap();
+  /// let hash = codec.encode(123456789).unwrap();
+  /// assert!(!hash.contains('0') && !hash.contains('O'));
+  /// ```
+  pub fn with_guards(mut self, guards: &str) -> HashidBuilder {
+    self.set_guards(guards);
+    self
+  }
+
+  /// Same as `with_guards`, on a builder held by mutable reference.
+  pub fn set_guards(&mut self, guards: &str) -> &mut HashidBuilder {
+    self.guards = Some(guards.to_string());
+    self
+  }
+
   /// Adjust the length of the hash string to be generated.
   pub fn with_length(mut self, length: usize) -> HashidBuilder {
+    self.set_length(length);
+    self
+  }
+
+  /// Same as `with_length`, on a builder held by mutable reference.
+  pub fn set_length(&mut self, length: usize) -> &mut HashidBuilder {
     self.min_length = Some(length);
     self
   }
 
+  /// Limit the length of hashes, encoding returns an `Error::HashTooLong` for numbers that don't fit.
+  ///
+  /// Like the minimum length, it doesn't count the prefix, suffix and authentication tag, which are always the same length.
+  /// Building returns an `Error::InvalidMaxLength` if it is 0 or shorter than the minimum length, which defaults to 4.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").with_max_length(5).build().unwrap();
+  /// assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+  /// assert!(matches!(codec.encode(123456789), Err(Error::HashTooLong { max: 5, .. })));
+  /// ```
+  pub fn with_max_length(mut self, length: usize) -> HashidBuilder {
+    self.set_max_length(length);
+    self
+  }
+
+  /// Same as `with_max_length`, on a builder held by mutable reference.
+  pub fn set_max_length(&mut self, length: usize) -> &mut HashidBuilder {
+    self.max_length = Some(length);
+    self
+  }
+
+  /// Make every hash exactly this long, for fixed-width columns: sets both the minimum and maximum length.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").with_exact_length(8).build().unwrap();
+  /// assert_eq!(codec.encode(1).unwrap(), "gB0NV05e");
+  /// assert!(matches!(codec.encode(u64::MAX >> 2), Err(Error::HashTooLong { max: 8, .. })));
+  /// ```
+  pub fn with_exact_length(self, length: usize) -> HashidBuilder {
+    self.with_length(length).with_max_length(length)
+  }
+
+  /// Same as `with_exact_length`, on a builder held by mutable reference.
+  pub fn set_exact_length(&mut self, length: usize) -> &mut HashidBuilder {
+    self.set_length(length).set_max_length(length)
+  }
+
+  /// Accept negative IDs instead of returning an `Error::InvalidInputId`.
+  ///
+  /// Every ID is zigzag-encoded before hashing (0, -1, 1, -2, 2... become 0, 1, 2, 3, 4...), and decoded back on `decode_signed`.
+  /// This changes the hashes of positive IDs too, so it must be set consistently for a given salt.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("my salt").allow_negative().build().unwrap();
+  /// let hash = codec.encode(-42i64).unwrap();
+  /// assert_eq!(codec.decode_signed(&hash), Ok(vec![-42]));
+  /// ```
+  pub fn allow_negative(mut self) -> HashidBuilder {
+    self.set_allow_negative();
+    self
+  }
+
+  /// Same as `allow_negative`, on a builder held by mutable reference.
+  pub fn set_allow_negative(&mut self) -> &mut HashidBuilder {
+    self.allow_negative = true;
+    self
+  }
+
+  /// Start every hash with this string, such as `usr_` to tell at a glance what kind of entity an ID refers to.
+  ///
+  /// Decoding strips it, and returns an `Error::InvalidPrefix` for hashes that don't start with it,
+  /// which catches IDs of another entity type being passed around.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let users = HashidBuilder::new().with_salt("this is my salt").with_prefix("usr_").build().unwrap();
+  /// assert_eq!(users.encode(12345).unwrap(), "usr_NkK9");
+  /// assert_eq!(users.decode("usr_NkK9"), Ok(vec![12345]));
+  /// assert_eq!(users.decode("org_NkK9"), Err(Error::InvalidPrefix));
+  /// ```
+  pub fn with_prefix(mut self, prefix: &str) -> HashidBuilder {
+    self.set_prefix(prefix);
+    self
+  }
+
+  /// Same as `with_prefix`, on a builder held by mutable reference.
+  pub fn set_prefix(&mut self, prefix: &str) -> &mut HashidBuilder {
+    self.prefix = prefix.to_string();
+    self
+  }
+
+  /// End every hash with this string, see `with_prefix`.
+  ///
+  /// Decoding returns an `Error::InvalidSuffix` for hashes that don't end with it.
+  pub fn with_suffix(mut self, suffix: &str) -> HashidBuilder {
+    self.set_suffix(suffix);
+    self
+  }
+
+  /// Same as `with_suffix`, on a builder held by mutable reference.
+  pub fn set_suffix(&mut self, suffix: &str) -> &mut HashidBuilder {
+    self.suffix = suffix.to_string();
+    self
+  }
+
+  /// Append an authentication tag of `tag_length` characters to every hash, computed with HMAC-SHA256 and a secret key.
+  ///
+  /// Unlike the salt, which only makes hashes look random, the key makes hashes impossible to forge without knowing it:
+  /// decoding returns an `Error::InvalidMac` for hashes whose tag doesn't match.
+  /// Use a key different from the salt. The tag must be between 4 and 16 characters, longer tags are harder to guess,
+  /// or building returns an `Error::InvalidMacSettings`.
+  ///
+  /// Requires the `mac` feature.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").with_mac_key(b"another secret", 8).build().unwrap();
+  /// let hash = codec.encode(12345).unwrap();
+  /// assert!(hash.starts_with("NkK9") && hash.len() == 12);
+  /// assert_eq!(codec.decode(&hash), Ok(vec![12345]));
+  /// assert_eq!(codec.decode("NkK9aaaaaaaa"), Err(Error::InvalidMac));
+  /// ```
+  #[cfg(feature = "mac")]
+  pub fn with_mac_key(mut self, key: &[u8], tag_length: usize) -> HashidBuilder {
+    self.set_mac_key(key, tag_length);
+    self
+  }
+
+  /// Same as `with_mac_key`, on a builder held by mutable reference.
+  #[cfg(feature = "mac")]
+  pub fn set_mac_key(&mut self, key: &[u8], tag_length: usize) -> &mut HashidBuilder {
+    self.mac = Some((key.to_vec(), tag_length));
+    self
+  }
+
+  /// Encrypt numbers with a secret key before hashing them, so hashes stay unreadable even if the salt leaks.
+  ///
+  /// Hashids only shuffle the alphabet with the salt, which can be recovered from enough hashes of known numbers.
+  /// With a key, numbers are first encrypted with a Feistel network keyed with HMAC-SHA256, a permutation of `u64`:
+  /// consecutive numbers get unrelated hashes, as long as those of large numbers, and numbers above `u64::MAX` return an `Error::InvalidInputId`. The key must not be empty, or building returns an `Error::InvalidFpeKey`.
+  ///
+  /// Requires the `fpe` feature.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").with_fpe_key(b"encryption key").build().unwrap();
+  /// let hash = codec.encode(12345).unwrap();
+  /// assert_ne!(hash, "NkK9");
+  /// assert_eq!(codec.decode(&hash), Ok(vec![12345]));
+  /// ```
+  #[cfg(feature = "fpe")]
+  pub fn with_fpe_key(mut self, key: &[u8]) -> HashidBuilder {
+    self.set_fpe_key(key);
+    self
+  }
+
+  /// Same as `with_fpe_key`, on a builder held by mutable reference.
+  #[cfg(feature = "fpe")]
+  pub fn set_fpe_key(&mut self, key: &[u8]) -> &mut HashidBuilder {
+    self.fpe = Some(key.to_vec());
+    self
+  }
+
+  /// Avoid hashes containing any of these words, such as profanities, ignoring case.
+  ///
+  /// A hash containing a blocked word is replaced by another one, which decodes to the same numbers,
+  /// so the blocklist must stay the same for hashes to keep decoding.
+  /// Words shorter than 3 characters are ignored. Adds to the words of previous calls.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+  ///
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").with_blocklist(&["nkk"]).build().unwrap();
+  /// let hash = codec.encode(12345).unwrap();
+  /// assert!(!hash.to_lowercase().contains("nkk"));
+  /// assert_eq!(codec.decode(&hash), Ok(vec![12345]));
+  /// ```
+  pub fn with_blocklist<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, words: I) -> HashidBuilder {
+    self.set_blocklist(words);
+    self
+  }
+
+  /// Same as `with_blocklist`, on a builder held by mutable reference.
+  pub fn set_blocklist<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, words: I) -> &mut HashidBuilder {
+    self.blocklist.extend(words.into_iter().map(|word| word.as_ref().to_string()));
+    self
+  }
+
+  /// Avoid hashes containing common English profanities, see `with_blocklist`.
+  ///
+  /// Requires the `blocklist` feature.
+  #[cfg(feature = "blocklist")]
+  pub fn with_default_blocklist(self) -> HashidBuilder {
+    self.with_blocklist(blocklist::DEFAULT_BLOCKLIST)
+  }
+
+  /// Same as `with_default_blocklist`, on a builder held by mutable reference.
+  #[cfg(feature = "blocklist")]
+  pub fn set_default_blocklist(&mut self) -> &mut HashidBuilder {
+    self.set_blocklist(blocklist::DEFAULT_BLOCKLIST)
+  }
+
+  /// Count the timestamps of `encode_with_timestamp` from this time instead of the Unix epoch, for shorter hashes.
+  pub fn with_timestamp_epoch(mut self, epoch: SystemTime) -> HashidBuilder {
+    self.set_timestamp_epoch(epoch);
+    self
+  }
+
+  /// Same as `with_timestamp_epoch`, on a builder held by mutable reference.
+  pub fn set_timestamp_epoch(&mut self, epoch: SystemTime) -> &mut HashidBuilder {
+    self.timestamp_epoch = epoch;
+    self
+  }
+
+  /// Report what the codec encodes and decodes to `metrics`, see [HashidMetrics](trait.HashidMetrics.html).
+  pub fn with_metrics(mut self, metrics: Arc<dyn HashidMetrics>) -> HashidBuilder {
+    self.set_metrics(metrics);
+    self
+  }
+
+  /// Same as `with_metrics`, on a builder held by mutable reference.
+  pub fn set_metrics(&mut self, metrics: Arc<dyn HashidMetrics>) -> &mut HashidBuilder {
+    self.metrics = Observer::new(metrics);
+    self
+  }
+
+  /// Force hashes to be uppercase or lowercase, see [Case](enum.Case.html).
+  pub fn with_output_case(mut self, case: Case) -> HashidBuilder {
+    self.set_output_case(case);
+    self
+  }
+
+  /// Same as `with_output_case`, on a builder held by mutable reference.
+  pub fn set_output_case(&mut self, case: Case) -> &mut HashidBuilder {
+    self.case = case;
+    self
+  }
+
+  /// Choose between this crate's historical derivation of separators and guards, and the one of the reference implementations.
+  /// Defaults to `Compat::Legacy`, see the [compat](compat/index.html) module for details.
+  pub fn with_compat(mut self, compat: Compat) -> HashidBuilder {
+    self.set_compat(compat);
+    self
+  }
+
+  /// Same as `with_compat`, on a builder held by mutable reference.
+  pub fn set_compat(&mut self, compat: Compat) -> &mut HashidBuilder {
+    self.compat = compat;
+    self
+  }
+
   /// Creates an complete instance of HashidCodec, validating it settings.
   /// Errors if incomplete in crucial parts.
   /// The builder returned can then be used to encode and decode.
@@ -172,42 +950,121 @@ impl HashidBuilder {
   /// or a `Error::InvalidAlphabetLength` if a custom alphabet is shorter than 16 characters.
   ///  ```
   /// use hashids::{HashidBuilder};
-  /// let builder_result = HashidBuilder::new().ok();
+  /// let builder_result = HashidBuilder::new().build();
   /// ```
+  /// With the `tracing` feature, the construction is in a `hashids_build` span, and reported by an event, without the salt.
+  pub fn build(self) -> Result<HashidCodec, Error>  {
+    let _span = trace::build_span();
+    let result = self.build_codec();
+    trace::built(&result);
+    result
+  }
+
+  /// Former name of `build`, easily mistaken for `Result::ok`.
+  #[deprecated(note = "use `build` instead")]
   pub fn ok(self) -> Result<HashidCodec, Error>  {
+    self.build()
+  }
+
+  fn build_codec(self) -> Result<HashidCodec, Error> {
+    let mut config = HashidConfig {
+      salt: String::new(),
+      unsalted: self.unsalted,
+      alphabet: self.alphabet.clone(),
+      separators: self.separators.clone(),
+      guards: self.guards.clone(),
+      min_length: self.min_length.unwrap_or(DEFAULT_MIN_LENGTH),
+      max_length: self.max_length,
+      allow_negative: self.allow_negative,
+      compat: self.compat,
+      case: self.case,
+      blocklist: self.blocklist.clone(),
+      prefix: self.prefix.clone(),
+      suffix: self.suffix.clone(),
+      timestamp_epoch: self.timestamp_epoch
+    };
 
+    let case = self.case;
     // Get custom alphabet or default otherwise
     let alphabet = {
-      match self.alphabet {
-        // Default alphabet is already manually checked to be only unique ascii chars, no need to revalidate that
-        None => DEFAULT_ALPHABET.to_string(),
+      match &self.alphabet {
+        // Default alphabet is long enough, but folding its case leaves duplicates to remove
+        None => get_unique_alphabet(&case.apply(DEFAULT_ALPHABET)),
         Some(custom) => {
-          if !custom.is_ascii() { return  Err(Error::NonAsciiAlphabet ) }
-          let unique = get_unique_alphabet(custom);
-          if unique.len() < MIN_ALPHABET_LENGTH { return Err(Error::InvalidAlphabetLength) };
+          let unique = get_unique_alphabet(&case.apply(custom));
+          if unique.len() < MIN_ALPHABET_LENGTH { return Err(Error::InvalidAlphabetLength { got: unique.len(), min: MIN_ALPHABET_LENGTH }) };
           unique
         }
       }
     };
     // get custom salt, set from builder function or by environnment
-    let salt = if let Some(custom) = self.salt { if !custom.0.is_ascii() { return  Err(Error::NonAsciiSalt ) } custom } else { 
-      let by_env = std::env::var(ENV_KEY);
-      match by_env {
-        Ok(var) => HashidSalt::from(var),
-        Err(_) => return Err(Error::MissingSalt)
+    let salt = if let Some(custom) = self.salt {
+      if let Some(first_bad_char) = custom.0.chars().find(|c| !c.is_ascii()) { return Err(Error::NonAsciiSalt { first_bad_char }) }
+      custom
+    } else { 
+      match self.env_salt() {
+        Some(var) => {
+          trace::salt_from_env(&self.env_key);
+          HashidSalt::from(var)
+        },
+        None => return Err(Error::MissingSalt)
       }
     };
+    if salt.0.is_empty() && !self.unsalted { return Err(Error::MissingSalt) }
+    if let Some((min_len, min_unique_chars)) = self.salt_strength {
+      if salt.is_weak(min_len, min_unique_chars) { return Err(Error::WeakSalt) }
+    }
+    let salt: Vec<char> = salt.0.chars().collect();
+    config.salt = salt.iter().collect();
     
     let min_hash_length = if let Some(custom) = self.min_length { custom } else { DEFAULT_MIN_LENGTH };
+    if self.max_length.is_some_and(|max| max == 0 || max < min_hash_length) { return Err(Error::InvalidMaxLength) }
     
-    let (t_separators, mut t_alphabet) = get_non_duplicated_string(DEFAULT_SEPARATORS.to_string(), alphabet);
-    let mut shuffled_separators = hashids_shuffle(t_separators.clone(), &salt)?;
+    // Custom guards are taken out of the alphabet before anything else is derived from it
+    let (custom_guards, alphabet) = match &self.guards {
+      None => (None, alphabet),
+      Some(custom) => {
+        let unique = get_unique_alphabet(&case.apply(custom));
+        let overlaps_separators = self.separators.as_ref().is_some_and(|separators| unique.iter().any(|c| case.apply(separators).contains(*c)));
+        if unique.is_empty() || overlaps_separators || !unique.iter().all(|c| alphabet.contains(c)) {
+          return Err(Error::InvalidGuards)
+        }
+        let (guards, alphabet) = get_non_duplicated_string(unique, alphabet);
+        if alphabet.len() < MIN_ALPHABET_LENGTH { return Err(Error::InvalidGuards) }
+        (Some(guards), alphabet)
+      }
+    };
+
+    let separators = match &self.separators {
+      None => DEFAULT_SEPARATORS.chars().collect(),
+      Some(custom) => {
+        let unique = get_unique_alphabet(&case.apply(custom));
+        if unique.len() < MIN_SEPARATORS_LENGTH
+          || !unique.iter().all(|c| alphabet.contains(c))
+          || alphabet.len() - unique.len() < MIN_ALPHABET_WITHOUT_SEPARATORS_LENGTH {
+          return Err(Error::InvalidSeparators)
+        }
+        unique
+      }
+    };
+
+    let (mut shuffled_separators, mut t_alphabet) = get_non_duplicated_string(separators, alphabet);
+    // An alphabet without any of the default separators gets its separators from the alphabet below
+    hashids_shuffle(&mut shuffled_separators, &salt);
     let alphabet_len = t_alphabet.len();
     
     let shuffled_separators_len = shuffled_separators.len();
 
-    if shuffled_separators_len <= 0 || ((alphabet_len/shuffled_separators_len) as f32) > SEPARATOR_DIV {
-      let mut seps_len =  ((alphabet_len as f32) / SEPARATOR_DIV) as usize;
+    // Custom separators are kept as they are
+    let too_few_separators = self.separators.is_none() && (shuffled_separators_len == 0 || match self.compat {
+      Compat::Legacy => ((alphabet_len/shuffled_separators_len) as f32) > SEPARATOR_DIV,
+      Compat::Reference => (alphabet_len as f32 / shuffled_separators_len as f32) > SEPARATOR_DIV
+    });
+    if too_few_separators {
+      let mut seps_len = match self.compat {
+        Compat::Legacy => ((alphabet_len as f32) / SEPARATOR_DIV) as usize,
+        Compat::Reference => ((alphabet_len as f32) / SEPARATOR_DIV).ceil() as usize
+      };
       if seps_len == 1 {
         seps_len = 2;
       };
@@ -215,33 +1072,67 @@ impl HashidBuilder {
       if seps_len > shuffled_separators_len {
         let diff = seps_len - shuffled_separators_len;
 
-        shuffled_separators.push_str(&t_alphabet[..diff]);
-        t_alphabet = t_alphabet[diff..].to_string();
+        shuffled_separators.extend(t_alphabet.drain(..diff));
       } else {
-        shuffled_separators = shuffled_separators[..seps_len].to_string();
+        shuffled_separators.truncate(seps_len);
       };
     };
 
-    let mut shuffled_alphabet = hashids_shuffle(t_alphabet, &salt)?;
+    let mut shuffled_alphabet = t_alphabet;
+    hashids_shuffle(&mut shuffled_alphabet, &salt);
 
-    let guard_count = (alphabet_len as f32 / GUARD_DIV as f32).ceil() as usize;
+    // The reference implementations count what's left of the alphabet after taking separators from it
+    let guards_alphabet_len = match self.compat {
+      Compat::Legacy => alphabet_len,
+      Compat::Reference => shuffled_alphabet.len()
+    };
+    let guard_count = (guards_alphabet_len as f32 / GUARD_DIV as f32).ceil() as usize;
 
-    let t_guards;
+    let t_guards: Vec<char>;
 
-    if alphabet_len < 3 {
-      t_guards = shuffled_separators[..guard_count].to_string();
-      shuffled_separators = shuffled_separators[guard_count..].to_string();
+    if let Some(mut custom) = custom_guards {
+      hashids_shuffle(&mut custom, &salt);
+      t_guards = custom;
+      if shuffled_alphabet.len() < 2 || shuffled_separators.is_empty() {
+        return Err(Error::InvalidGuards)
+      }
+    } else if guards_alphabet_len < 3 {
+      t_guards = shuffled_separators.drain(..guard_count).collect();
     } else {
-      t_guards = shuffled_alphabet[..guard_count].to_string();
-      shuffled_alphabet = shuffled_alphabet[guard_count..].to_string();
+      t_guards = shuffled_alphabet.drain(..guard_count).collect();
     };
 
+    // Words shorter than 3 characters would block too many hashes, and words with characters the codec never outputs can't appear
+    let blocklist = self.blocklist.iter()
+      .map(|word| word.to_lowercase())
+      .filter(|word| word.chars().count() >= 3)
+      .filter(|word| word.chars().all(|c| {
+        shuffled_alphabet.iter().chain(&shuffled_separators).chain(&t_guards).any(|symbol| symbol.to_lowercase().eq(c.to_lowercase()))
+      }))
+      .collect();
+
     Ok(HashidCodec {
+      config,
+      guard_set: CharSet::new(&t_guards),
+      separator_set: CharSet::new(&shuffled_separators),
+      symbol_set: CharSet::new(&[&shuffled_alphabet[..], &shuffled_separators, &t_guards].concat()),
       salt,
       min_hash_length,
+      max_hash_length: self.max_length,
       guards: t_guards,
       separators: shuffled_separators,
-      alphabet: shuffled_alphabet
+      alphabet: shuffled_alphabet,
+      allow_negative: self.allow_negative,
+      case: self.case,
+      blocklist,
+      prefix: self.prefix,
+      suffix: self.suffix,
+      timestamp_epoch: self.timestamp_epoch,
+      metrics: self.metrics,
+      #[cfg(feature = "mac")]
+      mac: self.mac.map(|(key, tag_length)| mac::MacKey::new(key, tag_length)).transpose()?,
+      #[cfg(feature = "fpe")]
+      fpe: self.fpe.map(fpe::FpeKey::new).transpose()?
     })
   }
 }
@@ -250,23 +1141,107 @@ impl HashidBuilder {
 ///
 /// It can only be created from a `HashidBuilder`, to validate and process input values conveniently.
 /// Once created, you can use the `.encode()` and `.decode` methods.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct HashidCodec {
-  salt: HashidSalt,
-  alphabet: String,
-  separators: String,
+  salt: Vec<char>,
+  alphabet: Vec<char>,
+  separators: Vec<char>,
   min_hash_length: usize,
-  guards: String 
+  max_hash_length: Option<usize>,
+  guards: Vec<char>,
+  allow_negative: bool,
+  case: Case,
+  blocklist: Vec<String>,
+  prefix: String,
+  suffix: String,
+  timestamp_epoch: SystemTime,
+  metrics: Observer,
+  #[cfg(feature = "mac")]
+  mac: Option<mac::MacKey>,
+  #[cfg(feature = "fpe")]
+  fpe: Option<fpe::FpeKey>,
+  config: HashidConfig,
+  guard_set: CharSet,
+  separator_set: CharSet,
+  // Alphabet, separators and guards together, to check the characters of a hash
+  symbol_set: CharSet
+}
+
+/// Codecs are equal when they encode and decode the same, even if built from different settings,
+/// for instance both compatibility modes with the default alphabet. Their metrics aren't compared.
+impl PartialEq for HashidCodec {
+  fn eq(&self, other: &Self) -> bool {
+    #[cfg(feature = "mac")]
+    if self.mac != other.mac {
+      return false
+    }
+    #[cfg(feature = "fpe")]
+    if self.fpe != other.fpe {
+      return false
+    }
+    self.salt == other.salt
+      && self.alphabet == other.alphabet
+      && self.separators == other.separators
+      && self.guards == other.guards
+      && self.min_hash_length == other.min_hash_length
+      && self.max_hash_length == other.max_hash_length
+      && self.allow_negative == other.allow_negative
+      && self.case == other.case
+      && self.blocklist == other.blocklist
+      && self.prefix == other.prefix
+      && self.suffix == other.suffix
+      && self.timestamp_epoch == other.timestamp_epoch
+  }
 }
 
-/// Uses a `HashidBuilder::new().ok()` and panics in case of error, which means it must have a salt set through environnment variables.
+/// Set of characters with a constant time lookup for ASCII, and a binary search for the others,
+/// used to check the characters of hashes and split them on guards and separators.
+#[derive(Debug, PartialEq)]
+struct CharSet {
+  ascii: [bool; 128],
+  others: Vec<char>
+}
+
+impl CharSet {
+  fn new(chars: &[char]) -> CharSet {
+    let mut set = CharSet { ascii: [false; 128], others: Vec::new() };
+    for &c in chars {
+      if c.is_ascii() { set.ascii[c as usize] = true } else { set.others.push(c) }
+    }
+    set.others.sort_unstable();
+    set.others.dedup();
+    set
+  }
+
+  fn contains(&self, c: char) -> bool {
+    if c.is_ascii() { self.ascii[c as usize] } else { self.others.binary_search(&c).is_ok() }
+  }
+}
+
+/// Buffer used while encoding, kept between the encodings of `encode_many`.
+///
+/// Holds the shuffled alphabet followed by the key it is shuffled with, both as long as the alphabet.
+#[derive(Default)]
+struct Scratch {
+  symbols: Vec<char>
+}
+
+impl Scratch {
+  fn symbols(&mut self, alphabet_len: usize) -> &mut [char] {
+    self.symbols.resize(alphabet_len * 2, '\0');
+    &mut self.symbols
+  }
+}
+
+
+/// Uses a `HashidBuilder::new().build()` and panics in case of error, which means it must have a salt set through environnment variables.
 /// 
 /// HashidCodec having a default implementation available does not mean it would be wise to skip building a lasting one and generate a new builder when needed,
 /// because the creation process involves some validation that can be heavy if repeated needlessly.  
 /// Having a persistent object keeping the settings avoids this performance hit.
 impl Default for HashidCodec {
   fn default() -> Self {
-      match HashidBuilder::new().ok() {
+      match HashidBuilder::new().build() {
         Ok(codec) => codec,
         Err(err) => {
           match err {
@@ -280,33 +1255,55 @@ impl Default for HashidCodec {
 
 impl HashidCodec {
 
-  // TODO: investigate if I even need this.
-  // pub fn decode_hex(&self, hash: String) -> String {
-  //   let numbers = self.decode(hash);
+  /// Converts a hexadecimal string, such as a MongoDB ObjectId, to a Hashid String.
+  ///
+  /// The hex string is cut in chunks of 12 characters which are encoded as several numbers,
+  /// the same way the reference hashids implementations do, so the output can be decoded by them too.
+  /// Returns an `Error::NonHexString` if the input is empty or contains anything else than hexadecimal digits.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let hash = codec.encode_hex("507f1f77bcf86cd799439011").unwrap();
+  /// assert_eq!(codec.decode_hex(&hash).unwrap(), "507f1f77bcf86cd799439011");
+  /// ```
+  pub fn encode_hex(&self, hex: &str) -> Result<String, Error> {
+    let numbers = hex_to_vec(hex)?;
+    self.encode_vec(&numbers)
+  }
 
-  //   // TODO: I get the feeling this is something stupid oveengineered.
-  //   let mut ret = String::new();
-  //   for number in numbers {
-  //     let r = format!("{:x}", number);
-  //     ret.push_str(&r[1..]);
-  //   }
+  /// Converts a Hashid created by `encode_hex` back to its hexadecimal string.
+  ///
+  /// Hexadecimal digits are always returned lowercase.
+  pub fn decode_hex(&self, hash: &str) -> Result<String, Error> {
+    let numbers = self.decode_numbers(hash)?;
+
+    let mut ret = String::new();
+    for number in numbers {
+      // Each chunk was prefixed with a 1 to keep its leading zeros, which is removed here.
+      let chunk = format!("{:x}", number);
+      if chunk.len() < 2 || !chunk.starts_with('1') {
+        return Err(Error::NonHexString)
+      }
+      ret.push_str(&chunk[1..]);
+    }
 
-  //   ret
-  // }
+    Ok(ret)
+  }
 
   /// Converts an ID integer to a Hashid String.
   ///
-  /// The integer can be any PositiveInteger (u32, u64, i32 and i64 are included), valid from 0 to 9007199254740992. (i64 max).
+  /// The integer can be any PositiveInteger (u32, u64, u128, i32 and i64 are included), valid from 0 to 9007199254740992. (i64 max), or up to `u128::MAX` for `u128`.
   /// The trait PositiveInteger must be in scope to allow generic usage.
   /// ```
   /// use hashids::{HashidBuilder, PositiveInteger, HashidCodec};
-  /// let codec = HashidBuilder::new().with_salt("this is my salt").ok().unwrap();
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
   /// let encoded_id = codec.encode(5i64).unwrap();
   /// assert_eq!( encoded_id, "0rDd".to_string() );
   ///
   /// let negative_id = codec.encode(-2);
   /// assert_eq!( negative_id, Err(hashids::Error::InvalidInputId) );
   /// ```
+  /// Negative IDs are accepted if the codec was built with `allow_negative()`.
   ///
   /// Why allow i64? It could be possible to erase the possibility of seeing negative numbers by just accepting usize, u32 and u64.
   /// However, the main usage of hashid is to obfuscate DB ids, and considering the prevalent use of diesel in the Rust ecosystem, it only makes sense to allow convenient interfacing.  
@@ -315,213 +1312,776 @@ impl HashidCodec {
   /// Why are negative numbers disallowed?  
   /// The hashid algorithm works through indexing in the alphabet, salt, and some guards characters, and a negative would throw the indexing and calculations off.
   pub fn encode<T: PositiveInteger>(&self, id: T) -> Result<String, Error> {
-    // Validate/Convert Input as a positive i64. 
-    // Error depending on PositiveInteger implementation, but probably a Error::InvalidInputId
-    let as_usize = id.to_usize()?;
+    let mut hash = String::new();
+    self.encode_into(id, &mut hash)?;
+    Ok(hash)
+  }
 
-    // TODO ?: make it not needing to be a vec, even internally?
-    let numbers = vec![as_usize];
-    let id = self.encode_vec(&numbers);
-    Ok(id)
+  /// Same as `encode`, but appends the hash to a caller-owned buffer instead of allocating a new String.
+  ///
+  /// Useful to encode many IDs in a row, reusing the same buffer. Nothing is appended if the ID is invalid.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let mut url = String::from("/users/");
+  /// codec.encode_into(12345, &mut url).unwrap();
+  /// assert_eq!(url, "/users/NkK9");
+  /// ```
+  pub fn encode_into<T: PositiveInteger>(&self, id: T, out: &mut String) -> Result<(), Error> {
+    let number = self.id_to_number(id)?;
+    self.encode_vec_into(&[number], out)
   }
 
-  fn encode_vec(&self, numbers: &Vec<usize>) -> String {
-    let mut number_hash_int  = 0;
-    
-    // magic number
-    let mut count = 100; 
+  /// Same as `encode`, but writes the hash at the start of `buf`, and returns its length in bytes.
+  ///
+  /// Nothing is allocated for alphabets of up to 128 characters, unless the codec has a blocklist, a MAC or FPE.
+  /// Returns an `Error::BufferTooSmall` if the hash doesn't fit, in which case the content of `buf` is unspecified.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let mut buf = [0u8; 16];
+  /// let len = codec.encode_to_slice(12345, &mut buf).unwrap();
+  /// assert_eq!(&buf[..len], b"NkK9");
+  /// assert!(codec.encode_to_slice(12345, &mut buf[..3]).is_err());
+  /// ```
+  pub fn encode_to_slice<T: PositiveInteger>(&self, id: T, buf: &mut [u8]) -> Result<usize, Error> {
+    let number = self.id_to_number(id)?;
+    let capacity = buf.len();
+    let mut out = SliceOut::new(buf);
+    with_symbols(self.alphabet.len(), |symbols| self.encode_vec_with(&[number], &mut out, symbols))?;
+    out.finish().ok_or(Error::BufferTooSmall { capacity })
+  }
+
+  /// Encodes a batch of IDs, one hash per ID, reusing the same working buffers for all of them.
+  ///
+  /// Returns the first error, if any ID is invalid.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// assert_eq!(codec.encode_many(&[12345u64, 5]), Ok(vec!["NkK9".to_string(), "0rDd".to_string()]));
+  /// ```
+  pub fn encode_many<T: PositiveInteger + Copy>(&self, ids: &[T]) -> Result<Vec<String>, Error> {
+    let mut scratch = Scratch::default();
+    ids.iter().map(|id| {
+      let number = self.id_to_number(*id)?;
+      let mut hash = String::new();
+      self.encode_vec_with(&[number], &mut hash, scratch.symbols(self.alphabet.len()))?;
+      Ok(hash)
+    }).collect()
+  }
+
+  /// Converts several IDs to a single Hashid String, which `decode` and `decode_as` return in the same order.
+  ///
+  /// Returns an `Error::InvalidInputId` if there are no IDs, or if any ID is invalid.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let hash = codec.encode_slice(&[42u64, 1337]).unwrap();
+  /// assert_eq!(hash, "KgUO9");
+  /// assert_eq!(codec.decode(&hash), Ok(vec![42, 1337]));
+  /// ```
+  pub fn encode_slice<T: PositiveInteger + Copy>(&self, ids: &[T]) -> Result<String, Error> {
+    if ids.is_empty() {
+      return Err(Error::InvalidInputId)
+    }
+    let numbers = ids.iter().map(|id| self.id_to_number(*id)).collect::<Result<Vec<u128>, Error>>()?;
+    self.encode_vec(&numbers)
+  }
+
+  /// Validate/Convert Input as a positive integer, widened to u128 so every PositiveInteger fits.
+  /// Error depending on PositiveInteger implementation, but probably a Error::InvalidInputId
+  fn id_to_number<T: PositiveInteger>(&self, id: T) -> Result<u128, Error> {
+    let number = if self.allow_negative { id.to_zigzag() } else { id.to_u128() }?;
+    #[cfg(feature = "fpe")]
+    if self.fpe.is_some() && number > u64::MAX as u128 {
+      return Err(Error::InvalidInputId)
+    }
+    Ok(number)
+  }
 
-    for number in numbers.iter() {
-      number_hash_int += number % count;
-      count += 1;
+  /// Converts a decoded number back to the requested integer type, reversing `id_to_number`.
+  fn number_to_id<T: FromHashidNumber>(&self, number: u128) -> Result<T, Error> {
+    if self.allow_negative { T::from_i128(unzigzag(number)) } else { T::from_u128(number) }
+  }
+
+  /// Decodes a hash made of a single ID to the requested integer type.
+  fn decode_id<T: FromHashidNumber>(&self, hash: &str) -> Result<T, Error> {
+    let numbers = self.decode_numbers(hash)?;
+    match numbers.as_slice() {
+      [number] => self.number_to_id(*number),
+      _ => Err(Error::UnexpectedNumberCount { expected: 1, got: numbers.len() })
+    }
+  }
+
+  fn encode_vec(&self, numbers: &[u128]) -> Result<String, Error> {
+    let mut ret_str = String::new();
+    self.encode_vec_into(numbers, &mut ret_str)?;
+    Ok(ret_str)
+  }
+
+  /// Appends the hash of the numbers to `out`, with the prefix and suffix.
+  ///
+  /// Nothing is appended if the hash would be longer than the maximum length.
+  fn encode_vec_into(&self, numbers: &[u128], out: &mut String) -> Result<(), Error> {
+    with_symbols(self.alphabet.len(), |symbols| self.encode_vec_with(numbers, out, symbols))
+  }
+
+  /// Same as `encode_vec_into`, with `symbols`, twice as long as the alphabet, as working space.
+  fn encode_vec_with(&self, numbers: &[u128], out: &mut impl HashOut, symbols: &mut [char]) -> Result<(), Error> {
+    #[cfg(feature = "fpe")]
+    let encrypted: Vec<u128>;
+    #[cfg(feature = "fpe")]
+    let numbers = match &self.fpe {
+      Some(fpe) => {
+        encrypted = numbers.iter().map(|number| fpe.encrypt(*number as u64) as u128).collect();
+        &encrypted[..]
+      },
+      None => numbers
     };
 
-    let idx = number_hash_int % self.alphabet.len();
-    let ret = self.alphabet[idx..idx+1].to_string();
-    let mut ret_str = ret.clone();
+    let original_len = out.len();
+    // Enough for most hashes of ASCII alphabets, so the String is allocated once
+    out.reserve(self.prefix.len() + self.min_hash_length.max(numbers.len() * 12) + self.suffix.len());
+    out.push_str(&self.prefix);
+    let start = out.len();
+    self.encode_body_into(numbers, out, symbols);
+    if let Some(max) = self.max_hash_length {
+      let length = out.as_str()[start..].chars().count();
+      if length > max {
+        out.truncate(original_len);
+        return Err(Error::HashTooLong { length, max })
+      }
+    }
+    #[cfg(feature = "mac")]
+    if let Some(mac) = &self.mac {
+      let tag = mac.tag(&self.prefix, &out.as_str()[start..], &self.alphabet);
+      out.push_str(&tag);
+    }
+    out.push_str(&self.suffix);
+    // A hash that didn't fit a caller's buffer fails in `encode_to_slice`, and isn't counted
+    if !out.overflowed() {
+      self.metrics.encoded();
+    }
+    Ok(())
+  }
 
-    let mut t_alphabet = self.alphabet.clone();
-    let mut i = 0;
-    let len = self.separators.len();
-    let last_len = numbers.len();
-    for number in numbers.iter() {
-      let buffer = format!("{}{}{}", ret, self.salt.0, t_alphabet);
-      t_alphabet = hashids_shuffle(t_alphabet.clone(), &HashidSalt::from(&buffer[0..t_alphabet.len()])).unwrap();
-      let last = hash(*number, &t_alphabet);
+  /// Appends the hash of the numbers to `out`, working in place on what comes after its current length.
+  ///
+  /// Hashes containing a blocked word are re-encoded with the next lottery character, which decoding reads from the hash itself.
+  /// If they all contain one, the first hash is kept.
+  fn encode_body_into(&self, numbers: &[u128], out: &mut impl HashOut, symbols: &mut [char]) {
+    let start = out.len();
+    self.encode_attempt_into(numbers, out, 0, symbols);
+    if self.blocklist.is_empty() {
+      return
+    }
+
+    for attempt in 1..self.alphabet.len() {
+      if !self.is_blocked(&out.as_str()[start..]) {
+        return
+      }
+      out.truncate(start);
+      self.encode_attempt_into(numbers, out, attempt, symbols);
+    }
+    if self.is_blocked(&out.as_str()[start..]) {
+      out.truncate(start);
+      self.encode_attempt_into(numbers, out, 0, symbols);
+    }
+  }
+
+  fn is_blocked(&self, hash: &str) -> bool {
+    let hash = hash.to_lowercase();
+    self.blocklist.iter().any(|word| hash.contains(word.as_str()))
+  }
+
+  /// Appends one hash of the numbers to `out`, using `symbols`, twice as long as the alphabet, as working space.
+  fn encode_attempt_into(&self, numbers: &[u128], out: &mut impl HashOut, attempt: usize, symbols: &mut [char]) {
+    let (t_alphabet, buffer) = symbols.split_at_mut(self.alphabet.len());
+    t_alphabet.copy_from_slice(&self.alphabet);
+    let number_hash_int = number_hash_int(numbers) + attempt;
 
-      ret_str.push_str(&last);
+    let lottery = self.alphabet[number_hash_int % self.alphabet.len()];
+    let start = out.len();
+    out.push(lottery);
+    // Lengths are counted in characters, which may be several bytes long
+    let mut length = 1;
+    let mut first_digit = lottery;
+
+    let last_len = numbers.len();
+    for (i, number) in numbers.iter().enumerate() {
+      shuffle_key_into(lottery, &self.salt, t_alphabet, buffer);
+      hashids_shuffle(t_alphabet, buffer);
+      let (first, digits) = hash_into(*number, t_alphabet, out);
+      length += digits;
+      if i == 0 {
+        first_digit = first;
+      }
 
       if (i + 1) < last_len {
-        let mut v = *number % (last.as_bytes()[0] as usize + i as usize);
-        v = v % len;
-        ret_str.push(self.separators.as_bytes()[v as usize] as char);
+        out.push(self.separator(*number, first, i));
+        length += 1;
       }
-      i += 1;
     };
 
-    if ret_str.len() < self.min_hash_length {
-      let guard_idx = (number_hash_int + ret_str.clone().into_bytes()[0] as usize) % self.guards.len();
-      let guard = self.guards[guard_idx..guard_idx+1].to_string();
-      // let mut t = guard.clone();
-      // t.push_str(&ret_str);
-      ret_str = format!("{}{}", guard, ret_str);
+    let guards = self.guards_for(number_hash_int, lottery, first_digit);
+    self.pad_into(out, start, length, guards, t_alphabet, buffer);
+  }
+
+  /// The separator following the `i`th number, whose first digit is `first`.
+  fn separator(&self, number: u128, first: char, i: usize) -> char {
+    let mut v = number % (first as u128 + i as u128);
+    v %= self.separators.len() as u128;
+    self.separators[v as usize]
+  }
+
+  /// The guards added before and after the hash when it is shorter than the minimum length.
+  fn guards_for(&self, number_hash_int: usize, lottery: char, first_digit: char) -> (char, char) {
+    let guard = |seed: char| self.guards[(number_hash_int + seed as usize) % self.guards.len()];
+    (guard(lottery), guard(first_digit))
+  }
 
-      if ret_str.len() < self.min_hash_length {
-        let guard_idx = (number_hash_int + ret_str.clone().into_bytes()[2] as usize) % self.guards.len();
-        ret_str.push_str(&self.guards[guard_idx..guard_idx+1]);
+  /// Brings the hash starting at `start` in `out`, `length` characters long, to the minimum length:
+  /// first with the guards, then with halves of the alphabet shuffled again and again.
+  ///
+  /// `t_alphabet` is the alphabet as shuffled for the last number, and `buffer` working space as long as it.
+  fn pad_into(&self, out: &mut impl HashOut, start: usize, mut length: usize, guards: (char, char), t_alphabet: &mut [char], buffer: &mut [char]) {
+    if length < self.min_hash_length {
+      out.insert(start, guards.0);
+      length += 1;
+
+      if length < self.min_hash_length {
+        out.push(guards.1);
+        length += 1;
       }
     };
 
+    // Small alphabets may need several rounds before being long enough
     let half_len = t_alphabet.len() / 2;
-    while ret_str.len() < self.min_hash_length {
-      t_alphabet = hashids_shuffle(t_alphabet.clone(), &HashidSalt::from(t_alphabet)).unwrap();
-      let mut t_ret = "".to_string();
-      t_ret.push_str(&t_alphabet[half_len..]);
-      t_ret.push_str(&ret_str[..]);
-      t_ret.push_str(&t_alphabet[0..half_len]);
-      ret_str = t_ret;
-
-      let excess = ret_str.len() - self.min_hash_length;
-      if excess > 0 {
-        let start_pos = excess / 2;
-        ret_str = ret_str[start_pos..start_pos + self.min_hash_length].to_string();
+    while length < self.min_hash_length {
+      buffer.copy_from_slice(t_alphabet);
+      hashids_shuffle(t_alphabet, buffer);
+      // The second half goes before the hash and the first half after it, less what exceeds the minimum length,
+      // cut evenly from both ends, so the hash is never longer than it ends up
+      let excess = (length + t_alphabet.len()).saturating_sub(self.min_hash_length);
+      let (cut_before, cut_after) = (excess / 2, excess - excess / 2);
+      for &c in t_alphabet[half_len + cut_before..].iter().rev() {
+        out.insert(start, c);
+      }
+      for &c in &t_alphabet[..half_len - cut_after] {
+        out.push(c);
       }
+      length += t_alphabet.len() - excess;
     };
+  }
+
+  /// Converts a Hashid back to the integers it was made of.
+  ///
+  /// Accepts anything that can be borrowed as a `&str`, so path parameters and `String`s alike can be passed without cloning.
+  /// The result is re-encoded to make sure the hash was genuinely produced with this codec's settings,
+  /// and an `Error::InvalidHash` is returned otherwise.
+  /// Characters that can't be part of a hash are reported with their position in an `Error::InvalidCharacter`.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// assert_eq!(codec.decode("NkK9"), Ok(vec![12345]));
+  /// assert_eq!(codec.decode(String::from("NkK9")), Ok(vec![12345]));
+  /// ```
+  pub fn decode(&self, hash: impl AsRef<str>) -> Result<Vec<usize>, Error> {
+    self.decode_as(hash.as_ref())
+  }
+
+  /// Same as `decode`, but converts the numbers to the integer type of your choice, checking they fit.
+  ///
+  /// Returns an `Error::NumberOverflow` rather than truncating if a number is out of range for `T`.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// assert_eq!(codec.decode_as::<i32>("NkK9"), Ok(vec![12345i32]));
+  ///
+  /// let large = codec.encode(u64::from(u32::MAX) + 1).unwrap();
+  /// assert_eq!(codec.decode_as::<u32>(&large), Err(Error::NumberOverflow));
+  /// ```
+  pub fn decode_as<T: FromHashidNumber>(&self, hash: &str) -> Result<Vec<T>, Error> {
+    self.decode_numbers(hash)?
+      .into_iter()
+      .map(|number| self.number_to_id(number))
+      .collect()
+  }
+
+  /// Same as `decode`, but without narrowing the numbers, so hashes of `u128` IDs can be decoded.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let hash = codec.encode(u128::MAX).unwrap();
+  /// assert_eq!(codec.decode_u128(&hash), Ok(vec![u128::MAX]));
+  /// ```
+  pub fn decode_u128(&self, hash: impl AsRef<str>) -> Result<Vec<u128>, Error> {
+    self.decode_as(hash.as_ref())
+  }
+
+  /// Same as `decode`, but appends the numbers to a caller-owned `Vec` and returns how many were appended.
+  ///
+  /// Allows batch decoders to reuse a single buffer. Nothing is appended if the hash is invalid.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let mut ids = Vec::new();
+  /// assert_eq!(codec.decode_into("NkK9", &mut ids), Ok(1));
+  /// assert_eq!(codec.decode_into("aBMswoO2UB3Sj", &mut ids), Ok(4));
+  /// assert_eq!(ids, vec![12345, 683, 94108, 123, 5]);
+  /// ```
+  pub fn decode_into(&self, hash: &str, out: &mut Vec<u64>) -> Result<usize, Error> {
+    let numbers = self.decode_u128(hash)?;
+    let previous_len = out.len();
+    for number in numbers {
+      match u64::try_from(number) {
+        Ok(number) => out.push(number),
+        Err(_) => {
+          out.truncate(previous_len);
+          return Err(Error::NumberOverflow)
+        }
+      }
+    }
+    Ok(out.len() - previous_len)
+  }
+
+  /// Decodes hashes of signed IDs, made by a codec built with `allow_negative()`.
+  ///
+  /// Without `allow_negative()`, IDs are never negative and this is equivalent to `decode` with i64 results.
+  /// Returns an `Error::NumberOverflow` if a number doesn't fit in an i64.
+  pub fn decode_signed(&self, hash: &str) -> Result<Vec<i64>, Error> {
+    self.decode_as(hash)
+  }
 
-    ret_str
+  /// Same as `decode`, but skips re-encoding the result to verify the hash, for about twice the throughput.
+  ///
+  /// Only use it on trusted input, such as hashes this codec produced itself:
+  /// a tampered or random hash will not be detected, and decodes to meaningless numbers instead of an `Error::InvalidHash`.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// assert_eq!(codec.decode_unchecked("NkK9"), Ok(vec![12345]));
+  /// ```
+  pub fn decode_unchecked(&self, hash: impl AsRef<str>) -> Result<Vec<usize>, Error> {
+    self.unhash_numbers(hash.as_ref())?
+      .into_iter()
+      .map(|number| self.number_to_id(number))
+      .collect()
+  }
+
+  /// The decoding itself, without interpreting the numbers according to `allow_negative`.
+  fn decode_numbers(&self, hash: &str) -> Result<Vec<u128>, Error> {
+    self.report_decode(self.verified_numbers(hash), hash)
+  }
+
+  /// Reports the outcome of decoding the hash to the metrics, and failures to tracing.
+  pub(crate) fn report_decode<T>(&self, result: Result<T, Error>, hash: &str) -> Result<T, Error> {
+    match &result {
+      Ok(_) => self.metrics.decoded(),
+      Err(err) => {
+        trace::decode_failed(err, hash);
+        self.metrics.decode_failed(err);
+      }
+    }
+    result
+  }
+
+  /// Same as `decode`, without reporting failures, for callers trying several codecs.
+  pub(crate) fn decode_quietly(&self, hash: &str) -> Result<Vec<usize>, Error> {
+    self.verified_numbers(hash)?.into_iter().map(|number| self.number_to_id(number)).collect()
+  }
+
+  /// Same as `decode_numbers`, without reporting failures.
+  fn verified_numbers(&self, hash: &str) -> Result<Vec<u128>, Error> {
+    let body = self.hash_body(hash)?;
+    if self.max_hash_length.is_some_and(|max| body.chars().count() > max) {
+      return Err(Error::InvalidHash)
+    }
+    // Blocked words shift the lottery character, only encoding again tells whether the shift was needed
+    let structural = self.blocklist.is_empty();
+    let ret = self.unhash_body(&body, structural)?;
+
+    if !structural {
+      let mut check_hash = String::new();
+      with_symbols(self.alphabet.len(), |symbols| self.encode_body_into(&ret, &mut check_hash, symbols));
+      if check_hash != *body {
+        return Err(Error::InvalidHash)
+      };
+    }
+
+    self.decrypt_numbers(ret)
+  }
+
+  /// Reads the numbers of a hash, without verifying it was genuinely produced by this codec.
+  fn unhash_numbers(&self, hash: &str) -> Result<Vec<u128>, Error> {
+    let result = self.hash_body(hash)
+      .and_then(|body| self.unhash_body(&body, false))
+      .and_then(|numbers| self.decrypt_numbers(numbers));
+    self.report_decode(result, hash)
+  }
+
+  /// A hash that decoded, as this codec writes it: decoding ignores case, so only the case of its body may differ.
+  pub(crate) fn canonical_hash(&self, hash: &str) -> String {
+    let body = &hash[self.prefix.len()..hash.len() - self.suffix.len()];
+    [self.prefix.as_str(), &self.case.apply(body), self.suffix.as_str()].concat()
+  }
+
+  /// Whether the character can be part of the hashes, outside of the prefix and suffix.
+  pub(crate) fn uses_character(&self, character: char) -> bool {
+    self.symbol_set.contains(character)
   }
 
-  pub fn decode(&self, hash: String) -> Result<Vec<usize>, Error> {
+  /// Reverses the encryption of `with_fpe_key`, if any.
+  fn decrypt_numbers(&self, numbers: Vec<u128>) -> Result<Vec<u128>, Error> {
+    #[cfg(feature = "fpe")]
+    if let Some(fpe) = &self.fpe {
+      // Encrypted numbers all fit in 64 bits
+      return numbers.into_iter()
+        .map(|number| u64::try_from(number).map(|number| fpe.decrypt(number) as u128).map_err(|_| Error::InvalidHash))
+        .collect()
+    }
+    Ok(numbers)
+  }
+
+  /// Strips the prefix and suffix off a hash, and folds what is left to the codec's case.
+  fn hash_body<'a>(&self, hash: &'a str) -> Result<Cow<'a, str>, Error> {
     if hash.is_empty() {
       return Err(Error::EmptyHash)
     }
-    
-    let regexp = format!("[{}]", self.guards);
-    let re = Regex::new(&regexp).unwrap();
-    let t_hash = re.replace_all(&hash, " ");
-    let split1: Vec<&str> = t_hash.split_whitespace().collect();
+    let body = hash.strip_prefix(self.prefix.as_str()).ok_or(Error::InvalidPrefix)?;
+    let body = body.strip_suffix(self.suffix.as_str()).ok_or(Error::InvalidSuffix)?;
+    let body = self.case.apply(body);
+    #[cfg(feature = "mac")]
+    if let Some(mac) = &self.mac {
+      let split = body.char_indices().rev().nth(mac.tag_length - 1).map(|(split, _)| split).ok_or(Error::InvalidMac)?;
+      if !mac.verify(&self.prefix, &body[..split], &body[split..], &self.alphabet) {
+        return Err(Error::InvalidMac)
+      }
+      return Ok(match body {
+        Cow::Borrowed(body) => Cow::Borrowed(&body[..split]),
+        Cow::Owned(mut body) => { body.truncate(split); Cow::Owned(body) }
+      })
+    }
+    Ok(body)
+  }
 
-    let mut i = 0;
+  /// Reads the numbers of a hash stripped by `hash_body`.
+  ///
+  /// With `verify`, also checks the hash is exactly what encoding the numbers gives, piece by piece:
+  /// the lottery character, digits without leading zeros, separators, guards and padding.
+  fn unhash_body(&self, hash: &str, verify: bool) -> Result<Vec<u128>, Error> {
+    if hash.is_empty() {
+      return Err(Error::EmptyHash)
+    }
 
-    let len = split1.len();
-    if len == 3 || len == 2 {
-      i = 1;
+    for (position, character) in hash.char_indices() {
+      if !self.symbol_set.contains(character) {
+        // Positions are reported in the hash as it was given, prefix included
+        return Err(Error::InvalidCharacter { character, position: self.prefix.len() + position })
+      }
     }
-    let mut hash_breakdown = split1[i].to_string();
+    
+    // Guards surround the body when the minimum length requires them: with 2 or 3 parts, the body is the second one
+    let mut parts = hash.split(|c| self.guard_set.contains(c)).filter(|part| !part.is_empty());
+    let first = parts.next();
+    let second = parts.next();
+    let fourth = parts.nth(1);
+    let hash_breakdown = match (second, fourth) {
+      (Some(second), None) => second,
+      // Nothing is left when the hash is only made of guards
+      _ => first.ok_or(Error::InvalidHash)?
+    };
+
+    let lottery = hash_breakdown.chars().next().ok_or(Error::InvalidHash)?;
+    let mut rest = &hash_breakdown[lottery.len_utf8()..];
+
+    with_symbols(self.alphabet.len(), |symbols| {
+      let (alphabet, buffer) = symbols.split_at_mut(self.alphabet.len());
+      alphabet.copy_from_slice(&self.alphabet);
+      let mut ret: Vec<u128> = Vec::new();
+      let mut first_digit = lottery;
+
+      let mut positions = Positions::default();
+      loop {
+        let end = rest.find(|c| self.separator_set.contains(c)).unwrap_or(rest.len());
+        let (s, separator) = (&rest[..end], rest[end..].chars().next());
+        rest = separator.map_or("", |separator| &rest[end + separator.len_utf8()..]);
+
+        if let Some(first) = s.chars().next() {
+          shuffle_key_into(lottery, &self.salt, alphabet, buffer);
+          hashids_shuffle(alphabet, buffer);
+          positions.rebuild(alphabet);
+          // A number too large for u128 can't have been encoded by this codec.
+          let number = unhash(s, alphabet, &positions).ok_or(Error::InvalidHash)?;
+          if verify {
+            let leading_zero = first == alphabet[0] && s.len() > first.len_utf8();
+            if leading_zero || separator.is_some_and(|separator| separator != self.separator(number, first, ret.len())) {
+              return Err(Error::InvalidHash)
+            }
+          }
+          if ret.is_empty() {
+            first_digit = first;
+          }
+          ret.push(number);
+        } else if verify {
+          // Encoding never puts separators next to each other, nor at either end
+          return Err(Error::InvalidHash)
+        }
 
-    let lottery = hash_breakdown[0..1].to_string();
-    hash_breakdown = hash_breakdown[1..].to_string();
+        if separator.is_none() {
+          break
+        }
+      };
 
-    let regexp2 = format!("[{}]", self.separators);
-    let re2 = Regex::new(&regexp2).unwrap();
-    hash_breakdown = re2.replace_all(&hash_breakdown, " ").to_string();
-    let split2: Vec<&str> = hash_breakdown.split_whitespace().collect();
+      if verify {
+        let number_hash_int = number_hash_int(&ret);
+        if lottery != self.alphabet[number_hash_int % self.alphabet.len()] {
+          return Err(Error::InvalidHash)
+        }
+        let guards = self.guards_for(number_hash_int, lottery, first_digit);
+        let genuine = with_string(|expected| {
+          expected.push_str(hash_breakdown);
+          self.pad_into(expected, 0, hash_breakdown.chars().count(), guards, alphabet, buffer);
+          expected == hash
+        });
+        if !genuine {
+          return Err(Error::InvalidHash)
+        }
+      }
 
-    let mut alphabet = self.alphabet.clone();
-    let mut ret: Vec<usize> = Vec::new();
+      Ok(ret)
+    })
+  }
 
-    for s in split2 {
-      let buffer = format!("{}{}{}", lottery, self.salt.0, alphabet);
+  /// Decodes a hash that is expected to contain exactly one number, which is what `encode` produces.
+  ///
+  /// Saves indexing into the `Vec` returned by `decode`.
+  /// Returns an `Error::UnexpectedNumberCount` if the hash holds several numbers.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let id = codec.decode_single("NkK9").unwrap();
+  /// assert_eq!(id, 12345);
+  /// ```
+  pub fn decode_single(&self, hash: &str) -> Result<u64, Error> {
+    let numbers = self.decode_u128(hash)?;
+    match numbers.as_slice() {
+      [number] => u64::try_from(*number).map_err(|_| Error::NumberOverflow),
+      _ => Err(Error::UnexpectedNumberCount { expected: 1, got: numbers.len() })
+    }
+  }
 
-      let alpha_len = alphabet.len();
-      alphabet = hashids_shuffle(alphabet, &HashidSalt::from(&buffer[0..alpha_len]))?;
-      ret.push(unhash(s.to_string(), &alphabet));
-    };
+  /// Decodes hashes made of a single ID each, keeping going past invalid ones.
+  ///
+  /// Returns one result per hash, in the same order, so bulk jobs can report exactly which hashes failed and why.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let results = codec.decode_many(vec!["NkK9", "", "0rDd"]);
+  /// assert_eq!(results, vec![Ok(12345), Err(Error::EmptyHash), Ok(5)]);
+  /// ```
+  pub fn decode_many<'a>(&self, hashes: impl IntoIterator<Item = &'a str>) -> Vec<Result<u64, Error>> {
+    hashes.into_iter().map(|hash| self.decode_single(hash)).collect()
+  }
 
-    let check_hash = self.encode_vec(&ret);
-    if check_hash != hash {
-      return Err(Error::InvalidHash)
-    };
+  /// Decodes a hash that is expected to contain exactly `N` numbers, returned as an array.
+  ///
+  /// Returns an `Error::UnexpectedNumberCount` if the hash holds any other amount of numbers.
+  /// ```
+  /// use hashids::{HashidBuilder, Error};
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let [a, b, c, d] = codec.decode_exact::<4>("aBMswoO2UB3Sj").unwrap();
+  /// assert_eq!((a, b, c, d), (683, 94108, 123, 5));
+  /// assert_eq!(codec.decode_exact::<2>("aBMswoO2UB3Sj"), Err(Error::UnexpectedNumberCount { expected: 2, got: 4 }));
+  /// ```
+  pub fn decode_exact<const N: usize>(&self, hash: &str) -> Result<[u64; N], Error> {
+    let numbers = self.decode_numbers(hash)?;
+    if numbers.len() != N {
+      return Err(Error::UnexpectedNumberCount { expected: N, got: numbers.len() })
+    }
 
+    let mut ret = [0; N];
+    for (slot, number) in ret.iter_mut().zip(numbers) {
+      *slot = self.number_to_id(number)?;
+    }
     Ok(ret)
   }
 
 }
 
 
-/// This trait is used to group and tag acceptable integer input: u32, u64, i32, i64.
+/// This trait is used to group and tag acceptable integer input: u32, u64, u128, i32, i64.
 ///
 /// The algorithm doesn't allow negative integers and floats, 
 /// however i32 and i64 are still acccpeted and errors if negative, because Diesel returns i64 integers, 
 /// even though I've never seen a database return an negative ID.
-/// Converts to usize internally.
+/// Zero is a valid ID for every implementation.
 pub trait PositiveInteger {
   fn to_usize(self) -> Result<usize, Error>;
+
+  fn to_u128(self) -> Result<u128, Error> where Self: Sized {
+    self.to_usize().map(|number| number as u128)
+  }
+
+  /// Zigzag encoding used by codecs built with `allow_negative()`: 0, -1, 1, -2, 2... become 0, 1, 2, 3, 4...
+  /// Signed implementations must override it to handle negative numbers.
+  fn to_zigzag(self) -> Result<u128, Error> where Self: Sized {
+    self.to_u128()?.checked_mul(2).ok_or(Error::InvalidInputId)
+  }
 }
 
 impl PositiveInteger for u32 {
   fn to_usize(self) -> Result<usize, Error> { Ok(self as usize) }
+
+  fn to_u128(self) -> Result<u128, Error> { Ok(self as u128) }
 }
 
 impl PositiveInteger for u64 {
   fn to_usize(self) -> Result<usize, Error> { 
-    if self >= std::i64::MAX as u64  {
+    usize::try_from(self.to_u128()?).map_err(|_| Error::InvalidInputId)
+  }
+
+  // Not through usize, which is only 32 bits on some targets
+  fn to_u128(self) -> Result<u128, Error> {
+    if self >= i64::MAX as u64  {
       return Err(Error::InvalidInputId)
     }
-    Ok(self as usize) }
+    Ok(self as u128)
+  }
+}
+
+impl PositiveInteger for u128 {
+  fn to_usize(self) -> Result<usize, Error> {
+    usize::try_from(self).map_err(|_| Error::InvalidInputId)
+  }
+
+  fn to_u128(self) -> Result<u128, Error> { Ok(self) }
+}
+
+/// This trait is used to group the integer types decoded numbers can be converted to: u32, u64, u128, usize, i32, i64.
+///
+/// Conversions are range-checked and return an `Error::NumberOverflow` instead of truncating.
+/// Used by `HashidCodec::decode_as`.
+pub trait FromHashidNumber: Sized {
+  fn from_u128(number: u128) -> Result<Self, Error>;
+
+  /// Used by codecs built with `allow_negative()`, where decoded numbers may be negative.
+  fn from_i128(number: i128) -> Result<Self, Error> {
+    u128::try_from(number).map_err(|_| Error::NumberOverflow).and_then(Self::from_u128)
+  }
+}
+
+macro_rules! impl_from_hashid_number {
+  ($($integer:ty),*) => {
+    $(
+      impl FromHashidNumber for $integer {
+        fn from_u128(number: u128) -> Result<Self, Error> {
+          <$integer>::try_from(number).map_err(|_| Error::NumberOverflow)
+        }
+
+        fn from_i128(number: i128) -> Result<Self, Error> {
+          <$integer>::try_from(number).map_err(|_| Error::NumberOverflow)
+        }
+      }
+    )*
+  };
+}
+
+impl_from_hashid_number!(u32, u64, u128, usize, i32, i64);
+
+/// References are accepted too, so iterators over borrowed IDs can be encoded directly.
+impl<T: PositiveInteger + Copy> PositiveInteger for &T {
+  fn to_usize(self) -> Result<usize, Error> { (*self).to_usize() }
+
+  fn to_u128(self) -> Result<u128, Error> { (*self).to_u128() }
+
+  fn to_zigzag(self) -> Result<u128, Error> { (*self).to_zigzag() }
 }
 
 impl PositiveInteger for i32 {
   fn to_usize(self) -> Result<usize, Error> {
-    if self <= 0  {
+    if self < 0  {
       Err(Error::InvalidInputId) 
     } else {
       Ok(self as usize) 
     }
   }
+
+  fn to_u128(self) -> Result<u128, Error> {
+    if self < 0  {
+      Err(Error::InvalidInputId)
+    } else {
+      Ok(self as u128)
+    }
+  }
+
+  fn to_zigzag(self) -> Result<u128, Error> { Ok(zigzag(self as i128)) }
 }
 
 impl PositiveInteger for i64 {
   fn to_usize(self) -> Result<usize, Error> {
-    if self <= 0  {
-      return Err(Error::InvalidInputId)
+    if self < 0  {
+      Err(Error::InvalidInputId)
     }
     // else if self >= std::i64::MAX  {
     //   return Err(Error::InvalidInputId)
     // }
     else {
-      Ok(self as usize) 
+      usize::try_from(self).map_err(|_| Error::InvalidInputId)
     }
   }
+
+  // Not through usize, which is only 32 bits on some targets
+  fn to_u128(self) -> Result<u128, Error> {
+    if self < 0  {
+      Err(Error::InvalidInputId)
+    } else {
+      Ok(self as u128)
+    }
+  }
+
+  fn to_zigzag(self) -> Result<u128, Error> { Ok(zigzag(self as i128)) }
 }
 
 
-/**
+/*
   Following are functions that do not actually use self, so do not belong scoped inside objects.
   They are not public, so API change is fine. Seperating them also greatly facilitates unit testing.
 */
 
 /// Filters separqtors out of the alphabet, and alphabet out of separators
-fn get_non_duplicated_string(separators: String, alphabet: String) -> (String, String) {
-  let check_separator: HashSet<char> = separators.chars().collect();
-  let check_alphabet: HashSet<char> = alphabet.chars().collect();
+fn get_non_duplicated_string(separators: Vec<char>, alphabet: Vec<char>) -> (Vec<char>, Vec<char>) {
+  let check_separator: HashSet<char> = separators.iter().copied().collect();
+  let check_alphabet: HashSet<char> = alphabet.iter().copied().collect();
 
-  let mut modified_separators = String::new();
-  let mut modified_alphabet = String::new();
-  
-  for c in separators.chars() {
-    if check_alphabet.contains(&c) {
-      modified_separators.push(c);
-    }
-  };
-  
-  for c in alphabet.chars() {
-    if !check_separator.contains(&c) {
-      modified_alphabet.push(c);
-    }
-  };
+  let modified_separators = separators.into_iter().filter(|c| check_alphabet.contains(c)).collect();
+  let modified_alphabet = alphabet.into_iter().filter(|c| !check_separator.contains(c)).collect();
 
   (modified_separators, modified_alphabet)
 }
 
 
-fn get_unique_alphabet(alphabet: String) -> String {
-  let mut unique_alphabet: String = String::new();
+/// Sum of the numbers modulo their rank, offset by 100, which selects the lottery character and the guards.
+fn number_hash_int(numbers: &[u128]) -> usize {
+  // magic number
+  (100..).zip(numbers.iter()).map(|(count, number)| (number % count) as usize).sum()
+}
+
+fn zigzag(number: i128) -> u128 {
+  ((number << 1) ^ (number >> 127)) as u128
+}
+
+fn unzigzag(number: u128) -> i128 {
+  ((number >> 1) as i128) ^ -((number & 1) as i128)
+}
+
+fn get_unique_alphabet(alphabet: &str) -> Vec<char> {
+  let mut unique_alphabet = Vec::new();
   let mut check_map = HashSet::new();
   
   for c in alphabet.chars() {
     // insert into a hashset gives a bool, true if it was actually inserted, false if it was already there.
-    if check_map.insert(c.clone()) {
+    if check_map.insert(c) {
       // the result is then used to create the alphabet
       unique_alphabet.push(c);
     }
@@ -529,88 +2089,116 @@ fn get_unique_alphabet(alphabet: String) -> String {
   unique_alphabet
 }
 
+/// The salt of the shuffles during encoding and decoding: the lottery character, the salt, then the alphabet,
+/// cut to the length of the alphabet.
+fn shuffle_key_into(lottery: char, salt: &[char], alphabet: &[char], key: &mut [char]) {
+  let symbols = std::iter::once(lottery).chain(salt.iter().copied()).chain(alphabet.iter().copied());
+  for (slot, symbol) in key.iter_mut().zip(symbols) {
+    *slot = symbol;
+  }
+}
+
 // Function used in both the HashidCode and the builder. 
-fn hashids_shuffle(alphabet: String, salt: &HashidSalt) -> Result<String, Error> {
-    
-  let salt_len = salt.0.len();
-  if salt_len <= 0 {
-    return Err(Error::MissingSalt)
+/// Shuffles the alphabet in place, so callers can shuffle a slice of a reused buffer without any allocation.
+/// The salt must not be the alphabet itself: shuffling an alphabet with itself needs a copy of it first.
+fn hashids_shuffle(alphabet: &mut [char], salt: &[char]) {
+  // Like the reference implementations, an empty salt leaves the alphabet as it is
+  if salt.is_empty() {
+    return
   };
-  if alphabet.len() <= 0 {
-    return Err(Error::InvalidAlphabetLength)
-  }
 
-  let salt_arr: Vec<char> = salt.0.chars().collect();
-  let len = alphabet.len();
-  let mut i: usize = len - 1;
   let mut v: usize = 0;
   let mut p: usize = 0;
 
-  let shuffle = &mut alphabet.into_bytes();
-  while i > 0 {
-    v %= salt_len;
-    let t = salt_arr[v] as usize;
+  for i in (1..alphabet.len()).rev() {
+    v %= salt.len();
+    let t = salt[v] as usize;
     p += t;
     let j = (t + v + p) % i;
 
-    shuffle.swap(i,j);
+    alphabet.swap(i,j);
 
-    i -= 1;
     v += 1; 
   }
+}
+
+/// Position of the ASCII characters in a shuffled alphabet, to be rebuilt whenever it is shuffled again.
+/// Other characters are looked up in the alphabet itself.
+struct Positions([u32; 128]);
+
+impl Default for Positions {
+  fn default() -> Positions {
+    Positions([u32::MAX; 128])
+  }
+}
+
+impl Positions {
+  fn rebuild(&mut self, alphabet: &[char]) {
+    self.0 = [u32::MAX; 128];
+    for (position, &c) in alphabet.iter().enumerate() {
+      if c.is_ascii() {
+        self.0[c as usize] = position as u32;
+      }
+    }
+  }
 
-  // convert the shuffle [u8] back to String and return that
-  let res : String = shuffle.iter().map(|i| *i as char).collect();
-  Ok(res)
+  fn get(&self, c: char, alphabet: &[char]) -> Option<usize> {
+    if c.is_ascii() {
+      Some(self.0[c as usize]).filter(|&position| position != u32::MAX).map(|position| position as usize)
+    } else {
+      alphabet.iter().position(|x| *x == c)
+    }
+  }
 }
 
-fn unhash(input: String, alphabet: &String) -> usize {
-  let mut number= 0;
-  let input_slice = input.as_bytes();
-  let alpha_slice = alphabet.as_bytes();
-  let len = input.len() -1;
-  let alpha_len = alphabet.len();
+/// Returns None if a character isn't part of the alphabet, or the number overflows a u128.
+fn unhash(input: &str, alphabet: &[char], positions: &Positions) -> Option<u128> {
+  let mut number: u128 = 0;
+  let alpha_len = alphabet.len() as u128;
 
-  for (i, v) in input_slice.iter().enumerate() {
-    let position = alpha_slice.iter().position(|x| x == v).unwrap_or(0);
-    let pow_size = len - i;
-    number += position * alpha_len.pow(pow_size as u32);
+  for v in input.chars() {
+    let position = positions.get(v, alphabet)? as u128;
+    number = number.checked_mul(alpha_len)?.checked_add(position)?;
   };
 
-  number
+  Some(number)
 }
 
-fn hash(mut input: usize, alphabet: &str) -> String {
-  let mut hash = "".to_string();
-  let len = alphabet.len();
+/// Appends the digits of the number in the alphabet to `hash`, returning the first digit and how many there are.
+fn hash_into(mut input: u128, alphabet: &[char], hash: &mut impl HashOut) -> (char, usize) {
+  // Alphabets have at least 2 symbols, so a u128 has at most 128 digits
+  let mut digits = ['\0'; 128];
+  let len = alphabet.len() as u128;
+  let mut count = 0;
 
-  let mut idx = input % len;
   loop {
-    hash = format!("{}{}", alphabet[idx..idx+1].to_string(), hash);
+    digits[count] = alphabet[(input % len) as usize];
+    count += 1;
     input /= len;
-    if input <= 0 {
+    if input == 0 {
       break;
     }
-    idx = input % len;
   };
-  hash
+  for &digit in digits[..count].iter().rev() {
+    hash.push(digit);
+  }
+  (digits[count - 1], count)
 }
 
 /// converts a HEX String to a vector of integers;
-fn hex_to_vec(hex: String) -> Result<Vec<usize>, Error> {
-  // check the string is valid HEX
-  let _ = i64::from_str_radix(&hex, 16).map_err(|_| Error::NonHexString)?;
-
-  let mut numbers = Vec::new();
-  // iterate chars by group of 12, guard div
-  let regex = Regex::new(r"[\w\W]{1,12}").unwrap();
-  for matcher in regex.find_iter(&hex) {
-    let num = format!("1{}", matcher.as_str());
-    let v = usize::from_str_radix(&num.to_string(), 16).map_err(|_| Error::NonHexString)?;
-    numbers.push(v);
+fn hex_to_vec(hex: &str) -> Result<Vec<u128>, Error> {
+  // check the string is valid HEX. Parsing it as a whole would overflow for anything longer than 16 digits.
+  if hex.is_empty() || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+    return Err(Error::NonHexString)
   }
-  
-  Ok(numbers)
+
+  // iterate digits by group of 12, guard div, each prefixed with a 1 so leading zeros survive
+  hex.as_bytes().chunks(12).map(|chunk| {
+    chunk.iter().try_fold(1u128, |number, &digit| {
+      let digit = (digit as char).to_digit(16).ok_or(Error::NonHexString)?;
+      number.checked_mul(16).and_then(|number| number.checked_add(digit as u128)).ok_or(Error::NonHexString)
+    })
+  }).collect()
 }
 
 #[cfg(test)]
@@ -620,38 +2208,90 @@ mod tests {
   #[test]
   fn decode_hex_string() {
     let hex = "123456789abcdef".to_string();
-    let result = hex_to_vec(hex);
+    let result = hex_to_vec(&hex);
     assert_eq!(result, Ok(vec![301490975054524, 7663]));
   }
 
+  #[test]
+  fn decode_hex_string_keeps_leading_zeros() {
+    assert_eq!(hex_to_vec("000000000000000a"), Ok(vec![0x1000000000000, 0x1000a]));
+    assert_eq!(hex_to_vec("é"), Err(Error::NonHexString));
+    assert_eq!(hex_to_vec(""), Err(Error::NonHexString));
+  }
+
+  #[test]
+  fn integers_widen_to_u128_without_usize() {
+    assert_eq!((1u64 << 32).to_u128(), Ok(1 << 32));
+    assert_eq!((1i64 << 32).to_u128(), Ok(1 << 32));
+    assert_eq!(u32::MAX.to_u128(), Ok(u32::MAX as u128));
+    assert_eq!(i32::MAX.to_u128(), Ok(i32::MAX as u128));
+    assert_eq!(i64::MAX.to_u128(), Ok(i64::MAX as u128));
+    assert_eq!(u64::MAX.to_u128(), Err(Error::InvalidInputId));
+    assert_eq!((-1i32).to_u128(), Err(Error::InvalidInputId));
+    assert_eq!((-1i64).to_u128(), Err(Error::InvalidInputId));
+  }
+
   #[test]
   fn decode_non_hex_string_error() {
     let data = "4g".to_string();
-    let result = hex_to_vec(data);
+    let result = hex_to_vec(&data);
     assert_eq!(result, Err(Error::NonHexString));
   }
 
+  fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+  }
+
   #[test]
   fn valid_hash() {
     let mut data = 500;
-    let result = hash(data, &DEFAULT_ALPHABET.to_string());
+    let mut result = String::new();
+    assert_eq!(hash_into(data, &chars(DEFAULT_ALPHABET), &mut result), ('i', 2));
     assert_eq!(result, "ie");
     data = 12546843121;
-    let result = hash(data, &DEFAULT_ALPHABET.to_string());
-    assert_eq!(result, "nRhrdB");
+    assert_eq!(hash_into(data, &chars(DEFAULT_ALPHABET), &mut result), ('n', 6));
+    assert_eq!(result, "ienRhrdB");
   }
 
   #[test]
   fn invalid_hash() {
     let data = 0;
-    let result = hash(data, &DEFAULT_ALPHABET.to_string());
+    let mut result = String::new();
+    hash_into(data, &chars(DEFAULT_ALPHABET), &mut result);
     assert_eq!(result, "a");
   }
 
   #[test]
   fn hash_shuffle() {
-    let shuffled = hashids_shuffle("anything really goes".to_string(), &HashidSalt::from("this is my salt"));
-    assert_eq!(shuffled, Ok(" eagnrlityas oelygnh".to_string()));
+    let mut shuffled = chars("anything really goes");
+    hashids_shuffle(&mut shuffled, &chars("this is my salt"));
+    assert_eq!(shuffled, chars(" eagnrlityas oelygnh"));
+  }
 
+  #[test]
+  fn hash_shuffle_part_of_buffer() {
+    let mut buffer = chars("anything really goes, and more");
+    hashids_shuffle(&mut buffer[..20], &chars("this is my salt"));
+    assert_eq!(buffer, chars(" eagnrlityas oelygnh, and more"));
+  }
+
+  #[test]
+  fn unicode_hash_roundtrip() {
+    let alphabet = chars("あいうえおかきくけこ🦀");
+    let mut hashed = String::new();
+    hash_into(1234567, &alphabet, &mut hashed);
+    let mut positions = Positions::default();
+    positions.rebuild(&alphabet);
+    assert_eq!(unhash(&hashed, &alphabet, &positions), Some(1234567));
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn unhash_unknown_character() {
+    let alphabet = chars("abcdefghijklmnop🦀");
+    let mut positions = Positions::default();
+    positions.rebuild(&alphabet);
+    assert_eq!(unhash("b🦀", &alphabet, &positions), Some(17 + 16));
+    assert_eq!(unhash("bz", &alphabet, &positions), None);
+    assert_eq!(unhash("b🐍", &alphabet, &positions), None);
+  }
+}
@@ -0,0 +1,60 @@
+//! Authentication of hashes with a truncated HMAC-SHA256, for hashes that must not be forged.
+use std::convert::TryInto;
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::Error;
+
+const MIN_TAG_LENGTH: usize = 4;
+const MAX_TAG_LENGTH: usize = 16;
+
+/// The secret key and the amount of characters of the tag appended to hashes.
+#[derive(Clone, PartialEq)]
+pub(crate) struct MacKey {
+  key: Vec<u8>,
+  pub(crate) tag_length: usize
+}
+
+// Keeps the key out of logs
+impl fmt::Debug for MacKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("MacKey").field("key", &"<redacted>").field("tag_length", &self.tag_length).finish()
+  }
+}
+
+impl MacKey {
+  pub(crate) fn new(key: Vec<u8>, tag_length: usize) -> Result<MacKey, Error> {
+    if key.is_empty() || !(MIN_TAG_LENGTH..=MAX_TAG_LENGTH).contains(&tag_length) {
+      return Err(Error::InvalidMacSettings)
+    }
+    Ok(MacKey { key, tag_length })
+  }
+
+  /// The tag of a hash, written with the characters of the alphabet.
+  ///
+  /// The prefix is authenticated along with the hash, so a hash can't be passed off as another entity's.
+  pub(crate) fn tag(&self, prefix: &str, body: &str, alphabet: &[char]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+    mac.update(prefix.as_bytes());
+    mac.update(&[0]);
+    mac.update(body.as_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    // Reduced modulo the alphabet length at each character, the bias is negligible for alphabets far smaller than 2^128
+    let mut number = u128::from_be_bytes(digest[..16].try_into().unwrap());
+    let len = alphabet.len() as u128;
+    (0..self.tag_length).map(|_| {
+      let character = alphabet[(number % len) as usize];
+      number /= len;
+      character
+    }).collect()
+  }
+
+  /// Compares the tag of a hash to the expected one, in constant time.
+  pub(crate) fn verify(&self, prefix: &str, body: &str, tag: &str, alphabet: &[char]) -> bool {
+    let expected = self.tag(prefix, body, alphabet);
+    expected.len() == tag.len() && expected.bytes().zip(tag.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+  }
+}
@@ -0,0 +1,29 @@
+/// Declares a `static` codec, built once on first use and shared by every thread.
+///
+/// The codec owns its shuffled alphabets, so it can't be built by a `const fn`:
+/// this is the next best thing, without threading a codec through every layer or building one per request.
+/// Takes either a salt literal, for the default settings, or a `HashidBuilder` expression.
+/// Panics on first use if the codec can't be built.
+/// ```
+/// use hashids::{hashid_codec, HashidBuilder};
+/// hashid_codec!(static CODEC = "this is my salt";);
+/// hashid_codec!(pub static LONG_CODEC = HashidBuilder::new().with_salt("this is my salt").with_length(8););
+///
+/// assert_eq!(CODEC.encode(12345).unwrap(), "NkK9");
+/// assert_eq!(LONG_CODEC.encode(1).unwrap(), "gB0NV05e");
+/// ```
+#[macro_export]
+macro_rules! hashid_codec {
+  ($(#[$attr:meta])* $vis:vis static $name:ident = $salt:literal;) => {
+    $crate::hashid_codec!($(#[$attr])* $vis static $name = $crate::HashidBuilder::new().with_salt($salt););
+  };
+  ($(#[$attr:meta])* $vis:vis static $name:ident = $builder:expr;) => {
+    $(#[$attr])*
+    $vis static $name: ::std::sync::LazyLock<$crate::HashidCodec> = ::std::sync::LazyLock::new(|| {
+      match $crate::HashidBuilder::build($builder) {
+        ::std::result::Result::Ok(codec) => codec,
+        ::std::result::Result::Err(err) => panic!("the `{}` codec can't be built: {}", stringify!($name), err)
+      }
+    });
+  };
+}
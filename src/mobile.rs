@@ -0,0 +1,67 @@
+//! Swift and Kotlin bindings through uniffi, so mobile apps build the same deep links as the backend.
+//!
+//! Built with `cargo rustc --lib --release --features uniffi --crate-type cdylib` (or `staticlib` for iOS), which needs
+//! Cargo 1.64 or later as the crate only declares a Rust library, the bindings are then generated from the library
+//! with `uniffi-bindgen`:
+//! ```sh
+//! uniffi-bindgen generate --library target/release/libhashids.so --language kotlin --out-dir out
+//! ```
+//! They export a `HashidCodec` class, and errors are thrown as `HashidsException` in Kotlin and `HashidsError` in Swift:
+//! ```kotlin
+//! val codec = HashidCodec("this is my salt", null, null)
+//! codec.encode(12345UL) // "NkK9"
+//! codec.decode("NkK9") // [12345UL]
+//! ```
+//! Requires the `uniffi` feature.
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Error, HashidBuilder};
+
+/// A codec for Swift and Kotlin, exported as `HashidCodec`.
+#[derive(uniffi::Object)]
+pub struct HashidCodec {
+  codec: crate::HashidCodec
+}
+
+#[uniffi::export]
+impl HashidCodec {
+  /// Builds a codec, with the default alphabet and minimum length when they aren't given.
+  #[uniffi::constructor(default(alphabet = None, min_length = None))]
+  pub fn new(salt: String, alphabet: Option<String>, min_length: Option<u32>) -> Result<Arc<Self>, HashidsError> {
+    let mut builder = HashidBuilder::new().with_string_salt(salt);
+    if let Some(alphabet) = alphabet { builder = builder.with_alphabet(alphabet) }
+    if let Some(min_length) = min_length { builder = builder.with_length(min_length as usize) }
+    Ok(Arc::new(HashidCodec { codec: builder.build().map_err(HashidsError::InvalidSettings)? }))
+  }
+
+  /// Encodes an ID.
+  pub fn encode(&self, id: u64) -> Result<String, HashidsError> {
+    self.codec.encode(id).map_err(HashidsError::InvalidId)
+  }
+
+  /// Decodes a hash to its numbers.
+  pub fn decode(&self, hash: &str) -> Result<Vec<u64>, HashidsError> {
+    self.codec.decode_as::<u64>(hash).map_err(HashidsError::InvalidHash)
+  }
+}
+
+/// Why a call failed, the message being the one of the `Error`.
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum HashidsError {
+  /// The settings of the codec are refused.
+  InvalidSettings(Error),
+  /// The ID can't be encoded.
+  InvalidId(Error),
+  /// The hash doesn't decode.
+  InvalidHash(Error)
+}
+
+impl fmt::Display for HashidsError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      HashidsError::InvalidSettings(err) | HashidsError::InvalidId(err) | HashidsError::InvalidHash(err) => err.fmt(f)
+    }
+  }
+}
@@ -0,0 +1,79 @@
+//! Callbacks counting what codecs do, for metrics.
+use std::fmt;
+use std::sync::Arc;
+
+use crate::Error;
+
+/// Callbacks of a codec's activity, to count it in a metrics system, set with `HashidBuilder::with_metrics`.
+///
+/// Every method does nothing by default. They are called while encoding and decoding, so they should be cheap,
+/// such as incrementing atomic counters. The `metrics` feature provides `HashidCounters`, for the `metrics` crate.
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use hashids::{Error, HashidBuilder, HashidMetrics};
+///
+/// #[derive(Default)]
+/// struct InvalidHashes(AtomicUsize);
+///
+/// impl HashidMetrics for InvalidHashes {
+///   fn decode_failed(&self, _error: &Error) {
+///     self.0.fetch_add(1, Ordering::Relaxed);
+///   }
+/// }
+///
+/// let invalid_hashes = Arc::new(InvalidHashes::default());
+/// let codec = HashidBuilder::new().with_salt("this is my salt").with_metrics(invalid_hashes.clone()).build().unwrap();
+/// assert!(codec.decode("NkK8").is_err());
+/// assert_eq!(invalid_hashes.0.load(Ordering::Relaxed), 1);
+/// ```
+pub trait HashidMetrics: Send + Sync {
+  /// A hash was encoded.
+  fn encoded(&self) {}
+  /// A hash was decoded.
+  fn decoded(&self) {}
+  /// A hash failed to decode, `Error::is_invalid_hash` telling apart the hashes that aren't valid for the codec.
+  fn decode_failed(&self, _error: &Error) {}
+  /// A `CachedCodec` found the hash or ID in its cache, instead of encoding or decoding it.
+  fn cache_hit(&self) {}
+  /// A `CachedCodec` didn't find the hash or ID in its cache.
+  fn cache_miss(&self) {}
+}
+
+/// The metrics of a codec, if any.
+#[derive(Clone, Default)]
+pub(crate) struct Observer(Option<Arc<dyn HashidMetrics>>);
+
+impl Observer {
+  pub(crate) fn new(metrics: Arc<dyn HashidMetrics>) -> Observer {
+    Observer(Some(metrics))
+  }
+
+  pub(crate) fn encoded(&self) {
+    if let Some(metrics) = &self.0 { metrics.encoded() }
+  }
+
+  pub(crate) fn decoded(&self) {
+    if let Some(metrics) = &self.0 { metrics.decoded() }
+  }
+
+  pub(crate) fn decode_failed(&self, error: &Error) {
+    if let Some(metrics) = &self.0 { metrics.decode_failed(error) }
+  }
+
+  #[cfg(feature = "cache")]
+  pub(crate) fn cache_hit(&self) {
+    if let Some(metrics) = &self.0 { metrics.cache_hit() }
+  }
+
+  #[cfg(feature = "cache")]
+  pub(crate) fn cache_miss(&self) {
+    if let Some(metrics) = &self.0 { metrics.cache_miss() }
+  }
+}
+
+impl fmt::Debug for Observer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(if self.0.is_some() { "Some(HashidMetrics)" } else { "None" })
+  }
+}
@@ -0,0 +1,54 @@
+//! Regular expressions matching the hashes of a codec, to validate them outside of Rust, in schemas or front-ends.
+use crate::HashidCodec;
+
+impl HashidCodec {
+  /// A regular expression matching the hashes of this codec, with its prefix, suffix and length limits,
+  /// in the syntax common to JSON Schema, JavaScript and the `regex` crate.
+  ///
+  /// Only the characters and length are checked, so a string matching it may still not decode.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").with_alphabet("0123456789abcdef".to_string())
+  ///   .with_prefix("usr_").with_max_length(12).build().unwrap();
+  /// assert_eq!(codec.hash_pattern(), "^usr_[0123456789abcdef]{4,12}$");
+  /// ```
+  pub fn hash_pattern(&self) -> String {
+    let mut symbols: Vec<char> = self.alphabet.iter().chain(&self.separators).chain(&self.guards).copied().collect();
+    symbols.sort_unstable();
+    symbols.dedup();
+
+    // Bodies hold at least a lottery character and a digit, the tag has a fixed length
+    #[allow(unused_mut)]
+    let (mut min, mut max) = (self.min_hash_length.max(2), self.max_hash_length);
+    #[cfg(feature = "mac")]
+    if let Some(mac) = &self.mac {
+      min += mac.tag_length;
+      max = max.map(|max| max + mac.tag_length);
+    }
+
+    let mut pattern = String::from("^");
+    push_escaped(&mut pattern, &self.prefix, false);
+    pattern.push('[');
+    for symbol in symbols {
+      push_escaped(&mut pattern, symbol.encode_utf8(&mut [0; 4]), true);
+    }
+    pattern.push(']');
+    match max {
+      Some(max) => pattern.push_str(&format!("{{{},{}}}", min, max)),
+      None => pattern.push_str(&format!("{{{},}}", min))
+    }
+    push_escaped(&mut pattern, &self.suffix, false);
+    pattern.push('$');
+    pattern
+  }
+}
+
+fn push_escaped(pattern: &mut String, s: &str, in_class: bool) {
+  for c in s.chars() {
+    let special = if in_class { "\\]^-[" } else { "\\^$.|?*+()[]{}/" };
+    if special.contains(c) {
+      pattern.push('\\');
+    }
+    pattern.push(c);
+  }
+}
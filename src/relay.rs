@@ -0,0 +1,100 @@
+//! Relay-style global object identifiers, unique across all the types of a GraphQL schema.
+use crate::{Error, HashidCodec};
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The type name and ID held by a global ID, see `HashidCodec::encode_global_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GlobalId {
+  pub type_name: String,
+  pub id: u64
+}
+
+impl GlobalId {
+  pub fn new(type_name: impl Into<String>, id: u64) -> GlobalId {
+    GlobalId { type_name: type_name.into(), id }
+  }
+}
+
+impl HashidCodec {
+  /// Converts a type name and an ID to an opaque global ID, the base64 of `"Type:hash"`, as used by Relay for node IDs and cursors.
+  ///
+  /// The type name is only base64 encoded, while the ID is hashed, so decoding checks it was made with this codec.
+  /// Returns an `Error::InvalidGlobalId` if the type name is empty or contains a `:`.
+  /// ```
+  /// use hashids::{HashidBuilder, GlobalId};
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let global_id = codec.encode_global_id("User", 12345).unwrap();
+  /// assert_eq!(global_id, "VXNlcjpOa0s5");
+  /// assert_eq!(codec.decode_global_id(&global_id), Ok(GlobalId::new("User", 12345)));
+  /// assert_eq!(codec.decode_global_id_of("User", &global_id), Ok(12345));
+  /// ```
+  pub fn encode_global_id(&self, type_name: &str, id: u64) -> Result<String, Error> {
+    if type_name.is_empty() || type_name.contains(':') {
+      return Err(Error::InvalidGlobalId)
+    }
+    let mut plain = format!("{}:", type_name);
+    self.encode_into(id, &mut plain)?;
+    Ok(base64_encode(plain.as_bytes()))
+  }
+
+  /// Converts a global ID made by `encode_global_id` back to its type name and ID.
+  ///
+  /// Returns an `Error::InvalidGlobalId` if it isn't a base64 type name and hash, or the hash's error if it doesn't decode.
+  pub fn decode_global_id(&self, global_id: &str) -> Result<GlobalId, Error> {
+    let plain = base64_decode(global_id).and_then(|bytes| String::from_utf8(bytes).ok()).ok_or(Error::InvalidGlobalId)?;
+    match plain.split_once(':') {
+      Some((type_name, hash)) if !type_name.is_empty() => Ok(GlobalId::new(type_name, self.decode_single(hash)?)),
+      _ => Err(Error::InvalidGlobalId)
+    }
+  }
+
+  /// Same as `decode_global_id`, but returns an `Error::InvalidGlobalId` if the global ID isn't of the given type,
+  /// so the ID of a node of another type isn't mistaken for one of this type.
+  pub fn decode_global_id_of(&self, type_name: &str, global_id: &str) -> Result<u64, Error> {
+    let global_id = self.decode_global_id(global_id)?;
+    if global_id.type_name != type_name {
+      return Err(Error::InvalidGlobalId)
+    }
+    Ok(global_id.id)
+  }
+}
+
+/// Standard base64 with padding, as Relay's reference implementation.
+fn base64_encode(bytes: &[u8]) -> String {
+  let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+  for chunk in bytes.chunks(3) {
+    let block = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+    for i in 0..4 {
+      if i <= chunk.len() {
+        out.push(BASE64_ALPHABET[(block >> (18 - 6 * i) & 63) as usize] as char);
+      } else {
+        out.push('=');
+      }
+    }
+  }
+  out
+}
+
+fn base64_decode(input: &str) -> Option<Vec<u8>> {
+  let input = input.as_bytes();
+  if input.is_empty() || input.len() % 4 != 0 {
+    return None
+  }
+  let mut out = Vec::with_capacity(input.len() / 4 * 3);
+  for (n, chunk) in input.chunks(4).enumerate() {
+    let last = n == input.len() / 4 - 1;
+    let padding = chunk.iter().rev().take_while(|&&byte| byte == b'=').count();
+    if padding > 2 || (padding > 0 && !last) {
+      return None
+    }
+    let mut block = 0u32;
+    for &byte in &chunk[..4 - padding] {
+      let value = BASE64_ALPHABET.iter().position(|&symbol| symbol == byte)?;
+      block = block << 6 | value as u32;
+    }
+    block <<= 6 * padding;
+    out.extend_from_slice(&block.to_be_bytes()[1..4 - padding]);
+  }
+  Some(out)
+}
@@ -0,0 +1,112 @@
+//! Hashes of 64 bits snowflake IDs, encoded as their timestamp, worker and sequence parts.
+//!
+//! Encoding the parts separately keeps hashes short for the small worker and sequence numbers,
+//! and lets the parts be read back from the hash alone.
+//! ```
+//! use hashids::HashidBuilder;
+//! use hashids::snowflake::{Snowflake, SnowflakeLayout};
+//! let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+//!
+//! let id = 1_541_815_603_606_036_480u64;
+//! let hash = codec.encode_snowflake(id, SnowflakeLayout::TWITTER).unwrap();
+//! assert_eq!(codec.decode_snowflake(&hash, SnowflakeLayout::TWITTER), Ok(id));
+//!
+//! let parts = SnowflakeLayout::TWITTER.split(id);
+//! assert_eq!(parts, Snowflake { timestamp: 367597485448, worker: 378, sequence: 0 });
+//! ```
+use std::convert::TryFrom;
+
+use crate::{Error, HashidCodec};
+
+/// How many bits of the ID each part takes, from the most significant: timestamp, worker, then sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowflakeLayout {
+  timestamp_bits: u32,
+  worker_bits: u32,
+  sequence_bits: u32
+}
+
+/// The parts of a snowflake ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Snowflake {
+  pub timestamp: u64,
+  pub worker: u64,
+  pub sequence: u64
+}
+
+impl SnowflakeLayout {
+  /// Twitter's layout: 41 bits of milliseconds, 10 bits of worker, 12 bits of sequence.
+  pub const TWITTER: SnowflakeLayout = SnowflakeLayout { timestamp_bits: 41, worker_bits: 10, sequence_bits: 12 };
+  /// Discord's layout: 42 bits of milliseconds, 10 bits of worker and process, 12 bits of sequence.
+  pub const DISCORD: SnowflakeLayout = SnowflakeLayout { timestamp_bits: 42, worker_bits: 10, sequence_bits: 12 };
+
+  /// A custom layout, `None` if the parts don't fit in 64 bits.
+  pub const fn new(timestamp_bits: u32, worker_bits: u32, sequence_bits: u32) -> Option<SnowflakeLayout> {
+    match timestamp_bits.checked_add(worker_bits) {
+      Some(bits) => match bits.checked_add(sequence_bits) {
+        Some(bits) if bits <= 64 => Some(SnowflakeLayout { timestamp_bits, worker_bits, sequence_bits }),
+        _ => None
+      },
+      None => None
+    }
+  }
+
+  /// Splits an ID into its parts. Bits above the timestamp are ignored.
+  pub fn split(self, id: u64) -> Snowflake {
+    Snowflake {
+      timestamp: shr(id, self.worker_bits + self.sequence_bits) & mask(self.timestamp_bits),
+      worker: shr(id, self.sequence_bits) & mask(self.worker_bits),
+      sequence: id & mask(self.sequence_bits)
+    }
+  }
+
+  /// Puts the parts back together, `None` if a part doesn't fit in its bits.
+  pub fn join(self, parts: Snowflake) -> Option<u64> {
+    if parts.timestamp > mask(self.timestamp_bits) || parts.worker > mask(self.worker_bits) || parts.sequence > mask(self.sequence_bits) {
+      return None
+    }
+    Some(shl(parts.timestamp, self.worker_bits + self.sequence_bits) | shl(parts.worker, self.sequence_bits) | parts.sequence)
+  }
+}
+
+fn mask(bits: u32) -> u64 {
+  if bits >= 64 { u64::MAX } else { (1 << bits) - 1 }
+}
+
+// Parts above 64 bits of shift are empty in layouts filling the 64 bits, shifting them out entirely gives 0
+fn shl(part: u64, bits: u32) -> u64 {
+  part.checked_shl(bits).unwrap_or(0)
+}
+
+fn shr(id: u64, bits: u32) -> u64 {
+  id.checked_shr(bits).unwrap_or(0)
+}
+
+impl HashidCodec {
+  /// Converts a snowflake ID to a Hashid String, encoding its timestamp, worker and sequence as three numbers.
+  ///
+  /// Returns an `Error::InvalidInputId` if the ID has bits set above the timestamp, which would be lost.
+  pub fn encode_snowflake(&self, id: u64, layout: SnowflakeLayout) -> Result<String, Error> {
+    let parts = layout.split(id);
+    if layout.join(parts) != Some(id) {
+      return Err(Error::InvalidInputId)
+    }
+    self.encode_vec(&[parts.timestamp as u128, parts.worker as u128, parts.sequence as u128])
+  }
+
+  /// Converts a hash made by `encode_snowflake` back to the snowflake ID.
+  ///
+  /// Returns an `Error::UnexpectedNumberCount` if the hash isn't made of three numbers,
+  /// or an `Error::NumberOverflow` if they don't fit the layout.
+  pub fn decode_snowflake(&self, hash: &str, layout: SnowflakeLayout) -> Result<u64, Error> {
+    let numbers = self.decode_numbers(hash)?;
+    match numbers.as_slice() {
+      [timestamp, worker, sequence] => {
+        let part = |number: u128| u64::try_from(number).map_err(|_| Error::NumberOverflow);
+        let parts = Snowflake { timestamp: part(*timestamp)?, worker: part(*worker)?, sequence: part(*sequence)? };
+        layout.join(parts).ok_or(Error::NumberOverflow)
+      },
+      _ => Err(Error::UnexpectedNumberCount { expected: 3, got: numbers.len() })
+    }
+  }
+}
@@ -0,0 +1,51 @@
+//! [proptest](https://docs.rs/proptest) strategies, to property test code that uses hashids.
+//!
+//! Requires the `test-support` feature.
+//! ```
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//! use hashids::test_support::{arb_codec, arb_id};
+//!
+//! TestRunner::default().run(&(arb_codec(), arb_id()), |(codec, id)| {
+//!   let hash = codec.encode(id).unwrap();
+//!   prop_assert_eq!(codec.decode_single(&hash), Ok(id));
+//!   Ok(())
+//! }).unwrap();
+//! ```
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+
+use crate::alphabet::Preset;
+use crate::{HashidBuilder, HashidCodec};
+
+/// IDs that `HashidCodec::encode` accepts as `u64`: up to, but excluding, `i64::MAX`.
+pub fn arb_id() -> impl Strategy<Value = u64> {
+  0..i64::MAX as u64
+}
+
+/// IDs that `HashidCodec::encode` accepts as `u128`, which is any of them unless the codec has an FPE key.
+pub fn arb_id_u128() -> impl Strategy<Value = u128> {
+  any::<u128>()
+}
+
+/// Salts of 1 to 32 printable ASCII characters.
+pub fn arb_salt() -> impl Strategy<Value = String> {
+  "[ -~]{1,32}"
+}
+
+/// Alphabets of the presets, or of 16 to 64 printable ASCII characters.
+pub fn arb_alphabet() -> impl Strategy<Value = String> {
+  let printable: Vec<char> = ('!'..='~').collect();
+  prop_oneof![
+    select(&Preset::ALL[..]).prop_map(|preset| preset.alphabet().to_string()),
+    subsequence(printable, 16..=64).prop_map(|alphabet| alphabet.into_iter().collect())
+  ]
+}
+
+/// Codecs with any salt and alphabet, and a minimum length of 0 to 32.
+pub fn arb_codec() -> impl Strategy<Value = HashidCodec> {
+  (arb_salt(), arb_alphabet(), 0..=32usize).prop_map(|(salt, alphabet, length)| {
+    HashidBuilder::new().with_salt(&salt).with_alphabet(alphabet).with_length(length).build()
+      .expect("generated settings are valid")
+  })
+}
@@ -0,0 +1,73 @@
+//! Test vectors from the reference hashids implementation, to check interoperability.
+//!
+//! The vectors were generated with hashids.js, and cover the official examples, custom alphabets
+//! (including regex-sensitive characters), minimum lengths and several numbers per hash.
+//! Downstream crates wrapping the codec can run them against their own configuration code with `check`.
+use serde::Deserialize;
+
+use crate::compat::Compat;
+use crate::{Error, HashidBuilder, HashidCodec};
+
+const VECTORS_JSON: &str = include_str!("test_vectors.json");
+
+/// A configuration, the numbers to encode, and the hash the reference implementation produces for them.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TestVector {
+  pub salt: String,
+  pub alphabet: String,
+  pub min_length: usize,
+  pub numbers: Vec<u64>,
+  pub hash: String
+}
+
+impl TestVector {
+  /// Builds a codec with this vector's configuration, in `Compat::Reference` mode.
+  pub fn codec(&self) -> Result<HashidCodec, Error> {
+    HashidBuilder::new()
+      .with_salt(&self.salt)
+      .with_alphabet(self.alphabet.clone())
+      .with_length(self.min_length)
+      .with_compat(Compat::Reference)
+      .build()
+  }
+}
+
+/// A vector the codec didn't agree with, along with what the codec produced instead.
+#[derive(Debug, PartialEq)]
+pub struct Failure {
+  pub vector: TestVector,
+  pub encoded: Result<String, Error>,
+  pub decoded: Result<Vec<u64>, Error>
+}
+
+/// All the embedded test vectors.
+pub fn vectors() -> Vec<TestVector> {
+  serde_json::from_str(VECTORS_JSON).expect("the embedded test vectors are valid JSON")
+}
+
+/// Encodes and decodes a vector with the given codec, which should be configured like `TestVector::codec`.
+pub fn check(codec: &HashidCodec, vector: &TestVector) -> Result<(), Box<Failure>> {
+  let numbers: Vec<u128> = vector.numbers.iter().map(|number| *number as u128).collect();
+  let encoded = codec.encode_vec(&numbers);
+  let decoded = codec.decode_numbers(&vector.hash)
+    .map(|numbers| numbers.into_iter().map(|number| number as u64).collect());
+
+  if encoded.as_ref() == Ok(&vector.hash) && decoded.as_ref() == Ok(&vector.numbers) {
+    Ok(())
+  } else {
+    Err(Box::new(Failure { vector: vector.clone(), encoded, decoded }))
+  }
+}
+
+/// Runs every embedded vector, with codecs built by `TestVector::codec`, and returns the ones that failed.
+/// ```
+/// assert_eq!(hashids::test_vectors::run(), vec![]);
+/// ```
+pub fn run() -> Vec<Failure> {
+  vectors().iter()
+    .filter_map(|vector| match vector.codec() {
+      Ok(codec) => check(&codec, vector).err().map(|failure| *failure),
+      Err(err) => Some(Failure { vector: vector.clone(), encoded: Err(err.clone()), decoded: Err(err) })
+    })
+    .collect()
+}
@@ -0,0 +1,43 @@
+//! Hashes carrying their creation time, to expire links without looking them up.
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime};
+
+use crate::{Error, HashidCodec, PositiveInteger};
+
+impl HashidCodec {
+  /// Converts an ID to a Hashid String along with a timestamp, stored as whole seconds since the codec's epoch.
+  ///
+  /// The epoch is the Unix epoch unless set with `HashidBuilder::with_timestamp_epoch`, a recent epoch makes shorter hashes.
+  /// Returns an `Error::InvalidTimestamp` if the timestamp is before the epoch.
+  /// ```
+  /// use std::time::{Duration, SystemTime};
+  /// use hashids::HashidBuilder;
+  /// let epoch = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").with_timestamp_epoch(epoch).build().unwrap();
+  ///
+  /// let created = epoch + Duration::from_secs(86_400);
+  /// let hash = codec.encode_with_timestamp(12345, created).unwrap();
+  /// assert_eq!(codec.decode_with_timestamp(&hash), Ok((12345, created)));
+  /// ```
+  pub fn encode_with_timestamp<T: PositiveInteger>(&self, id: T, timestamp: SystemTime) -> Result<String, Error> {
+    let seconds = timestamp.duration_since(self.timestamp_epoch).map_err(|_| Error::InvalidTimestamp)?.as_secs();
+    let numbers = [self.id_to_number(id)?, seconds as u128];
+    self.encode_vec(&numbers)
+  }
+
+  /// Converts a hash made by `encode_with_timestamp` back to the ID and the timestamp, rounded down to the second.
+  ///
+  /// Returns an `Error::UnexpectedNumberCount` if the hash doesn't hold an ID and a timestamp.
+  pub fn decode_with_timestamp(&self, hash: &str) -> Result<(u64, SystemTime), Error> {
+    let numbers = self.decode_numbers(hash)?;
+    match numbers.as_slice() {
+      [id, seconds] => {
+        let id = self.number_to_id(*id)?;
+        let seconds = u64::try_from(*seconds).map_err(|_| Error::InvalidTimestamp)?;
+        let timestamp = self.timestamp_epoch.checked_add(Duration::from_secs(seconds)).ok_or(Error::InvalidTimestamp)?;
+        Ok((id, timestamp))
+      },
+      _ => Err(Error::UnexpectedNumberCount { expected: 2, got: numbers.len() })
+    }
+  }
+}
@@ -0,0 +1,40 @@
+//! Spans and events of the `tracing` feature, which do nothing without it.
+//!
+//! The salt is never part of them, nor the hashes, which can be long and come from anyone.
+use crate::{Error, HashidCodec};
+
+/// A span entered while the builder makes a codec, so its events tell which construction they come from.
+pub(crate) struct BuildSpan {
+  #[cfg(feature = "tracing")]
+  _entered: tracing::span::EnteredSpan
+}
+
+pub(crate) fn build_span() -> BuildSpan {
+  BuildSpan {
+    #[cfg(feature = "tracing")]
+    _entered: tracing::debug_span!("hashids_build").entered()
+  }
+}
+
+pub(crate) fn salt_from_env(key: &str) {
+  #[cfg(feature = "tracing")]
+  tracing::debug!(variable = key, "salt read from the environnment");
+  let _ = key;
+}
+
+pub(crate) fn built(result: &Result<HashidCodec, Error>) {
+  #[cfg(feature = "tracing")]
+  match result {
+    Ok(codec) => tracing::debug!(alphabet_length = codec.alphabet.len(), min_length = codec.min_hash_length,
+      prefix = %codec.prefix, suffix = %codec.suffix, salted = !codec.salt.is_empty(), "codec built"),
+    Err(err) => tracing::warn!(error = %err, code = err.code(), "codec settings refused")
+  }
+  let _ = result;
+}
+
+/// A hash that doesn't decode, reported at the debug level as invalid hashes are usually the client's doing.
+pub(crate) fn decode_failed(err: &Error, hash: &str) {
+  #[cfg(feature = "tracing")]
+  tracing::debug!(error = %err, code = err.code(), hash_length = hash.len(), "hash failed to decode");
+  let _ = (err, hash);
+}
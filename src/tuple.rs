@@ -0,0 +1,107 @@
+//! Strongly-typed encoding of several numbers in a single hash, for composite identifiers.
+use crate::{Error, FromHashidNumber, HashidCodec, PositiveInteger};
+
+/// Tuples of 2 to 4 integers that can be encoded in, and decoded from, a single hash.
+///
+/// Each element can be any integer that is both a `PositiveInteger` and a `FromHashidNumber`,
+/// see `HashidCodec::encode_tuple` and `HashidCodec::decode_tuple`.
+pub trait HashidTuple: Sized {
+  #[doc(hidden)]
+  fn encode_tuple_into(self, codec: &HashidCodec, out: &mut String) -> Result<(), Error>;
+
+  #[doc(hidden)]
+  fn decode_tuple_from(codec: &HashidCodec, hash: &str) -> Result<Self, Error>;
+}
+
+macro_rules! impl_hashid_tuple {
+  ($($name:ident),+) => {
+    impl<$($name: PositiveInteger + FromHashidNumber),+> HashidTuple for ($($name,)+) {
+      #[allow(non_snake_case)]
+      fn encode_tuple_into(self, codec: &HashidCodec, out: &mut String) -> Result<(), Error> {
+        let ($($name,)+) = self;
+        let numbers = [$(codec.id_to_number($name)?),+];
+        codec.encode_vec_into(&numbers, out)
+      }
+
+      #[allow(non_snake_case)]
+      fn decode_tuple_from(codec: &HashidCodec, hash: &str) -> Result<Self, Error> {
+        let numbers = codec.decode_numbers(hash)?;
+        match numbers.as_slice() {
+          [$($name),+] => Ok(($(codec.number_to_id::<$name>(*$name)?,)+)),
+          _ => Err(Error::UnexpectedNumberCount { expected: [$(stringify!($name)),+].len(), got: numbers.len() })
+        }
+      }
+    }
+  };
+}
+
+impl_hashid_tuple!(A, B);
+impl_hashid_tuple!(A, B, C);
+impl_hashid_tuple!(A, B, C, D);
+
+/// An identifier scoped to a tenant, such as the primary key of a sharded table, encoded as two numbers.
+/// ```
+/// use hashids::{HashidBuilder, CompositeId};
+/// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+/// let hash = codec.encode_composite(CompositeId { tenant: 42, id: 1337 }).unwrap();
+/// assert_eq!(hash, "KgUO9");
+/// assert_eq!(codec.decode_composite(&hash), Ok(CompositeId { tenant: 42, id: 1337 }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CompositeId {
+  pub tenant: u64,
+  pub id: u64
+}
+
+impl From<(u64, u64)> for CompositeId {
+  fn from((tenant, id): (u64, u64)) -> CompositeId {
+    CompositeId { tenant, id }
+  }
+}
+
+impl From<CompositeId> for (u64, u64) {
+  fn from(composite: CompositeId) -> (u64, u64) {
+    (composite.tenant, composite.id)
+  }
+}
+
+impl HashidCodec {
+  /// Converts a tuple of 2 to 4 integers to a single Hashid String.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let hash = codec.encode_tuple((683u64, 94108u64, 123u64, 5u64)).unwrap();
+  /// assert_eq!(hash, "aBMswoO2UB3Sj");
+  /// ```
+  pub fn encode_tuple<T: HashidTuple>(&self, tuple: T) -> Result<String, Error> {
+    let mut hash = String::new();
+    tuple.encode_tuple_into(self, &mut hash)?;
+    Ok(hash)
+  }
+
+  /// Converts a hash made by `encode_tuple` back to the tuple of integers.
+  ///
+  /// Returns an `Error::UnexpectedNumberCount` if the hash doesn't hold as many numbers as the tuple has elements,
+  /// or an `Error::NumberOverflow` if one of them doesn't fit its element type.
+  /// ```
+  /// use hashids::HashidBuilder;
+  /// let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  /// let (tenant, id) = codec.decode_tuple::<(u32, i64)>("KgUO9").unwrap();
+  /// assert_eq!((tenant, id), (42, 1337));
+  /// ```
+  pub fn decode_tuple<T: HashidTuple>(&self, hash: &str) -> Result<T, Error> {
+    T::decode_tuple_from(self, hash)
+  }
+
+  /// Converts a `CompositeId` to a Hashid String, the same as `encode_tuple((tenant, id))`.
+  pub fn encode_composite(&self, composite: CompositeId) -> Result<String, Error> {
+    self.encode_tuple(<(u64, u64)>::from(composite))
+  }
+
+  /// Converts a hash made by `encode_composite` back to the `CompositeId`.
+  ///
+  /// Returns an `Error::UnexpectedNumberCount` if the hash doesn't hold exactly two numbers.
+  pub fn decode_composite(&self, hash: &str) -> Result<CompositeId, Error> {
+    self.decode_tuple::<(u64, u64)>(hash).map(CompositeId::from)
+  }
+}
@@ -0,0 +1,58 @@
+//! JavaScript bindings, so frontends and workers produce the same hashes as the backend.
+//!
+//! Built with `cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib`,
+//! then `wasm-bindgen`, the module exports a `HashidCodec` class:
+//! ```js
+//! import { HashidCodec } from "hashids";
+//! const codec = new HashidCodec("this is my salt");
+//! codec.encode(12345); // "NkK9"
+//! codec.decode("NkK9"); // Float64Array [12345]
+//! ```
+//! Requires the `wasm` feature.
+use wasm_bindgen::prelude::*;
+
+use crate::{Error, HashidBuilder};
+
+/// Largest integer JavaScript numbers hold exactly, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// A codec for JavaScript, exported as `HashidCodec`.
+///
+/// IDs are JavaScript numbers, so they are limited to `Number.MAX_SAFE_INTEGER`. Errors are thrown with the message
+/// of the `Error`.
+#[wasm_bindgen(js_name = HashidCodec)]
+pub struct JsHashidCodec {
+  codec: crate::HashidCodec
+}
+
+#[wasm_bindgen(js_class = HashidCodec)]
+impl JsHashidCodec {
+  /// Builds a codec, with the default alphabet and minimum length when they aren't given.
+  #[wasm_bindgen(constructor)]
+  pub fn new(salt: &str, alphabet: Option<String>, min_length: Option<usize>) -> Result<JsHashidCodec, JsError> {
+    let mut builder = HashidBuilder::new().with_salt(salt);
+    if let Some(alphabet) = alphabet { builder = builder.with_alphabet(alphabet) }
+    if let Some(min_length) = min_length { builder = builder.with_length(min_length) }
+    Ok(JsHashidCodec { codec: builder.build().map_err(js_error)? })
+  }
+
+  /// Encodes an ID, which must be a positive integer up to `Number.MAX_SAFE_INTEGER`.
+  pub fn encode(&self, id: f64) -> Result<String, JsError> {
+    if id.fract() != 0.0 || !(0.0..=MAX_SAFE_INTEGER as f64).contains(&id) {
+      return Err(js_error(Error::InvalidInputId))
+    }
+    // As a u128, which never goes through the 32 bits usize of wasm32
+    self.codec.encode(id as u128).map_err(js_error)
+  }
+
+  /// Decodes a hash to its numbers, refusing the ones above `Number.MAX_SAFE_INTEGER`.
+  pub fn decode(&self, hash: &str) -> Result<Vec<f64>, JsError> {
+    self.codec.decode_u128(hash).map_err(js_error)?.into_iter()
+      .map(|number| if number <= MAX_SAFE_INTEGER as u128 { Ok(number as f64) } else { Err(js_error(Error::NumberOverflow)) })
+      .collect()
+  }
+}
+
+fn js_error(err: Error) -> JsError {
+  JsError::new(&err.to_string())
+}
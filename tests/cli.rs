@@ -0,0 +1,308 @@
+#![cfg(feature = "cli")]
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use hashids::HashidBuilder;
+
+/// Runs the `hashids` binary, with the salt given through the environnment.
+fn hashids(args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_hashids")).args(args).env("HASHID_SALT", "this is my salt").output().unwrap()
+}
+
+/// Runs the `hashids` binary with `input` written to its standard input.
+fn hashids_with_input(args: &[&str], input: &str) -> Output {
+  let mut child = Command::new(env!("CARGO_BIN_EXE_hashids")).args(args).env("HASHID_SALT", "this is my salt")
+    .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn().unwrap();
+  child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+  child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+  String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn encode_decode() {
+  let output = hashids(&["encode", "12345", "5"]);
+  assert!(output.status.success());
+  assert_eq!(stdout(&output), "NkK9\n0rDd\n");
+
+  let output = hashids(&["decode", "NkK9", "KgUO9"]);
+  assert!(output.status.success());
+  assert_eq!(stdout(&output), "12345\n42,1337\n");
+
+  let output = hashids_with_input(&["--salt", "-", "--min-length", "8", "encode", "12345"], "another salt\n");
+  assert_ne!(stdout(&output), "NkK9\n");
+  assert_eq!(stdout(&output).trim().len(), 8);
+}
+
+#[test]
+fn decode_reports_bad_hashes() {
+  let output = hashids(&["decode", "NkK-", "NkK9"]);
+  assert!(!output.status.success());
+  assert_eq!(stdout(&output), "12345\n");
+  assert!(String::from_utf8_lossy(&output.stderr).contains("NkK-: invalid character '-'"));
+}
+
+#[test]
+fn config_show() {
+  let output = hashids(&["--prefix", "usr_", "config", "show"]);
+  let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  assert_eq!(config["salt"], "<redacted>");
+  assert_eq!(config["prefix"], "usr_");
+
+  let output = hashids(&["config", "show", "--show-salt"]);
+  let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  assert_eq!(config["salt"], "this is my salt");
+}
+
+#[test]
+fn csv_columns() {
+  let dir = std::env::temp_dir().join(format!("hashids-cli-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let input = dir.join("users.csv");
+  std::fs::write(&input, "id,name\n12345,\"Smith, J\"\n5,Bob\n").unwrap();
+  let input = input.to_str().unwrap();
+
+  let output = hashids(&["encode", "--csv", input, "--column", "id"]);
+  assert_eq!(stdout(&output), "id,name\nNkK9,\"Smith, J\"\n0rDd,Bob\n");
+
+  let encoded = dir.join("encoded.csv");
+  let encoded = encoded.to_str().unwrap();
+  let output = hashids(&["encode", "--csv", input, "--column", "id", "--append", "hash", "--out", encoded]);
+  assert!(output.status.success());
+  assert_eq!(std::fs::read_to_string(encoded).unwrap(), "id,name,hash\n12345,\"Smith, J\",NkK9\n5,Bob,0rDd\n");
+
+  let output = hashids(&["decode", "--csv", encoded, "--column", "hash"]);
+  assert_eq!(stdout(&output), "id,name,hash\n12345,\"Smith, J\",12345\n5,Bob,5\n");
+
+  let output = hashids(&["encode", "--csv", input, "--column", "name"]);
+  assert!(!output.status.success());
+  assert!(String::from_utf8_lossy(&output.stderr).contains("line 2: `Smith, J` is not a valid ID"));
+  assert!(!hashids(&["encode", "--csv", input, "--column", "missing"]).status.success());
+  std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn json_lines() {
+  let input = "{\"id\":12345,\"name\":\"a\"}\n\n{\"name\":\"b\",\"id\":\"5\"}\n{\"id\":null}\n";
+  let output = hashids_with_input(&["encode", "--jsonl", "--field", "id"], input);
+  assert!(output.status.success());
+  let encoded = stdout(&output);
+  assert_eq!(encoded, "{\"id\":\"NkK9\",\"name\":\"a\"}\n{\"name\":\"b\",\"id\":\"0rDd\"}\n{\"id\":null}\n");
+
+  let output = hashids_with_input(&["decode", "--jsonl", "--field", "id"], &encoded);
+  assert_eq!(stdout(&output), "{\"id\":12345,\"name\":\"a\"}\n{\"name\":\"b\",\"id\":5}\n{\"id\":null}\n");
+  let output = hashids_with_input(&["decode", "--jsonl", "--field", "id"], "{\"id\":\"KgUO9\"}");
+  assert_eq!(stdout(&output), "{\"id\":[42,1337]}\n");
+
+  let output = hashids_with_input(&["encode", "--jsonl", "--field", "id"], "{\"id\":1}\n{\"id\":-1}\n");
+  assert!(!output.status.success());
+  assert!(String::from_utf8_lossy(&output.stderr).contains("line 2: `-1` is not a valid ID"));
+  assert!(!hashids(&["encode", "--field", "id", "1"]).status.success());
+}
+
+#[test]
+fn repl() {
+  let output = hashids_with_input(&["repl"], "12345 NkK9\nNkK-\n\nKgUO9\nexit\n5\n");
+  assert!(output.status.success());
+  assert_eq!(stdout(&output), "12345 => NkK9\nNkK9 <= 12345\nNkK-: invalid character '-' in the hash at position 3\nKgUO9 <= 42,1337\n");
+
+  // 9269 is both a number and the hash of 7
+  let output = hashids_with_input(&["--alphabet", "0123456789abcdef", "repl"], "9269");
+  assert_eq!(stdout(&output), "9269 => 3a37a\n9269 <= 7\n");
+}
+
+#[test]
+fn config_files() {
+  let dir = std::env::temp_dir().join(format!("hashids-cli-config-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let toml = dir.join("hashids.toml");
+  std::fs::write(&toml, "salt = \"this is my salt\"\nmin_length = 8\n").unwrap();
+  let json = dir.join("hashids.json");
+  std::fs::write(&json, r#"{ "min_length": 8 }"#).unwrap();
+
+  assert_eq!(stdout(&hashids(&["--config", toml.to_str().unwrap(), "encode", "1"])), "gB0NV05e\n");
+  // Files without a salt take the one of the environnment
+  assert_eq!(stdout(&hashids(&["--config", json.to_str().unwrap(), "encode", "1"])), "gB0NV05e\n");
+  assert_eq!(stdout(&hashids(&["--config", toml.to_str().unwrap(), "--min-length", "4", "encode", "1"])), "0NV0\n");
+  assert!(!hashids(&["--config", dir.join("missing.toml").to_str().unwrap(), "encode", "1"]).status.success());
+  std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn doctor() {
+  let output = hashids(&["doctor"]);
+  assert!(output.status.success());
+  assert_eq!(stdout(&output), "pass  salt\npass  alphabet\npass  min length\npass  codec\n");
+
+  let output = hashids(&["--salt", "abc", "--allow-salt-arg", "--min-length", "100", "doctor"]);
+  assert!(output.status.success());
+  assert_eq!(stdout(&output), "warn  salt: the salt is 3 characters long, at least 8 are recommended\npass  alphabet\n\
+    warn  min length: a minimum length of 100 is unusually large\npass  codec\n");
+
+  let output = hashids(&["--alphabet", "abcdef", "doctor"]);
+  assert!(!output.status.success());
+  assert_eq!(stdout(&output), "pass  salt\nfail  alphabet: the alphabet has 6 unique characters, at least 16 are required\n\
+    pass  min length\nfail  codec: the alphabet has 6 unique characters, at least 16 are required\n");
+}
+
+#[test]
+fn migrate() {
+  let dir = std::env::temp_dir().join(format!("hashids-cli-migrate-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let old = dir.join("old.toml");
+  std::fs::write(&old, "salt = \"this is my salt\"\n").unwrap();
+  let new = dir.join("new.toml");
+  std::fs::write(&new, "salt = \"a new salt\"\nmin_length = 8\n").unwrap();
+  let (old, new) = (old.to_str().unwrap(), new.to_str().unwrap());
+
+  let output = hashids_with_input(&["migrate", "--from", old, "--to", new], "NkK9\n\nKgUO9\nNkK-\n0rDd\n");
+  assert!(!output.status.success());
+  assert_eq!(stdout(&output), "old,new\nNkK9,RQ7rel1M\nKgUO9,qQvDCYoO\n0rDd,JxOy0dBo\n");
+  assert!(String::from_utf8_lossy(&output.stderr).contains("line 4: NkK-: invalid character '-' in the hash at position 3"));
+  assert_eq!(stdout(&hashids(&["--config", new, "decode", "RQ7rel1M", "JxOy0dBo"])), "12345\n5\n");
+  let reencoded = HashidBuilder::new().with_salt("a new salt").with_length(8).build().unwrap();
+  assert_eq!(reencoded.decode("qQvDCYoO"), Ok(vec![42, 1337]));
+
+  let mapping = dir.join("mapping.csv");
+  let output = hashids_with_input(&["migrate", "--from", old, "--to", new, "--out", mapping.to_str().unwrap()], "NkK9\n");
+  assert!(output.status.success());
+  assert_eq!(std::fs::read_to_string(&mapping).unwrap(), "old,new\nNkK9,RQ7rel1M\n");
+  std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_vectors() {
+  let output = hashids(&["vectors", "--count", "50", "--seed", "42"]);
+  assert!(output.status.success());
+  assert_eq!(stdout(&output), stdout(&hashids(&["vectors", "--count", "50", "--seed", "42"])));
+  assert_ne!(stdout(&output), stdout(&hashids(&["vectors", "--count", "50", "--seed", "7"])));
+
+  let vectors: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+  assert_eq!(vectors.len(), 50);
+  assert_eq!(vectors[0]["salt"], "this is my salt");
+  assert_eq!(vectors[0]["min_length"], 4);
+  #[cfg(feature = "test-vectors")]
+  for vector in serde_json::from_slice::<Vec<hashids::test_vectors::TestVector>>(&output.stdout).unwrap() {
+    assert_eq!(hashids::test_vectors::check(&vector.codec().unwrap(), &vector), Ok(()));
+  }
+
+  assert!(!hashids(&["--prefix", "usr_", "vectors"]).status.success());
+}
+
+#[test]
+fn bench() {
+  let output = hashids(&["bench", "--duration-ms", "10"]);
+  assert!(output.status.success());
+  let report = stdout(&output);
+  let lines: Vec<&str> = report.lines().collect();
+  assert_eq!(lines.len(), 2);
+  assert!(lines[0].starts_with("encode") && lines[0].ends_with("ns/op"), "{}", report);
+  assert!(lines[1].starts_with("decode") && lines[1].contains("ops/s"), "{}", report);
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn serve() {
+  use std::io::{BufRead, BufReader, Read};
+  use std::net::TcpStream;
+
+  let mut child = Command::new(env!("CARGO_BIN_EXE_hashids")).args(["serve", "--port", "0", "--threads", "1"])
+    .env("HASHID_SALT", "this is my salt").stdout(Stdio::piped()).spawn().unwrap();
+  let mut listening = String::new();
+  BufReader::new(child.stdout.take().unwrap()).read_line(&mut listening).unwrap();
+  let address = listening.trim().strip_prefix("listening on http://").unwrap().to_string();
+
+  let get = |path: &str| {
+    let mut stream = TcpStream::connect(&address).unwrap();
+    write!(stream, "GET {} HTTP/1.0\r\n\r\n", path).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    (head.split(' ').nth(1).unwrap().parse::<u16>().unwrap(), body.to_string())
+  };
+  assert_eq!(get("/encode/12345"), (200, r#"{"hash":"NkK9"}"#.to_string()));
+  assert_eq!(get("/decode/KgUO9"), (200, r#"{"numbers":[42,1337]}"#.to_string()));
+  assert_eq!(get("/decode/NkK-"), (404, r#"{"error":"invalid character '-' in the hash at position 3","code":"HASHIDS_INVALID_CHARACTER"}"#.to_string()));
+  assert_eq!(get("/encode/-1").0, 400);
+  assert_eq!(get("/users/NkK9").0, 404);
+  child.kill().unwrap();
+  child.wait().unwrap();
+}
+
+#[test]
+fn init() {
+  let dir = std::env::temp_dir().join(format!("hashids-cli-init-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let (config, env) = (dir.join("hashids.toml"), dir.join("hashids.env"));
+  let args = ["init", "--out", config.to_str().unwrap(), "--env-out", env.to_str().unwrap()];
+
+  let output = hashids_with_input(&args, "9\nbase62\nabc\n6\n");
+  assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+  assert!(stdout(&output).contains("no alphabet preset named `9`"));
+  assert_eq!(std::fs::read_to_string(&config).unwrap(), "# The salt is read from the HASHID_SALT environnment variable\n\
+    alphabet = \"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz\"\nmin_length = 6\n");
+  let env_file = std::fs::read_to_string(&env).unwrap();
+  let salt = env_file.trim().strip_prefix("HASHID_SALT=").unwrap();
+  assert_eq!(salt.len(), 32);
+  assert!(salt.bytes().all(|b| b.is_ascii_alphanumeric()));
+
+  let output = Command::new(env!("CARGO_BIN_EXE_hashids")).args(["--config", config.to_str().unwrap(), "doctor"])
+    .env("HASHID_SALT", salt).output().unwrap();
+  assert_eq!(stdout(&output), "pass  salt\npass  alphabet\npass  min length\npass  codec\n");
+
+  // Existing files are kept, unless forced
+  let output = hashids_with_input(&args, "");
+  assert!(String::from_utf8_lossy(&output.stderr).contains("already exists"));
+  let mut forced = args.to_vec();
+  forced.extend(["--preset", "crockford32", "--min-length", "4", "--force"]);
+  assert!(hashids(&forced).status.success());
+  assert!(std::fs::read_to_string(&config).unwrap().contains("alphabet = \"0123456789ABCDEFGHJKMNPQRSTVWXYZ\""));
+  assert_ne!(std::fs::read_to_string(&env).unwrap(), env_file);
+  std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn bulk_stats() {
+  let input = "{\"id\":1}\n{\"id\":-1}\n{\"id\":5}\n";
+  let output = hashids_with_input(&["encode", "--jsonl", "--field", "id", "--skip-invalid", "--stats"], input);
+  assert!(!output.status.success());
+  assert_eq!(stdout(&output), "{\"id\":\"0NV0\"}\n{\"id\":\"0rDd\"}\n");
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(stderr.contains("line 2: `-1` is not a valid ID"), "{}", stderr);
+  assert!(stderr.contains("3 rows processed, 1 failed, in "), "{}", stderr);
+
+  let output = hashids_with_input(&["encode", "--jsonl", "--field", "id", "--stats"], "{\"id\":5}\n");
+  assert!(output.status.success());
+  assert!(String::from_utf8_lossy(&output.stderr).starts_with("1 rows processed, 0 failed"));
+  // Stops at the first failure without `--skip-invalid`
+  let output = hashids_with_input(&["encode", "--jsonl", "--field", "id"], input);
+  assert_eq!(stdout(&output), "{\"id\":\"0NV0\"}\n");
+  assert!(!hashids(&["encode", "--stats", "1"]).status.success());
+}
+
+#[test]
+fn salt_inputs() {
+  let dir = std::env::temp_dir().join(format!("hashids-cli-salt-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let salt_file = dir.join("salt");
+  std::fs::write(&salt_file, "another salt\n").unwrap();
+  let expected = stdout(&hashids_with_input(&["--salt", "-", "encode", "1"], "another salt\n"));
+  assert_ne!(expected, stdout(&hashids(&["encode", "1"])));
+
+  assert_eq!(stdout(&hashids(&["--salt-file", salt_file.to_str().unwrap(), "encode", "1"])), expected);
+  let output = Command::new(env!("CARGO_BIN_EXE_hashids")).args(["--salt-env", "OTHER_SALT", "encode", "1"])
+    .env("OTHER_SALT", "another salt").output().unwrap();
+  assert_eq!(stdout(&output), expected);
+  assert_eq!(stdout(&hashids(&["--salt", "another salt", "--allow-salt-arg", "encode", "1"])), expected);
+  // The rest of the standard input is left to the command
+  let output = hashids_with_input(&["--salt", "-", "encode", "--jsonl", "--field", "id"], "another salt\n{\"id\":1}\n");
+  assert_eq!(stdout(&output), format!("{{\"id\":\"{}\"}}\n", expected.trim()));
+
+  let output = hashids(&["--salt", "another salt", "encode", "1"]);
+  assert!(!output.status.success());
+  assert!(String::from_utf8_lossy(&output.stderr).contains("leak to the shell history"));
+  assert!(!hashids(&["--salt-env", "MISSING_HASHID_SALT", "encode", "1"]).status.success());
+  std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "derive")]
+extern crate hashids;
+
+use std::convert::TryFrom;
+use std::sync::OnceLock;
+
+use hashids::{HashidBuilder, HashidCodec, HashidId, Error};
+
+fn user_codec() -> &'static HashidCodec {
+  static CODEC: OnceLock<HashidCodec> = OnceLock::new();
+  CODEC.get_or_init(|| HashidBuilder::new().with_salt("this is my salt").build().unwrap())
+}
+
+#[derive(Debug, PartialEq, HashidId)]
+#[hashid(codec = "user_codec()")]
+struct UserId(i64);
+
+#[test]
+fn derived_encode_and_decode() {
+  let id = UserId(12345);
+  assert_eq!(id.encode(), Ok("NkK9".to_string()));
+  assert_eq!(id.to_string(), "NkK9");
+  assert_eq!(UserId::decode("NkK9"), Ok(UserId(12345)));
+  assert_eq!(UserId::try_from("NkK9"), Ok(UserId(12345)));
+}
+
+#[test]
+fn derived_decode_errors() {
+  assert_eq!(UserId::decode("aBMswoO2UB3Sj"), Err(Error::UnexpectedNumberCount { expected: 1, got: 4 }));
+  assert_eq!(UserId(-1).encode(), Err(Error::InvalidInputId));
+}
+
+#[cfg(feature = "serde")]
+mod serde_fields {
+  use hashids::{HashidBuilder, HashidSerde, hashid_codec};
+
+  hashid_codec!(static ORDER_CODEC = HashidBuilder::new().with_salt("orders").with_length(8););
+
+  #[derive(Debug, PartialEq, HashidSerde)]
+  #[serde(rename_all = "camelCase")]
+  struct Order {
+    #[hashid(codec = "ORDER_CODEC")]
+    id: i64,
+    #[hashid(codec = "super::user_codec()")]
+    customer_id: u64,
+    #[hashid(codec = "super::user_codec()")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    referrer_id: Option<u32>,
+    total_cents: u32
+  }
+
+  #[test]
+  fn derived_serde_fields() {
+    let order = Order { id: 1, customer_id: 12345, referrer_id: None, total_cents: 999 };
+    let json = serde_json::to_string(&order).unwrap();
+    let hash = ORDER_CODEC.encode(1).unwrap();
+    assert_eq!(json, format!(r#"{{"id":"{}","customerId":"NkK9","totalCents":999}}"#, hash));
+    assert_eq!(serde_json::from_str::<Order>(&json).unwrap(), order);
+
+    let referred = Order { referrer_id: Some(5), ..order };
+    let json = serde_json::to_string(&referred).unwrap();
+    assert!(json.contains(r#""referrerId":"0rDd""#));
+    assert_eq!(serde_json::from_str::<Order>(&json).unwrap(), referred);
+  }
+
+  #[test]
+  fn derived_serde_errors() {
+    let err = serde_json::from_str::<Order>(r#"{"id":"NkK9","customerId":"NkK9","totalCents":1}"#).unwrap_err();
+    assert!(err.to_string().starts_with("the hash"), "{}", err);
+    assert!(serde_json::from_str::<Order>(r#"{"id":1,"customerId":"NkK9","totalCents":1}"#).is_err());
+  }
+}
@@ -0,0 +1,217 @@
+use std::sync::Once;
+
+use hashids::{HashidBuilder, Error};
+
+/// Tests run in parallel, and share the global codec.
+fn init() {
+  static INIT: Once = Once::new();
+  INIT.call_once(|| hashids::init(HashidBuilder::new().with_salt("this is my salt")).unwrap());
+}
+
+#[test]
+fn global_codec() {
+  assert_eq!(hashids::init(HashidBuilder::new().with_salt("salt").with_alphabet("abc".to_string())),
+    Err(Error::InvalidAlphabetLength { got: 3, min: 16 }));
+  init();
+  assert_eq!(hashids::init(HashidBuilder::new().with_salt("another salt")), Err(Error::AlreadyInitialized));
+
+  let handles: Vec<_> = (0..4u64).map(|i| std::thread::spawn(move || hashids::encode(12345 + i).unwrap())).collect();
+  let hashes: Vec<String> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+  assert_eq!(hashes[0], "NkK9");
+  assert_eq!(hashids::decode(&hashes[1]), Ok(vec![12346]));
+  assert_eq!(hashids::global().unwrap().decode_single("0rDd"), Ok(5));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_hashids() {
+  use hashids::Hashid;
+  struct Order;
+
+  init();
+  let order: Hashid<Order> = serde_json::from_str("\"NkK9\"").unwrap();
+  assert_eq!(order.id(), 12345);
+  assert_eq!(serde_json::to_string(&order).unwrap(), "\"NkK9\"");
+
+  let err = serde_json::from_str::<Hashid<Order>>("\"NkK-\"").unwrap_err();
+  assert!(err.to_string().starts_with("invalid character '-' in the hash at position 3"));
+  assert!(serde_json::from_str::<Hashid<Order>>("12345").is_err());
+}
+
+#[cfg(feature = "serde_with")]
+#[test]
+fn serde_with_adapter() {
+  use hashids::HashidAs;
+  use serde::{Deserialize, Serialize};
+  use serde_with::serde_as;
+
+  #[serde_as]
+  #[derive(Debug, PartialEq, Serialize, Deserialize)]
+  struct Line {
+    #[serde_as(as = "HashidAs")]
+    order_id: u32,
+    #[serde_as(as = "Vec<HashidAs>")]
+    product_ids: Vec<u64>
+  }
+
+  init();
+  let line = Line { order_id: 12345, product_ids: vec![5, 12345] };
+  let json = serde_json::to_string(&line).unwrap();
+  assert_eq!(json, r#"{"order_id":"NkK9","product_ids":["0rDd","NkK9"]}"#);
+  assert_eq!(serde_json::from_str::<Line>(&json).unwrap(), line);
+
+  let err = serde_json::from_str::<Line>(r#"{"order_id":"KgUO9","product_ids":[]}"#).unwrap_err();
+  assert!(err.to_string().starts_with("the hash contains 2 numbers, 1 were expected"));
+}
+
+#[test]
+fn hashid_i64() {
+  use hashids::HashidI64;
+
+  init();
+  let id = HashidI64::from(12345);
+  assert_eq!(id.encode().unwrap(), "NkK9");
+  assert_eq!("NkK9".parse::<HashidI64>(), Ok(id));
+  assert_eq!(i64::from(id), 12345);
+  assert_eq!(HashidI64::decode("KgUO9"), Err(Error::UnexpectedNumberCount { expected: 1, got: 2 }));
+  assert_eq!(HashidI64(-1).encode(), Err(Error::InvalidInputId));
+
+  #[cfg(feature = "serde")]
+  {
+    assert_eq!(serde_json::to_string(&id).unwrap(), "\"NkK9\"");
+    assert_eq!(serde_json::from_str::<HashidI64>("\"NkK9\"").unwrap(), id);
+  }
+}
+
+#[cfg(feature = "rocket")]
+mod rocket_params {
+  use hashids::Hashid;
+  use rocket::form::Form;
+  use rocket::request::FromParam;
+  use rocket::{get, routes, uri, FromForm};
+
+  use super::{init, Error};
+
+  struct User;
+
+  #[get("/users/<id>")]
+  fn show_user(id: Hashid<User>) -> String {
+    format!("user {}", id.id())
+  }
+
+  #[derive(FromForm)]
+  struct Search {
+    user: Hashid<User>
+  }
+
+  #[test]
+  fn rocket_params() {
+    init();
+    let id = Hashid::<User>::from_param("NkK9").unwrap();
+    assert_eq!(id.id(), 12345);
+    assert_eq!(Hashid::<User>::from_param("KgUO9").err(), Some(Error::UnexpectedNumberCount { expected: 1, got: 2 }));
+    assert_eq!(uri!(show_user(&id)).to_string(), "/users/NkK9");
+    assert_eq!(rocket::build().mount("/", routes![show_user]).routes().count(), 1);
+
+    assert_eq!(Form::<Search>::parse("user=NkK9").unwrap().user, id);
+    assert!(Form::<Search>::parse("user=NkK-").is_err());
+  }
+}
+
+#[cfg(feature = "poem")]
+#[test]
+fn poem_openapi_types() {
+  use hashids::Hashid;
+  use poem_openapi::types::{ParseFromJSON, ParseFromParameter, ToJSON, Type};
+  struct User;
+
+  init();
+  let id = Hashid::<User>::parse_from_parameter("NkK9").unwrap();
+  assert_eq!(id.id(), 12345);
+  assert!(Hashid::<User>::parse_from_parameter("NkK-").is_err());
+  assert_eq!(Hashid::<User>::parse_from_json(Some(serde_json::json!("NkK9"))).unwrap(), id);
+  assert!(Hashid::<User>::parse_from_json(Some(serde_json::json!(12345))).is_err());
+  assert_eq!(id.to_json(), Some(serde_json::json!("NkK9")));
+  assert_eq!(Hashid::<User>::name(), "string_hashid");
+}
+
+#[cfg(feature = "async-graphql")]
+#[test]
+fn async_graphql_scalar() {
+  use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+  use hashids::Hashid;
+  struct User;
+  struct Query;
+
+  #[Object]
+  impl Query {
+    async fn user_name(&self, id: Hashid<User>) -> String {
+      format!("user {}", id.id())
+    }
+
+    async fn user_id(&self) -> Hashid<User> {
+      Hashid::new(5, hashids::global().unwrap()).unwrap()
+    }
+  }
+
+  init();
+  let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+  let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+  let execute = |query: &str| runtime.block_on(schema.execute(query));
+
+  assert_eq!(execute(r#"{ userName(id: "NkK9") userId }"#).data.to_string(), r#"{userName: "user 12345", userId: "0rDd"}"#);
+  let response = execute(r#"{ userName(id: "NkK-") }"#);
+  assert!(response.errors[0].message.contains("invalid character '-'"), "{:?}", response.errors);
+  assert!(!execute(r#"{ userName(id: 12345) }"#).errors.is_empty());
+}
+
+#[cfg(feature = "juniper")]
+#[test]
+fn juniper_scalar() {
+  use hashids::Hashid;
+  use juniper::{graphql_object, graphql_value, EmptyMutation, EmptySubscription, RootNode, Variables};
+  struct User;
+  struct Query;
+
+  #[graphql_object]
+  impl Query {
+    fn user_name(id: Hashid<User>) -> String {
+      format!("user {}", id.id())
+    }
+
+    fn user_id() -> Hashid<User> {
+      Hashid::new(5, hashids::global().unwrap()).unwrap()
+    }
+  }
+
+  init();
+  let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+  let execute = |query: &str| juniper::execute_sync(query, None, &schema, &Variables::new(), &());
+
+  let (data, errors) = execute(r#"{ userName(id: "NkK9") userId }"#).unwrap();
+  assert!(errors.is_empty());
+  assert_eq!(data, graphql_value!({ "userName": "user 12345", "userId": "0rDd" }));
+  assert!(execute(r#"{ userName(id: "NkK-") }"#).is_err());
+  assert!(execute(r#"{ userName(id: 12345) }"#).is_err());
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn json_schemas() {
+  use hashids::{Hashid, HashidI64};
+  struct User;
+
+  #[derive(schemars::JsonSchema)]
+  #[allow(dead_code)]
+  struct UserDto {
+    id: Hashid<User>,
+    manager_id: Option<HashidI64>
+  }
+
+  init();
+  let pattern = "^[0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz]{4,}$";
+  let schema = schemars::schema_for!(UserDto);
+  let properties = schema.get("properties").unwrap();
+  assert_eq!(properties["id"], serde_json::json!({ "type": "string", "format": "hashid", "pattern": pattern }));
+  assert_eq!(properties["manager_id"]["pattern"], pattern);
+}
@@ -1,10 +1,10 @@
 extern crate hashids;
 
-use hashids::{HashidBuilder, HashidSalt, Error};
+use hashids::{HashidBuilder, HashidSalt, Error, EncodeIterExt, Hashid, DiagnosticKind, Severity, Case, HashidKeyring, HashidVersions, CompositeId, compat, alphabet};
 
 #[test]
 fn single_usize_from_single_salt() {
-  let ids = HashidBuilder::new().with_hashid_salt(HashidSalt::from("this is my salt")).ok().unwrap();
+  let ids = HashidBuilder::new().with_hashid_salt(HashidSalt::from("this is my salt")).build().unwrap();
 
   let numbers = 12345i64;
   let encode = ids.encode(numbers).unwrap();
@@ -16,13 +16,13 @@ fn single_usize_from_single_salt() {
 
 #[test]
 fn decoding_from_different_salt_gives_error() {
-  let ids = HashidBuilder::new().with_string_salt("this is my salt".to_string()).ok().unwrap();
+  let ids = HashidBuilder::new().with_string_salt("this is my salt".to_string()).build().unwrap();
 
   let numbers = 12345;
   let encode = ids.encode(numbers).unwrap();
   assert_eq!(encode, "NkK9");
   
-  let ids2 = HashidBuilder::new().with_salt("this is my pepper").ok().unwrap();
+  let ids2 = HashidBuilder::new().with_salt("this is my pepper").build().unwrap();
   
   let longs = ids2.decode(encode);
   
@@ -32,7 +32,7 @@ fn decoding_from_different_salt_gives_error() {
 // #[test]
 // fn multiple_integers_to_single_hash() {
 //   // I don't know what this could even be used for. But my lack of understanding should not remove a feature.
-//   let ids = HashidBuilder::new().with_salt("this is my salt").ok().unwrap();
+//   let ids = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
   
 //   let numbers: Vec<i64> = vec![683, 94108, 123, 5];
 //   let encode = ids.encode(&numbers).unwrap();
@@ -42,7 +42,7 @@ fn decoding_from_different_salt_gives_error() {
 
 #[test]
 fn negative_integers_errors() {
-  let codec = HashidBuilder::new().with_salt("this is my salt").ok().unwrap();
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
 
   let numbers = -94108;
   let encode = codec.encode(numbers);
@@ -55,7 +55,7 @@ fn with_custom_length() {
   let ids = HashidBuilder::new()
                           .with_salt("this is my salt")
                           .with_length(8)
-                          .ok().unwrap();
+                          .build().unwrap();
   let numbers= 1;
   let encode = ids.encode(numbers).unwrap();
 
@@ -68,7 +68,7 @@ fn with_custom_alphabet() {
   let ids = HashidBuilder::new()
                         .with_salt("this is my salt")
                         .with_alphabet("123456789aberzxvtcfhuist".to_string())
-                        .ok().unwrap();
+                        .build().unwrap();
   
   let numbers = 1234567;
   let encode = ids.encode(numbers).unwrap();
@@ -76,36 +76,48 @@ fn with_custom_alphabet() {
   assert_eq!(encode, "xez268x");
 }
 
-// #[test]
-// fn with_nonascii_alphabet() {
-//   let ids = HashidBuilder::new()
-//                         .with_salt("漢字注入注意")
-//                         .with_alphabet("あいうえおかきくけこたちつてとさしすせそ".to_string())
-//                         .ok();
-                        
-//   assert_eq!(ids, Err(Error::NonAsciiAlphabet));
-//   // let numbers = 1234567;
-//   // let encode = ids.encode(numbers).unwrap();
-  
-// }
+#[test]
+fn with_nonascii_alphabet() {
+  let codec = HashidBuilder::new()
+                        .with_salt("this is my salt")
+                        .with_alphabet("あいうえおかきくけこたちつてとさしすせそ🦀🐍🐘🐹".to_string())
+                        .with_length(10)
+                        .build().unwrap();
+
+  for id in [0u64, 1, 1234567, 9_000_000_000_000_000_000] {
+    let encode = codec.encode(id).unwrap();
+    assert!(encode.chars().count() >= 10);
+    assert!(!encode.is_ascii());
+    assert_eq!(codec.decode_single(&encode), Ok(id));
+  }
+  let encode = codec.encode_tuple((683u64, 94108u64, 123u64, 5u64)).unwrap();
+  assert_eq!(codec.decode_exact::<4>(&encode), Ok([683, 94108, 123, 5]));
+  assert_eq!(codec.decode("あいうxえ"), Err(Error::InvalidCharacter { character: 'x', position: 9 }));
+
+  // Exact lengths count characters, not bytes
+  let codec = HashidBuilder::new().with_salt("this is my salt")
+                        .with_alphabet("あいうえおかきくけこたちつてとさしすせそ".to_string())
+                        .with_exact_length(6)
+                        .build().unwrap();
+  assert_eq!(codec.encode(1).unwrap().chars().count(), 6);
+}
 
 #[test]
 fn invalid_alphabet_fails() {
   let builder = HashidBuilder::new().with_salt("this is my salt")
           .with_alphabet("abcdefghijklm".to_string())
-          .ok();
+          .build();
 
   match builder {
     Ok(_v) => panic!("Invalid alphabet was accepted"),
-    Err(e) => assert_eq!(e, Error::InvalidAlphabetLength)
+    Err(e) => assert_eq!(e, Error::InvalidAlphabetLength { got: 13, min: 16 })
   }
 }
 
 #[test]
 fn without_salt_error() {
-  std::env::remove_var("HASHID_SALT");
-  match HashidBuilder::new().ok() {
-    Ok(_) => panic!("Created a HashidCodec without salt. A test failure might be due to envvar thread unsafety in Unix, try again in isolation."),
+  match HashidBuilder::from_env_source(|_| None).build() {
+    Ok(_) => panic!("Created a HashidCodec without salt."),
     Err(err) => assert_eq!(err, Error::MissingSalt)
   }
 }
@@ -113,7 +125,7 @@ fn without_salt_error() {
 #[test]
 fn with_envvar_salt() {
   std::env::set_var("HASHID_SALT", "organic salt");
-  let the_most_simple_builder = HashidBuilder::new().ok();
+  let the_most_simple_builder = HashidBuilder::new().build();
   match the_most_simple_builder {
     Ok(ids) => {
       let numbers = 12345;
@@ -128,9 +140,52 @@ fn with_envvar_salt() {
   }
 }
 
+#[test]
+fn env_keys() {
+  std::env::set_var("ENV_KEYS_USERS_SALT", "this is my salt");
+  std::env::set_var("ENV_KEYS_ORDERS_HASHID_SALT", "organic salt");
+  let users = HashidBuilder::new().with_env_key("ENV_KEYS_USERS_SALT").build().unwrap();
+  let orders = HashidBuilder::new().with_env_prefix("ENV_KEYS_ORDERS").build().unwrap();
+  assert_eq!(users.encode(12345).unwrap(), "NkK9");
+  assert_eq!(orders.encode(12345).unwrap(), "PWbG");
+  // A salt set through code still wins
+  let codec = HashidBuilder::new().with_env_key("ENV_KEYS_USERS_SALT").with_salt("organic salt").build().unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "PWbG");
+
+  let missing = HashidBuilder::new().with_env_key("ENV_KEYS_MISSING_SALT");
+  assert!(missing.validate().iter().any(|d| d.kind == DiagnosticKind::MissingSalt && d.message.contains("ENV_KEYS_MISSING_SALT")));
+  assert_eq!(missing.build().err(), Some(Error::MissingSalt));
+}
+
+#[test]
+fn env_sources() {
+  use std::collections::HashMap;
+  let env: HashMap<&str, &str> = [("HASHID_SALT", "this is my salt"), ("ORDERS_HASHID_SALT", "organic salt")].iter().copied().collect();
+  let source = move |key: &str| env.get(key).map(|salt| salt.to_string());
+  let codec = HashidBuilder::from_env_source(source.clone()).build().unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+  let orders = HashidBuilder::from_env_source(source.clone()).with_env_prefix("ORDERS").build().unwrap();
+  assert_eq!(orders.encode(12345).unwrap(), "PWbG");
+
+  let missing = HashidBuilder::from_env_source(source).with_env_key("MISSING");
+  assert!(missing.validate().iter().any(|d| d.kind == DiagnosticKind::MissingSalt));
+  assert_eq!(missing.build().err(), Some(Error::MissingSalt));
+}
+
+#[test]
+fn builder_default_and_set() {
+  let mut builder = HashidBuilder::default();
+  builder.set_salt("this is my salt").set_prefix("u_");
+  let codec = std::mem::take(&mut builder).build().unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "u_NkK9");
+  // The builder left behind is back to the defaults, without the prefix
+  builder.set_salt("this is my salt");
+  assert_eq!(std::mem::take(&mut builder).build().unwrap().encode(12345).unwrap(), "NkK9");
+}
+
 // #[test]
 // fn same_integers() {
-//   let ids = HashidBuilder::new().with_salt("this is my salt").ok().unwrap();
+//   let ids = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
 
 //   let numbers: Vec<i64> = vec![5, 5, 5, 5];
 //   let encode = ids.encode(&numbers).unwrap();
@@ -140,7 +195,7 @@ fn with_envvar_salt() {
 
 // #[test]
 // fn encode_int_series() {
-//   let ids = HashidBuilder::new().with_salt("this is my salt").ok().unwrap();
+//   let ids = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
 
 //   let numbers: Vec<i64> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
 //   let encode = ids.encode(&numbers).unwrap();
@@ -153,7 +208,7 @@ fn encode_successive_ints() {
   let ids = HashidBuilder::new()
       .with_salt("this is my salt")
       .with_length(2)
-      .ok()
+      .build()
       .unwrap();
 
   let numbers_1 = 1;
@@ -178,7 +233,7 @@ fn encode_successive_ints() {
 
 #[test]
 fn decode_successive_ints() {
-  let ids = HashidBuilder::new().with_salt("this is my salt").ok().unwrap();
+  let ids = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
 
   let numbers_1 = 1;
   let encode_1 = ids.encode(numbers_1).unwrap();
@@ -194,12 +249,1472 @@ fn decode_successive_ints() {
 
 #[test]
 fn decode_string_out_of_alphabet() {
-  let ids = HashidBuilder::new().with_salt("this is my salt").with_alphabet("ABCDEFGHIJKabcdefghijk".to_string()).ok().unwrap();
+  let ids = HashidBuilder::new().with_salt("this is my salt").with_alphabet("ABCDEFGHIJKabcdefghijk".to_string()).build().unwrap();
 
   let numbers_1 = 1;
   let encode_1 = ids.encode(numbers_1).unwrap();
   assert_eq!(encode_1, "dDKk");
   let decoded_string = "dDzK".to_string();
   let decoded_1 = ids.decode(decoded_string);
-  assert_eq!(decoded_1, Err(Error::InvalidHash));
+  assert_eq!(decoded_1, Err(Error::InvalidCharacter { character: 'z', position: 2 }));
+}
+
+#[test]
+fn decode_single_number() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let encode = codec.encode(12345).unwrap();
+  assert_eq!(codec.decode_single(&encode), Ok(12345));
+}
+
+#[test]
+fn decode_single_rejects_several_numbers() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  // [683, 94108, 123, 5] from the reference implementation
+  assert_eq!(codec.decode_single("aBMswoO2UB3Sj"), Err(Error::UnexpectedNumberCount { expected: 1, got: 4 }));
+}
+
+#[test]
+fn decode_borrowed_str() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let path_param: &str = "NkK9";
+  assert_eq!(codec.decode(path_param), Ok(vec![12345]));
+}
+
+#[test]
+fn encode_hex_reference_value() {
+  let codec = HashidBuilder::new().with_salt("This is my salt").build().unwrap();
+
+  let encode = codec.encode_hex("507f1f77bcf86cd799439011").unwrap();
+  assert_eq!(encode, "YO4jDOaZYlhw9mw6m3kB");
+  assert_eq!(codec.decode_hex(&encode), Ok("507f1f77bcf86cd799439011".to_string()));
+}
+
+#[test]
+fn encode_hex_keeps_leading_zeros() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let encode = codec.encode_hex("000000DEADbeef").unwrap();
+  assert_eq!(codec.decode_hex(&encode), Ok("000000deadbeef".to_string()));
+}
+
+#[test]
+fn encode_non_hex_errors() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  assert_eq!(codec.encode_hex("not hex"), Err(Error::NonHexString));
+  assert_eq!(codec.encode_hex(""), Err(Error::NonHexString));
+}
+
+#[test]
+fn u128_round_trip() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let id = u128::MAX - 12345;
+  let encode = codec.encode(id).unwrap();
+  assert_eq!(codec.decode_u128(&encode), Ok(vec![id]));
+  assert_eq!(codec.decode(&encode), Err(Error::NumberOverflow));
+
+  // Small u128 values give the same hashes as other integer types
+  assert_eq!(codec.encode(12345u128).unwrap(), "NkK9");
+}
+
+#[test]
+fn zero_round_trips_for_every_integer_type() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let encode = codec.encode(0u32).unwrap();
+  assert_eq!(codec.encode(0u64).unwrap(), encode);
+  assert_eq!(codec.encode(0u128).unwrap(), encode);
+  assert_eq!(codec.encode(0i32).unwrap(), encode);
+  assert_eq!(codec.encode(0i64).unwrap(), encode);
+
+  assert_eq!(codec.decode(&encode), Ok(vec![0]));
+  assert_eq!(codec.decode_single(&encode), Ok(0));
+}
+
+#[test]
+fn negative_ids_with_allow_negative() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").allow_negative().build().unwrap();
+
+  for id in [-94108i64, -1, 0, 1, 94108, i64::MIN, i64::MAX].iter() {
+    let encode = codec.encode(*id).unwrap();
+    assert_eq!(codec.decode_signed(&encode), Ok(vec![*id]));
+  }
+
+  // Unsigned IDs are zigzagged too, so they decode the same way
+  let encode = codec.encode(94108u32).unwrap();
+  assert_eq!(encode, codec.encode(94108i64).unwrap());
+  assert_eq!(codec.decode(&encode), Ok(vec![94108]));
+
+  let negative = codec.encode(-5i32).unwrap();
+  assert_eq!(codec.decode(&negative), Err(Error::NumberOverflow));
+}
+
+#[test]
+fn encode_iterator_lazily() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let mut hashes = vec![12345i64, -1, 5].into_iter().encode_with(&codec);
+  assert_eq!(hashes.size_hint(), (3, Some(3)));
+  assert_eq!(hashes.next(), Some(Ok("NkK9".to_string())));
+  assert_eq!(hashes.next(), Some(Err(Error::InvalidInputId)));
+  assert_eq!(hashes.next(), Some(Ok("0rDd".to_string())));
+  assert_eq!(hashes.next(), None);
+}
+
+#[test]
+fn encode_into_reused_buffer() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_length(8).build().unwrap();
+
+  let mut buffer = String::new();
+  codec.encode_into(1, &mut buffer).unwrap();
+  assert_eq!(buffer, "gB0NV05e");
+
+  buffer.push(',');
+  codec.encode_into(1, &mut buffer).unwrap();
+  assert_eq!(buffer, "gB0NV05e,gB0NV05e");
+
+  assert_eq!(codec.encode_into(-1, &mut buffer), Err(Error::InvalidInputId));
+  assert_eq!(buffer, "gB0NV05e,gB0NV05e");
+}
+
+#[test]
+fn encode_to_fixed_buffer() {
+  let mut buf = [0u8; 64];
+  for min_length in [0, 8, 30] {
+    let codec = HashidBuilder::new().with_salt("this is my salt").with_length(min_length).with_prefix("u_").build().unwrap();
+    for id in [0u64, 1, 12345, u32::MAX as u64] {
+      let hash = codec.encode(id).unwrap();
+      let len = codec.encode_to_slice(id, &mut buf).unwrap();
+      assert_eq!(&buf[..len], hash.as_bytes());
+      // Exactly as long as the hash fits, a byte less doesn't
+      assert_eq!(codec.encode_to_slice(id, &mut buf[..hash.len()]), Ok(hash.len()));
+      assert_eq!(codec.encode_to_slice(id, &mut buf[..hash.len() - 1]), Err(Error::BufferTooSmall { capacity: hash.len() - 1 }));
+    }
+  }
+
+  // Lengths are in bytes, some characters taking several
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_alphabet("αβγδεζηθικλμνξοπρστυφχψω".to_string()).build().unwrap();
+  let hash = codec.encode(12345).unwrap();
+  let len = codec.encode_to_slice(12345, &mut buf).unwrap();
+  assert_eq!(std::str::from_utf8(&buf[..len]), Ok(hash.as_str()));
+  assert!(codec.encode_to_slice(12345, &mut buf[..len - 1]).is_err());
+  assert_eq!(codec.encode_to_slice(-1, &mut buf), Err(Error::InvalidInputId));
+}
+
+#[test]
+fn decode_into_reused_buffer() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let mut ids = vec![42];
+  assert_eq!(codec.decode_into("NkK9", &mut ids), Ok(1));
+  assert_eq!(ids, vec![42, 12345]);
+
+  let too_large = codec.encode(u128::MAX).unwrap();
+  assert_eq!(codec.decode_into(&too_large, &mut ids), Err(Error::NumberOverflow));
+  assert_eq!(codec.decode_into("NkK8", &mut ids), Err(Error::InvalidHash));
+  assert_eq!(ids, vec![42, 12345]);
+}
+
+#[test]
+fn decode_as_checks_range() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  assert_eq!(codec.decode_as::<u64>("NkK9"), Ok(vec![12345u64]));
+  assert_eq!(codec.decode_as::<i64>("aBMswoO2UB3Sj"), Ok(vec![683i64, 94108, 123, 5]));
+
+  let large = codec.encode(i64::MAX).unwrap();
+  assert_eq!(codec.decode_as::<i64>(&large), Ok(vec![i64::MAX]));
+  assert_eq!(codec.decode_as::<i32>(&large), Err(Error::NumberOverflow));
+  assert_eq!(codec.decode_as::<u32>(&large), Err(Error::NumberOverflow));
+
+  let negative_codec = HashidBuilder::new().with_salt("this is my salt").allow_negative().build().unwrap();
+  let negative = negative_codec.encode(-12i32).unwrap();
+  assert_eq!(negative_codec.decode_as::<i32>(&negative), Ok(vec![-12]));
+  assert_eq!(negative_codec.decode_as::<u64>(&negative), Err(Error::NumberOverflow));
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_round_trip() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let uuid = uuid::Uuid::from_u64_pair(u64::MAX, 0);
+  let encode = codec.encode_uuid(uuid).unwrap();
+  assert_eq!(codec.decode_uuid(&encode), Ok(uuid));
+  assert_eq!(codec.decode_uuid("NkK9"), Err(Error::UnexpectedNumberCount { expected: 2, got: 1 }));
+}
+
+#[test]
+fn tuple_round_trips() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let encode = codec.encode_tuple((1u64, 2u64, 3u64)).unwrap();
+  assert_eq!(encode, "laHquq");
+  assert_eq!(codec.decode_tuple::<(u64, u64, u64)>(&encode), Ok((1, 2, 3)));
+  assert_eq!(codec.decode_tuple::<(u64, u64)>(&encode), Err(Error::UnexpectedNumberCount { expected: 2, got: 3 }));
+  assert_eq!(codec.decode_tuple::<(u64, u64, u64, u64)>(&encode), Err(Error::UnexpectedNumberCount { expected: 4, got: 3 }));
+
+  assert_eq!(codec.encode_tuple((1i64, -2i64)), Err(Error::InvalidInputId));
+}
+
+#[test]
+fn decode_exact_amount_of_numbers() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  assert_eq!(codec.decode_exact::<1>("NkK9"), Ok([12345]));
+  assert_eq!(codec.decode_exact::<3>("laHquq"), Ok([1, 2, 3]));
+  assert_eq!(codec.decode_exact::<4>("laHquq"), Err(Error::UnexpectedNumberCount { expected: 4, got: 3 }));
+  assert_eq!(codec.decode_exact::<3>("laHquu"), Err(Error::InvalidHash));
+}
+
+#[test]
+fn typed_hashids() {
+  struct Order;
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  let order_id = Hashid::<Order>::new(683, &codec).unwrap();
+  assert_eq!(order_id.id(), 683);
+  assert_eq!(Hashid::<Order>::parse(order_id.as_str(), &codec), Ok(order_id.clone()));
+  assert_eq!(u64::from(order_id), 683);
+
+  assert_eq!(Hashid::<Order>::parse("aBMswoO2UB3Sj", &codec), Err(Error::UnexpectedNumberCount { expected: 1, got: 4 }));
+
+  // A hash in another case is parsed to the codec's own
+  let upper = HashidBuilder::new().with_salt("this is my salt").with_output_case(Case::Upper).build().unwrap();
+  let order_id = Hashid::<Order>::new(12345, &upper).unwrap();
+  let parsed = Hashid::<Order>::parse(&order_id.as_str().to_lowercase(), &upper).unwrap();
+  assert_eq!(parsed.as_str(), order_id.as_str());
+  assert_eq!(parsed.to_string(), order_id.to_string());
+  assert_eq!(parsed, order_id);
+  let set: std::collections::HashSet<_> = [order_id].iter().cloned().collect();
+  assert!(set.contains(&parsed));
+
+  // Only the body is folded, the prefix stays as it is
+  let prefixed = HashidBuilder::new().with_salt("this is my salt").with_output_case(Case::Upper).with_prefix("ord_").build().unwrap();
+  let order_id = Hashid::<Order>::new(12345, &prefixed).unwrap();
+  assert_eq!(Hashid::<Order>::parse(&order_id.as_str().to_lowercase(), &prefixed), Ok(order_id));
+}
+
+#[test]
+fn decode_unchecked_skips_verification() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  assert_eq!(codec.decode_unchecked("aBMswoO2UB3Sj"), Ok(vec![683, 94108, 123, 5]));
+
+  // Tampering goes unnoticed
+  assert_eq!(codec.decode("NkK8"), Err(Error::InvalidHash));
+  assert!(codec.decode_unchecked("NkK8").is_ok());
+}
+
+#[test]
+fn decode_malformed_hashes_errors() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  assert_eq!(codec.decode("é"), Err(Error::InvalidCharacter { character: 'é', position: 0 }));
+  assert_eq!(codec.decode_unchecked("Nk\u{e9}K9"), Err(Error::InvalidCharacter { character: 'é', position: 2 }));
+  assert_eq!(codec.decode_unchecked("N kK9"), Err(Error::InvalidCharacter { character: ' ', position: 1 }));
+}
+
+#[test]
+fn validate_reports_without_consuming() {
+  let builder = HashidBuilder::new()
+      .with_salt("this is my salt")
+      .with_alphabet("abcdefghijklmnopqrstuCFHISTU".to_string());
+  let diagnostics = builder.validate();
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(diagnostics[0].kind, DiagnosticKind::SmallEffectiveAlphabet);
+  assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+  // The builder can still be used afterwards
+  assert!(builder.build().is_ok());
+
+  let builder = HashidBuilder::new().with_salt("漢字").with_length(100);
+  let kinds: Vec<DiagnosticKind> = builder.validate().iter().map(|d| d.kind).collect();
+  assert_eq!(kinds, vec![DiagnosticKind::NonAsciiSalt, DiagnosticKind::LargeMinLength]);
+
+  assert!(HashidBuilder::new().with_salt("this is my salt").validate().is_empty());
+}
+
+#[test]
+fn reference_compat_matches_hashids_js() {
+  // Expected values computed with hashids.js
+  let codec = HashidBuilder::new()
+      .with_salt("this is my salt")
+      .with_alphabet("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ".to_string())
+      .with_compat(compat::Reference)
+      .build().unwrap();
+  assert_eq!(codec.encode(1234567).unwrap(), "4QML8L");
+  assert_eq!(codec.encode_tuple((1u64, 2u64, 3u64)).unwrap(), "ZVHVIB");
+
+  // Without any of the default separators in the alphabet
+  let codec = HashidBuilder::new()
+      .with_salt("this is my salt")
+      .with_alphabet("0123456789abdegjklmnopqrvwxyz".to_string())
+      .with_compat(compat::Reference)
+      .build().unwrap();
+  assert_eq!(codec.encode(1234567).unwrap(), "qqzawm");
+  assert_eq!(codec.encode_tuple((1u64, 2u64, 3u64)).unwrap(), "dv1o2r");
+
+  // Guards are taken from separators when the alphabet is mostly separators
+  let codec = HashidBuilder::new()
+      .with_salt("this is my salt")
+      .with_alphabet("cfhistuCFHISTUab".to_string())
+      .with_length(10)
+      .with_compat(compat::Reference)
+      .build().unwrap();
+  assert_eq!(codec.encode_tuple((7u64, 8u64)).unwrap(), "UabbbCabbb");
+  assert_eq!(codec.decode_tuple::<(u64, u64)>("UabbbCabbb"), Ok((7, 8)));
+}
+
+#[test]
+fn reference_compat_same_as_legacy_for_default_alphabet() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_compat(compat::Reference).build().unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+  assert_eq!(codec, HashidBuilder::new().with_salt("this is my salt").build().unwrap());
+}
+
+#[cfg(feature = "test-vectors")]
+#[test]
+fn reference_test_vectors() {
+  let vectors = hashids::test_vectors::vectors();
+  assert!(vectors.len() >= 400);
+  assert_eq!(hashids::test_vectors::run(), vec![]);
+}
+
+#[test]
+fn long_min_length_with_small_alphabet() {
+  let codec = HashidBuilder::new()
+      .with_salt("this is my salt")
+      .with_alphabet("0123456789abcdef".to_string())
+      .with_length(40)
+      .build().unwrap();
+
+  let encode = codec.encode(1).unwrap();
+  assert_eq!(encode.len(), 40);
+  assert_eq!(codec.decode(&encode), Ok(vec![1]));
+}
+
+#[test]
+fn with_custom_separators() {
+  let codec = HashidBuilder::new()
+      .with_salt("this is my salt")
+      .with_separators("-_")
+      .with_alphabet("abcdefghijklmnopqrstuvwxyz-_".to_string())
+      .build().unwrap();
+
+  let encode = codec.encode_tuple((683u64, 94108u64, 123u64, 5u64)).unwrap();
+  assert_eq!(encode.chars().filter(|c| *c == '-' || *c == '_').count(), 3);
+  assert_eq!(codec.decode_tuple::<(u64, u64, u64, u64)>(&encode), Ok((683, 94108, 123, 5)));
+}
+
+#[test]
+fn invalid_custom_separators() {
+  let build = |separators: &str| HashidBuilder::new().with_salt("this is my salt").with_separators(separators).build();
+
+  // Not in the alphabet
+  assert_eq!(build("-_").err(), Some(Error::InvalidSeparators));
+  // Not enough separators
+  assert_eq!(build("xx").err(), Some(Error::InvalidSeparators));
+  assert_eq!(build("é").err(), Some(Error::InvalidSeparators));
+  // Nothing left in the alphabet
+  let codec = HashidBuilder::new().with_salt("this is my salt")
+      .with_alphabet("abcdefghijklmnopq".to_string())
+      .with_separators("abcdefghijklmno")
+      .build();
+  assert_eq!(codec.err(), Some(Error::InvalidSeparators));
+}
+
+#[test]
+fn with_custom_guards() {
+  let codec = HashidBuilder::new()
+      .with_salt("this is my salt")
+      .with_guards("/?")
+      .with_alphabet("abcdefghijklmnopqrstuvwxyz/?".to_string())
+      .with_length(12)
+      .build().unwrap();
+
+  let encode = codec.encode(1).unwrap();
+  assert_eq!(encode.len(), 12);
+  assert!(encode.contains('/') || encode.contains('?'));
+  assert_eq!(codec.decode(&encode), Ok(vec![1]));
+
+  // Guards never appear in hashes that don't need padding
+  let encode = codec.encode(9_000_000_000_000_000_000u64).unwrap();
+  assert!(encode.len() > 12);
+  assert!(!encode.contains('/') && !encode.contains('?'));
+  assert_eq!(codec.decode_single(&encode), Ok(9_000_000_000_000_000_000));
+}
+
+#[test]
+fn invalid_custom_guards() {
+  let build = |guards: &str| HashidBuilder::new().with_salt("this is my salt").with_guards(guards).build();
+
+  assert_eq!(build("").err(), Some(Error::InvalidGuards));
+  assert_eq!(build("/").err(), Some(Error::InvalidGuards));
+  assert_eq!(build("abcdefghijklmnopqrstuvwxyzABDEGJKLMNOPQRVWXYZ1234567890").err(), Some(Error::InvalidGuards));
+
+  let overlapping = HashidBuilder::new().with_salt("this is my salt").with_separators("xyz").with_guards("z").build();
+  assert_eq!(overlapping.err(), Some(Error::InvalidGuards));
+}
+
+#[test]
+fn salt_strength() {
+  let build = |salt: &str| HashidBuilder::new().with_salt(salt).require_salt_strength(8, 4).build();
+
+  assert_eq!(build("a").err(), Some(Error::WeakSalt));
+  assert_eq!(build("abababababab").err(), Some(Error::WeakSalt));
+  assert_eq!(build("abcd").err(), Some(Error::WeakSalt));
+  assert!(build("this is my salt").is_ok());
+  // Not enforced unless asked for
+  assert!(HashidBuilder::new().with_salt("a").build().is_ok());
+
+  let diagnostics = HashidBuilder::new().with_salt("a").require_salt_strength(8, 4).validate();
+  assert!(diagnostics.iter().any(|d| d.kind == DiagnosticKind::WeakSalt && d.severity == Severity::Error));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn generated_salts() {
+  let line = HashidSalt::generate(32).to_dotenv();
+  let salt = line.strip_prefix("HASHID_SALT=").unwrap();
+  assert_eq!(salt.len(), 32);
+  assert!(salt.bytes().all(|b| b.is_ascii_alphanumeric()));
+  assert_ne!(HashidSalt::generate(32), HashidSalt::generate(32));
+  assert!(HashidBuilder::new().with_hashid_salt(HashidSalt::generate(16)).require_salt_strength(16, 8).build().is_ok());
+}
+
+#[test]
+fn salt_dotenv_lines() {
+  assert_eq!(HashidSalt::from("a-b_c.1").to_dotenv(), "HASHID_SALT=a-b_c.1");
+  assert_eq!(HashidSalt::from("this is my salt").to_dotenv(), r#"HASHID_SALT="this is my salt""#);
+  assert_eq!(HashidSalt::from(r"$HOME\").to_dotenv(), r#"HASHID_SALT="\$HOME\\""#);
+  assert_eq!(HashidSalt::from("").to_dotenv(), r#"HASHID_SALT="""#);
+}
+
+#[test]
+fn without_salt() {
+  // Values from the reference implementation, with an empty salt
+  let codec = HashidBuilder::new().without_salt().with_length(0).build().unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "j0gW");
+  assert_eq!(codec.encode(0).unwrap(), "gY");
+  assert_eq!(codec.decode("j0gW"), Ok(vec![12345]));
+
+  let codec = HashidBuilder::new().without_salt().with_length(8).with_compat(compat::Reference).build().unwrap();
+  assert_eq!(codec.encode(1).unwrap(), "olejRejN");
+
+  assert_eq!(HashidBuilder::new().with_salt("").build().err(), Some(Error::MissingSalt));
+}
+
+#[test]
+fn output_case() {
+  let upper = HashidBuilder::new().with_salt("this is my salt").with_output_case(Case::Upper).build().unwrap();
+  let lower = HashidBuilder::new().with_salt("this is my salt").with_output_case(Case::Lower).build().unwrap();
+
+  for id in [0u64, 1, 12345, 123456789, 9_000_000_000_000_000_000] {
+    let hash = upper.encode(id).unwrap();
+    assert!(!hash.chars().any(|c| c.is_ascii_lowercase()));
+    assert_eq!(upper.decode_single(&hash), Ok(id));
+    assert_eq!(upper.decode_single(&hash.to_lowercase()), Ok(id));
+
+    let hash = lower.encode(id).unwrap();
+    assert!(!hash.chars().any(|c| c.is_ascii_uppercase()));
+    assert_eq!(lower.decode_single(&hash.to_uppercase()), Ok(id));
+  }
+
+  let preserve = HashidBuilder::new().with_salt("this is my salt").with_output_case(Case::Preserve).build().unwrap();
+  assert_eq!(preserve.encode(12345).unwrap(), "NkK9");
+
+  // Case folding can make a custom alphabet too short
+  let short = HashidBuilder::new().with_salt("this is my salt").with_alphabet("abcdefghABCDEFGH".to_string()).with_output_case(Case::Upper).build();
+  assert_eq!(short.err(), Some(Error::InvalidAlphabetLength { got: 8, min: 16 }));
+}
+
+#[test]
+fn alphabet_presets() {
+  for preset in alphabet::Preset::ALL.iter().copied() {
+    let codec = HashidBuilder::new().with_salt("this is my salt").with_preset(preset).with_length(10).build().unwrap();
+    for id in [0u64, 1, 12345, 9_000_000_000_000_000_000] {
+      let hash = codec.encode(id).unwrap();
+      assert!(hash.chars().all(|c| preset.alphabet().contains(c)), "{:?} produced {}", preset, hash);
+      assert_eq!(codec.decode_single(&hash), Ok(id));
+    }
+  }
+
+  let preset = HashidBuilder::new().with_salt("this is my salt").with_preset(alphabet::Preset::Default).build().unwrap();
+  assert_eq!(preset.encode(12345).unwrap(), "NkK9");
+}
+
+#[test]
+fn blocklist() {
+  let plain = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let blocked: Vec<String> = (0u64..200).map(|id| plain.encode(id).unwrap()[1..4].to_lowercase()).collect();
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_blocklist(&blocked).build().unwrap();
+
+  for id in 0u64..200 {
+    let hash = codec.encode(id).unwrap();
+    assert!(!blocked.iter().any(|word| hash.to_lowercase().contains(word.as_str())), "{} contains a blocked word", hash);
+    assert_eq!(codec.decode_single(&hash), Ok(id));
+    // Hashes made without the blocklist are rejected when they differ
+    let original = plain.encode(id).unwrap();
+    if original != hash {
+      assert_eq!(codec.decode_single(&original), Err(Error::InvalidHash));
+    }
+  }
+
+  // Blocklists that don't apply leave hashes untouched
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_blocklist(vec!["ab", "~~~"]).build().unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+}
+
+#[cfg(feature = "blocklist")]
+#[test]
+fn default_blocklist() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_default_blocklist().build().unwrap();
+  for id in 0u64..10000 {
+    let hash = codec.encode(id).unwrap().to_lowercase();
+    assert!(!hashids::blocklist::DEFAULT_BLOCKLIST.iter().any(|word| hash.contains(word)), "{} contains a blocked word", hash);
+  }
+}
+
+#[test]
+fn prefix_and_suffix() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_prefix("usr_").with_suffix(".v1").build().unwrap();
+
+  let hash = codec.encode(12345).unwrap();
+  assert_eq!(hash, "usr_NkK9.v1");
+  assert_eq!(codec.decode(&hash), Ok(vec![12345]));
+  assert_eq!(codec.encode_tuple((683u64, 94108u64, 123u64, 5u64)).unwrap(), "usr_aBMswoO2UB3Sj.v1");
+  assert_eq!(codec.decode_unchecked("usr_NkK9.v1"), Ok(vec![12345]));
+
+  assert_eq!(codec.decode("NkK9.v1"), Err(Error::InvalidPrefix));
+  assert_eq!(codec.decode("org_NkK9.v1"), Err(Error::InvalidPrefix));
+  assert_eq!(codec.decode("usr_NkK9"), Err(Error::InvalidSuffix));
+  assert_eq!(codec.decode("usr_.v1"), Err(Error::EmptyHash));
+  assert_eq!(codec.decode("usr_Nk-9.v1"), Err(Error::InvalidCharacter { character: '-', position: 6 }));
+
+  // The length of the prefix and suffix doesn't count towards the minimum length
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_prefix("usr_").with_length(8).build().unwrap();
+  assert_eq!(codec.encode(1).unwrap(), "usr_gB0NV05e");
+}
+
+#[cfg(feature = "mac")]
+#[test]
+fn mac_tags() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_prefix("usr_").with_mac_key(b"mac key", 6).build().unwrap();
+  let plain = HashidBuilder::new().with_salt("this is my salt").with_prefix("usr_").build().unwrap();
+
+  for id in [0u64, 1, 12345, 9_000_000_000_000_000_000] {
+    let hash = codec.encode(id).unwrap();
+    let untagged = plain.encode(id).unwrap();
+    assert!(hash.starts_with(&untagged) && hash.len() == untagged.len() + 6);
+    assert_eq!(codec.decode_single(&hash), Ok(id));
+    assert_eq!(codec.decode_single(&untagged), Err(Error::InvalidMac));
+  }
+
+  // Same settings, another key
+  let other = HashidBuilder::new().with_salt("this is my salt").with_prefix("usr_").with_mac_key(b"other key", 6).build().unwrap();
+  assert_eq!(other.decode(codec.encode(12345).unwrap()), Err(Error::InvalidMac));
+  // Same key, another prefix
+  let orgs = HashidBuilder::new().with_salt("this is my salt").with_prefix("org_").with_mac_key(b"mac key", 6).build().unwrap();
+  let hash = codec.encode(12345).unwrap().replacen("usr_", "org_", 1);
+  assert_eq!(orgs.decode(hash), Err(Error::InvalidMac));
+
+  let invalid = |key: &[u8], length| HashidBuilder::new().with_salt("this is my salt").with_mac_key(key, length).build().err();
+  assert_eq!(invalid(b"", 8), Some(Error::InvalidMacSettings));
+  assert_eq!(invalid(b"mac key", 3), Some(Error::InvalidMacSettings));
+  assert_eq!(invalid(b"mac key", 17), Some(Error::InvalidMacSettings));
+}
+
+#[cfg(feature = "fpe")]
+#[test]
+fn fpe_encryption() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_fpe_key(b"encryption key").build().unwrap();
+  let other = HashidBuilder::new().with_salt("this is my salt").with_fpe_key(b"other key").build().unwrap();
+
+  let mut hashes = std::collections::HashSet::new();
+  for id in (0u64..1000).chain([u32::MAX as u64, 9_000_000_000_000_000_000]) {
+    let hash = codec.encode(id).unwrap();
+    assert!(hashes.insert(hash.clone()));
+    assert_eq!(codec.decode_single(&hash), Ok(id));
+    assert_ne!(other.encode(id).unwrap(), hash);
+  }
+  assert_eq!(codec.encode_tuple((1u64, 2u64)).map(|hash| codec.decode_tuple::<(u64, u64)>(&hash)), Ok(Ok((1, 2))));
+  assert_eq!(codec.encode(u64::MAX as u128 + 1), Err(Error::InvalidInputId));
+
+  let signed = HashidBuilder::new().with_salt("this is my salt").allow_negative().with_fpe_key(b"encryption key").build().unwrap();
+  assert_eq!(signed.decode_signed(&signed.encode(i64::MIN + 1).unwrap()), Ok(vec![i64::MIN + 1]));
+
+  assert_eq!(HashidBuilder::new().with_salt("this is my salt").with_fpe_key(b"").build().err(), Some(Error::InvalidFpeKey));
+}
+
+#[test]
+fn max_and_exact_length() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_exact_length(6).with_prefix("id_").build().unwrap();
+  let mut out = String::from("/");
+  for id in 0u64..5000 {
+    let hash = codec.encode(id).unwrap();
+    assert_eq!(hash.len(), 9);
+    assert_eq!(codec.decode_single(&hash), Ok(id));
+  }
+  assert!(matches!(codec.encode_into(u64::MAX >> 4, &mut out), Err(Error::HashTooLong { max: 6, .. })));
+  assert_eq!(out, "/");
+  assert_eq!(codec.encode_tuple((1u64, 2u64, 3u64, 4u64)), Err(Error::HashTooLong { length: 8, max: 6 }));
+
+  // Hashes of another codec, longer than the maximum, can't be decoded
+  let unlimited = HashidBuilder::new().with_salt("this is my salt").with_length(6).with_prefix("id_").build().unwrap();
+  assert_eq!(codec.decode_single(&unlimited.encode(u64::MAX >> 4).unwrap()), Err(Error::InvalidHash));
+
+  let build = |min, max| HashidBuilder::new().with_salt("this is my salt").with_length(min).with_max_length(max).build().err();
+  assert_eq!(build(0, 0), Some(Error::InvalidMaxLength));
+  assert_eq!(build(8, 6), Some(Error::InvalidMaxLength));
+  assert_eq!(build(0, 1), None);
+}
+
+#[test]
+fn encode_many() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_length(8).build().unwrap();
+  let ids: Vec<u64> = (0..300).collect();
+  let hashes = codec.encode_many(&ids).unwrap();
+  for (id, hash) in ids.iter().zip(&hashes) {
+    assert_eq!(hash, &codec.encode(*id).unwrap());
+  }
+  assert_eq!(codec.encode_many::<u64>(&[]), Ok(vec![]));
+  assert_eq!(codec.encode_many(&[1i64, -1]), Err(Error::InvalidInputId));
+}
+
+#[test]
+fn encode_slice() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  assert_eq!(codec.encode_slice(&[42u64, 1337]), codec.encode_tuple((42u64, 1337u64)));
+  assert_eq!(codec.encode_slice(&[12345u32]), codec.encode(12345));
+  let hash = codec.encode_slice(&[1u128, u128::MAX, 0]).unwrap();
+  assert_eq!(codec.decode_u128(&hash), Ok(vec![1, u128::MAX, 0]));
+  assert_eq!(codec.encode_slice::<u64>(&[]), Err(Error::InvalidInputId));
+  assert_eq!(codec.encode_slice(&[1i64, -1]), Err(Error::InvalidInputId));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_encode_and_decode_many() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let ids: Vec<u64> = (0..2_000).collect();
+  let hashes = codec.par_encode_many(&ids).unwrap();
+  assert_eq!(hashes, codec.encode_many(&ids).unwrap());
+  assert_eq!(codec.par_decode_many(&hashes), Ok(ids));
+
+  assert_eq!(codec.par_encode_many(&[1i64, -1]), Err(Error::InvalidInputId));
+  assert_eq!(codec.par_decode_many(&["NkK9", "aBMswoO2UB3Sj", "NkK"]), Err(Error::UnexpectedNumberCount { expected: 1, got: 4 }));
+}
+
+#[test]
+fn decode_many() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let rows = ["NkK9", "aBMswoO2UB3Sj", "NkK-", "Nkk9", "0rDd"];
+  let results = codec.decode_many(rows.iter().copied());
+  assert_eq!(results, vec![
+    Ok(12345),
+    Err(Error::UnexpectedNumberCount { expected: 1, got: 4 }),
+    Err(Error::InvalidCharacter { character: '-', position: 3 }),
+    Err(Error::InvalidHash),
+    Ok(5)
+  ]);
+
+  let owned: Vec<String> = codec.encode_many(&[1u64, 2, 3]).unwrap();
+  let ids: Result<Vec<u64>, Error> = codec.decode_many(owned.iter().map(String::as_str)).into_iter().collect();
+  assert_eq!(ids, Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn keyring_rotation() {
+  let keyring = HashidKeyring::new(&["third salt", "second salt", "this is my salt"], |builder| builder).unwrap();
+  assert_eq!(keyring.decode("NkK9"), Ok((vec![12345], 2)));
+  let current = keyring.encode(12345).unwrap();
+  assert_eq!(keyring.decode(&current), Ok((vec![12345], 0)));
+  assert_eq!(keyring.current().decode(&current), Ok(vec![12345]));
+  assert_eq!(keyring.decode("NkK-"), Err(Error::InvalidCharacter { character: '-', position: 3 }));
+
+  assert_eq!(HashidKeyring::new(&[], |builder| builder).err(), Some(Error::MissingSalt));
+  let invalid = HashidKeyring::new(&["salt"], |builder| builder.with_alphabet("abc".to_string()));
+  assert_eq!(invalid.err(), Some(Error::InvalidAlphabetLength { got: 3, min: 16 }));
+
+  let codecs = vec![
+    HashidBuilder::new().with_salt("second salt").build().unwrap(),
+    HashidBuilder::new().with_salt("this is my salt").build().unwrap()
+  ];
+  let keyring = HashidKeyring::from_codecs(codecs).unwrap();
+  assert_eq!(keyring.codecs().len(), 2);
+  assert_eq!(keyring.decode("NkK9"), Ok((vec![12345], 1)));
+}
+
+#[test]
+fn versioned_hashes() {
+  let v1 = || HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let v2 = || HashidBuilder::new().with_salt("second salt").with_length(8).build().unwrap();
+  let versions = HashidVersions::new().with_version('.', v1()).unwrap().with_version('~', v2()).unwrap();
+
+  let hash = versions.encode(12345).unwrap();
+  assert_eq!(hash.chars().count(), 9);
+  assert_eq!(versions.decode(&hash), Ok((vec![12345], '~')));
+  assert_eq!(versions.current().map(|(version, _)| version), Some('~'));
+  assert_eq!(versions.decode(".NkK9"), Ok((vec![12345], '.')));
+  assert_eq!(versions.decode("NkK9"), Err(Error::UnknownVersion));
+  assert_eq!(versions.decode(""), Err(Error::EmptyHash));
+  assert_eq!(HashidVersions::new().encode(12345), Err(Error::NoVersion));
+  assert!(HashidVersions::new().current().is_none());
+  assert_eq!(versions.decode("~NkK-"), Err(Error::InvalidCharacter { character: '-', position: 3 }));
+
+  // Versions must be unique, and stay out of every codec's hashes
+  assert_eq!(HashidVersions::new().with_version('a', v1()).err(), Some(Error::InvalidVersionCharacter));
+  assert_eq!(HashidVersions::new().with_version('~', v1()).unwrap().with_version('~', v2()).err(), Some(Error::InvalidVersionCharacter));
+  let lowercase = HashidBuilder::new().with_salt("this is my salt").with_alphabet("abcdefghijklmnopqrstuvwxyz".to_string()).build().unwrap();
+  assert_eq!(HashidVersions::new().with_version('A', lowercase).unwrap().with_version('~', v1()).err(), Some(Error::InvalidVersionCharacter));
+}
+
+#[test]
+fn composite_ids() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let composite = CompositeId { tenant: 7, id: 9_000_000_000_000_000_000 };
+  let hash = codec.encode_composite(composite).unwrap();
+  assert_eq!(codec.decode_composite(&hash), Ok(composite));
+  assert_eq!(codec.decode_tuple::<(u64, u64)>(&hash), Ok((7, 9_000_000_000_000_000_000)));
+  assert_eq!(CompositeId::from((7, 9)), CompositeId { tenant: 7, id: 9 });
+
+  assert_eq!(codec.decode_composite("NkK9"), Err(Error::UnexpectedNumberCount { expected: 2, got: 1 }));
+  assert_eq!(codec.encode_composite(CompositeId { tenant: u64::MAX, id: 1 }), Err(Error::InvalidInputId));
+}
+
+#[test]
+fn timestamped_hashes() {
+  use std::time::{Duration, SystemTime};
+
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let created = SystemTime::UNIX_EPOCH + Duration::from_secs(1_750_000_000);
+  let hash = codec.encode_with_timestamp(12345u64, created).unwrap();
+  assert_eq!(codec.decode_with_timestamp(&hash), Ok((12345, created)));
+  // Sub-second precision is dropped
+  let hash = codec.encode_with_timestamp(12345u64, created + Duration::from_millis(999)).unwrap();
+  assert_eq!(codec.decode_with_timestamp(&hash), Ok((12345, created)));
+
+  let epoch = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+  let recent = HashidBuilder::new().with_salt("this is my salt").with_timestamp_epoch(epoch).build().unwrap();
+  let short = recent.encode_with_timestamp(12345u64, created).unwrap();
+  assert!(short.len() < hash.len());
+  assert_eq!(recent.decode_with_timestamp(&short), Ok((12345, created)));
+  assert_eq!(recent.encode_with_timestamp(1u64, SystemTime::UNIX_EPOCH), Err(Error::InvalidTimestamp));
+
+  assert_eq!(codec.decode_with_timestamp("NkK9"), Err(Error::UnexpectedNumberCount { expected: 2, got: 1 }));
+}
+
+#[test]
+fn snowflake_ids() {
+  use hashids::snowflake::{Snowflake, SnowflakeLayout};
+
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  for id in [0u64, 1, 1_541_815_603_606_036_480, 175_928_847_299_117_063, i64::MAX as u64] {
+    let hash = codec.encode_snowflake(id, SnowflakeLayout::DISCORD).unwrap();
+    assert_eq!(codec.decode_snowflake(&hash, SnowflakeLayout::DISCORD), Ok(id));
+  }
+  assert_eq!(codec.encode_snowflake(u64::MAX, SnowflakeLayout::TWITTER), Err(Error::InvalidInputId));
+
+  let layout = SnowflakeLayout::new(32, 16, 16).unwrap();
+  let id = layout.join(Snowflake { timestamp: 1, worker: 2, sequence: 3 }).unwrap();
+  assert_eq!(id, (1 << 32) | (2 << 16) | 3);
+  assert_eq!(layout.join(Snowflake { timestamp: 1, worker: 1 << 16, sequence: 3 }), None);
+  assert_eq!(SnowflakeLayout::new(48, 16, 1), None);
+  assert_eq!(SnowflakeLayout::new(u32::MAX, 1, 0), None);
+
+  // Layouts filling the 64 bits, down to a single part
+  for layout in [SnowflakeLayout::new(0, 0, 64), SnowflakeLayout::new(0, 32, 32), SnowflakeLayout::new(64, 0, 0)].iter().map(|layout| layout.unwrap()) {
+    let hash = codec.encode_snowflake(u64::MAX, layout).unwrap();
+    assert_eq!(codec.decode_snowflake(&hash, layout), Ok(u64::MAX));
+  }
+  let full = SnowflakeLayout::new(0, 0, 64).unwrap();
+  assert_eq!(full.split(u64::MAX), Snowflake { timestamp: 0, worker: 0, sequence: u64::MAX });
+  assert_eq!(full.join(Snowflake { timestamp: 1, worker: 0, sequence: 0 }), None);
+
+  // A worker too large for the layout
+  let hash = codec.encode_tuple((1u64, 1u64 << 16, 3u64)).unwrap();
+  assert_eq!(codec.decode_snowflake(&hash, layout), Err(Error::NumberOverflow));
+  assert_eq!(codec.decode_snowflake("NkK9", layout), Err(Error::UnexpectedNumberCount { expected: 3, got: 1 }));
+}
+
+#[test]
+fn error_display() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let err = codec.decode("Nk-9").unwrap_err();
+  assert_eq!(err.to_string(), "invalid character '-' in the hash at position 2");
+
+  let boxed: Box<dyn std::error::Error> = Box::new(Error::MissingSalt);
+  assert!(boxed.to_string().contains("HASHID_SALT"));
+
+  let err = HashidBuilder::new().with_salt("salt").with_alphabet("abc".to_string()).build().unwrap_err();
+  assert_eq!(err.to_string(), "the alphabet has 3 unique characters, at least 16 are required");
+}
+
+#[test]
+fn structured_errors() {
+  let err = HashidBuilder::new().with_salt("sel de Guérande").build().unwrap_err();
+  assert_eq!(err, Error::NonAsciiSalt { first_bad_char: 'é' });
+
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  match codec.decode_exact::<2>("aBMswoO2UB3Sj") {
+    Err(Error::UnexpectedNumberCount { expected, got }) => assert_eq!((expected, got), (2, 4)),
+    other => panic!("unexpected result {:?}", other)
+  }
+  assert_eq!(codec.decode_exact::<2>("aBMswoO2UB3Sj").unwrap_err().to_string(), "the hash contains 4 numbers, 2 were expected");
+}
+
+#[test]
+fn error_codes() {
+  fn build(salt: &str) -> hashids::Result<hashids::HashidCodec> {
+    HashidBuilder::new().with_salt(salt).with_alphabet("abc".to_string()).build()
+  }
+  assert_eq!(build("salt").unwrap_err().code(), "HASHIDS_INVALID_ALPHABET_LENGTH");
+  assert_eq!(Error::MissingSalt.code(), "HASHIDS_MISSING_SALT");
+  assert_eq!(Error::InvalidCharacter { character: '-', position: 0 }.code(), "HASHIDS_INVALID_CHARACTER");
+}
+
+#[test]
+fn invalid_hash_errors() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  assert!(codec.decode("NkK-").unwrap_err().is_invalid_hash());
+  assert!(codec.decode_single("KgUO9").unwrap_err().is_invalid_hash());
+  assert!(!Error::MissingSalt.is_invalid_hash());
+  assert!(!codec.encode(-1).unwrap_err().is_invalid_hash());
+}
+
+#[test]
+fn whitespace_and_regex_characters_in_alphabet() {
+  let codec = HashidBuilder::new().with_salt("this is my salt")
+    .with_alphabet("abcdefghijklmnop ]^[-\\.*+?".to_string())
+    .with_length(12)
+    .build().unwrap();
+  for id in [0u64, 1, 12345, u32::MAX as u64] {
+    let hash = codec.encode(id).unwrap();
+    assert_eq!(codec.decode_single(&hash), Ok(id));
+  }
+  let hash = codec.encode_tuple((683u64, 94108u64, 123u64, 5u64)).unwrap();
+  assert_eq!(codec.decode_tuple(&hash), Ok((683u64, 94108u64, 123u64, 5u64)));
+}
+
+#[test]
+fn long_alphabet() {
+  // Longer than what fits in the stack buffers
+  let alphabet: String = (0x4e00u32..0x4e00 + 300).filter_map(char::from_u32).collect();
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_alphabet(alphabet).with_length(20).build().unwrap();
+  for id in [0u64, 1, 12345, 9_000_000_000_000_000_000] {
+    let hash = codec.encode(id).unwrap();
+    assert_eq!(hash.chars().count(), 20);
+    assert_eq!(codec.decode_single(&hash), Ok(id));
+  }
+  assert_eq!(codec.encode_many(&[1u64, 2]).unwrap(), vec![codec.encode(1).unwrap(), codec.encode(2).unwrap()]);
+}
+
+hashids::hashid_codec!(static STATIC_CODEC = "this is my salt";);
+hashids::hashid_codec!(
+  /// A codec that can't be built.
+  static INVALID_CODEC = HashidBuilder::new().with_salt("this is my salt").with_alphabet("abc".to_string());
+);
+
+#[test]
+fn static_codecs() {
+  let handles: Vec<_> = (0..4u64).map(|i| std::thread::spawn(move || STATIC_CODEC.encode(12345 + i).unwrap())).collect();
+  let hashes: Vec<String> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+  assert_eq!(hashes[0], "NkK9");
+  assert_eq!(STATIC_CODEC.decode_single(&hashes[3]), Ok(12348));
+
+  let panic = std::panic::catch_unwind(|| INVALID_CODEC.encode(1)).unwrap_err();
+  assert_eq!(panic.downcast_ref::<String>().unwrap(), "the `INVALID_CODEC` codec can't be built: the alphabet has 3 unique characters, at least 16 are required");
+}
+
+#[cfg(feature = "cache")]
+#[test]
+fn cached_codec() {
+  use std::num::NonZeroUsize;
+  use hashids::CachedCodec;
+
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let cached = CachedCodec::new(codec, NonZeroUsize::new(2).unwrap());
+  for _ in 0..3 {
+    for id in [12345u64, 5, 1] {
+      let hash = cached.encode(id).unwrap();
+      assert_eq!(hash, cached.codec().encode(id).unwrap());
+      assert_eq!(cached.decode_single(&hash), Ok(id));
+    }
+  }
+  assert_eq!(cached.encode(-1i64), Err(Error::InvalidInputId));
+  assert_eq!(cached.decode_single("NkK-"), Err(Error::InvalidCharacter { character: '-', position: 3 }));
+  cached.clear();
+  assert_eq!(cached.decode_single("0rDd"), Ok(5));
+}
+
+#[test]
+fn config_round_trip() {
+  use hashids::{HashidCodec, HashidConfig};
+
+  let codec = HashidBuilder::new().with_salt("this is my salt")
+    .with_alphabet("abcdefghijklmnopqrstuvwxyz1234567890".to_string())
+    .with_length(10)
+    .with_prefix("usr_")
+    .with_output_case(Case::Upper)
+    .with_blocklist(["abc"])
+    .build().unwrap();
+  let config = codec.to_config();
+  assert_eq!(config.salt, "this is my salt");
+  assert_eq!(config.prefix, "usr_");
+  assert_eq!(HashidCodec::from_config(config.clone()), Ok(codec));
+  assert_eq!(HashidBuilder::from_config(config).build().unwrap().to_config().min_length, 10);
+
+  let invalid = HashidConfig { salt: "salt".to_string(), alphabet: Some("abc".to_string()), ..HashidConfig::default() };
+  assert_eq!(HashidCodec::from_config(invalid), Err(Error::InvalidAlphabetLength { got: 3, min: 16 }));
+  assert_eq!(HashidCodec::from_config(HashidConfig::default()), Err(Error::MissingSalt));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn config_serde() {
+  use hashids::{HashidCodec, HashidConfig};
+
+  let config: HashidConfig = serde_json::from_str(r#"{"salt": "this is my salt", "min_length": 8, "case": "Upper"}"#).unwrap();
+  assert_eq!(config.case, Case::Upper);
+  let codec = HashidCodec::from_config(config).unwrap();
+  let json = serde_json::to_string(&codec.to_config()).unwrap();
+  let restored = HashidCodec::from_config(serde_json::from_str(&json).unwrap()).unwrap();
+  assert_eq!(restored.encode(1).unwrap(), codec.encode(1).unwrap());
+}
+
+#[cfg(feature = "config-file")]
+#[test]
+fn config_files() {
+  let dir = std::env::temp_dir().join(format!("hashids-config-files-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let build = |name: &str, contents: &str| {
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    HashidBuilder::from_file(&path).and_then(|builder| builder.build()).map_err(|err| err.to_string().replace(path.to_str().unwrap(), name))
+  };
+
+  let codec = build("literal.toml", "salt = \"this is my salt\"\nmin_length = 8\nprefix = \"u_\"\n").unwrap();
+  assert_eq!(codec.encode(1).unwrap(), "u_gB0NV05e");
+  let codec = build("literal.json", r#"{ "salt": "this is my salt", "alphabet": "0123456789abcdef" }"#).unwrap();
+  assert_eq!(codec.to_config().alphabet.as_deref(), Some("0123456789abcdef"));
+  std::env::set_var("HASHIDS_CONFIG_FILES_SALT", "this is my salt");
+  let codec = build("env.toml", "salt = { env = \"HASHIDS_CONFIG_FILES_SALT\" }\n").unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+  assert!(build("unsalted.toml", "unsalted = true\n").is_ok());
+
+  assert_eq!(build("unset.toml", "salt = { env = \"HASHIDS_CONFIG_FILES_UNSET\" }\n").unwrap_err(),
+    "unset.toml: the salt is read from the `HASHIDS_CONFIG_FILES_UNSET` environnment variable, which isn't set");
+  assert_eq!(build("typo.toml", "salt = \"this is my salt\"\nmin_lenght = 8\n").unwrap_err(), "typo.toml: unknown key `min_lenght`");
+  assert_eq!(build("type.json", r#"{ "min_length": "8" }"#).unwrap_err(),
+    "type.json: invalid `min_length`: invalid type: string \"8\", expected usize");
+  assert_eq!(build("salt.toml", "salt = 1\n").unwrap_err(),
+    "salt.toml: the `salt` must be a string, or a table naming the environnment variable holding it");
+  assert!(build("syntax.toml", "salt = \n").unwrap_err().starts_with("syntax.toml: "));
+  let err = HashidBuilder::from_file(dir.join("missing.toml")).err().unwrap();
+  assert_eq!(err.code(), "HASHIDS_INVALID_CONFIG_FILE");
+  assert!(err.to_string().contains("can't be read"));
+  std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "figment")]
+#[test]
+fn figment_layers() {
+  use figment::providers::{Env, Format, Toml};
+  use figment::Figment;
+  use hashids::HashidConfig;
+
+  let path = std::env::temp_dir().join(format!("hashids-figment-{}.toml", std::process::id()));
+  std::fs::write(&path, "[hashids]\nsalt = \"this is my salt\"\nmin_length = 6\nprefix = \"u_\"\n").unwrap();
+  std::env::set_var("HASHIDS_FIGMENT_TEST_HASHIDS__MIN_LENGTH", "8");
+  let figment = || Figment::from(HashidConfig { min_length: 12, ..HashidConfig::default() })
+    .merge(Toml::file(&path))
+    .merge(Env::prefixed("HASHIDS_FIGMENT_TEST_").split("__"));
+
+  // The environnment overrides the file, which overrides the defaults
+  let codec = HashidBuilder::from_figment(&figment()).unwrap().build().unwrap();
+  assert_eq!(codec.encode(1).unwrap(), "u_gB0NV05e");
+
+  std::env::set_var("HASHIDS_FIGMENT_TEST_HASHIDS__MIN_LENGTH", "eight");
+  assert!(HashidBuilder::from_figment(&figment()).err().unwrap().to_string().contains("\"eight\", expected usize"));
+  std::env::remove_var("HASHIDS_FIGMENT_TEST_HASHIDS__MIN_LENGTH");
+  std::fs::remove_file(&path).unwrap();
+
+  // Without the key, every setting takes its default
+  let codec = HashidBuilder::from_figment(&Figment::new()).unwrap().with_salt("this is my salt").build().unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_events() {
+  use std::sync::{Arc, Mutex};
+  use tracing::field::{Field, Visit};
+  use tracing::span::{Attributes, Id, Record};
+  use tracing::{Event, Metadata, Subscriber};
+
+  /// Records every event as its level and fields, and the spans they are in.
+  #[derive(Clone, Default)]
+  struct Recorder(Arc<Mutex<Vec<String>>>);
+
+  struct Line(String);
+
+  impl Visit for Line {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+      self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+  }
+
+  impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool { true }
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+      self.0.lock().unwrap().push(format!("span {}", span.metadata().name()));
+      Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+      let mut line = Line(event.metadata().level().to_string());
+      event.record(&mut line);
+      self.0.lock().unwrap().push(line.0);
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+  }
+
+  let recorder = Recorder::default();
+  tracing::subscriber::with_default(recorder.clone(), || {
+    let codec = HashidBuilder::new().with_salt("this is my salt").with_prefix("u_").build().unwrap();
+    assert!(HashidBuilder::new().with_salt("this is my salt").with_alphabet("abc".to_string()).build().is_err());
+    codec.decode("u_NkK9").unwrap();
+    assert!(codec.decode("u_NkK-").is_err());
+
+    // Older salts failing on newer hashes aren't reported
+    let keyring = HashidKeyring::new(&["my new salt", "this is my salt"], |builder| builder).unwrap();
+    keyring.decode("NkK9").unwrap();
+  });
+
+  let events = recorder.0.lock().unwrap();
+  assert_eq!(events[..], [
+    "span hashids_build",
+    "DEBUG message=codec built alphabet_length=44 min_length=4 prefix=u_ suffix= salted=true",
+    "span hashids_build",
+    "WARN message=codec settings refused error=the alphabet has 3 unique characters, at least 16 are required code=\"HASHIDS_INVALID_ALPHABET_LENGTH\"",
+    "DEBUG message=hash failed to decode error=invalid character '-' in the hash at position 5 code=\"HASHIDS_INVALID_CHARACTER\" hash_length=6",
+    "span hashids_build",
+    "DEBUG message=codec built alphabet_length=44 min_length=4 prefix= suffix= salted=true",
+    "span hashids_build",
+    "DEBUG message=codec built alphabet_length=44 min_length=4 prefix= suffix= salted=true"
+  ]);
+  assert!(!events.iter().any(|event| event.contains("this is my salt")));
+}
+
+#[test]
+fn metrics_callbacks() {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::Arc;
+  use hashids::HashidMetrics;
+
+  #[derive(Default)]
+  struct Counts { encoded: AtomicUsize, decoded: AtomicUsize, invalid: AtomicUsize, hits: AtomicUsize, misses: AtomicUsize }
+
+  impl HashidMetrics for Counts {
+    fn encoded(&self) { self.encoded.fetch_add(1, Ordering::Relaxed); }
+    fn decoded(&self) { self.decoded.fetch_add(1, Ordering::Relaxed); }
+    fn decode_failed(&self, error: &Error) {
+      assert!(error.is_invalid_hash());
+      self.invalid.fetch_add(1, Ordering::Relaxed);
+    }
+    fn cache_hit(&self) { self.hits.fetch_add(1, Ordering::Relaxed); }
+    fn cache_miss(&self) { self.misses.fetch_add(1, Ordering::Relaxed); }
+  }
+  let count = |counter: &AtomicUsize| counter.load(Ordering::Relaxed);
+
+  let counts = Arc::new(Counts::default());
+  let codec = HashidBuilder::new().with_salt("this is my salt").with_metrics(counts.clone()).build().unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+  codec.encode_many(&[1u64, 2]).unwrap();
+  assert_eq!(codec.decode("NkK9"), Ok(vec![12345]));
+  assert!(codec.decode("NkK-").is_err());
+  assert_eq!((count(&counts.encoded), count(&counts.decoded), count(&counts.invalid)), (3, 1, 1));
+  // Hashes that don't fit the buffer aren't counted as encoded
+  let mut buf = [0u8; 4];
+  assert!(codec.encode_to_slice(12345, &mut buf[..3]).is_err());
+  assert_eq!(codec.encode_to_slice(12345, &mut buf), Ok(4));
+  assert_eq!(count(&counts.encoded), 4);
+  // Metrics don't make codecs different
+  assert_eq!(codec, HashidBuilder::new().with_salt("this is my salt").build().unwrap());
+
+  // Keyrings report once per hash, not for each salt tried
+  let counts = Arc::new(Counts::default());
+  let metrics = counts.clone();
+  let keyring = HashidKeyring::new(&["my new salt", "this is my salt"], |builder| builder.with_metrics(metrics.clone())).unwrap();
+  assert_eq!(keyring.decode("NkK9"), Ok((vec![12345], 1)));
+  assert!(keyring.decode("NkK-").is_err());
+  assert_eq!((count(&counts.decoded), count(&counts.invalid)), (1, 1));
+
+  #[cfg(feature = "cache")]
+  {
+    use std::num::NonZeroUsize;
+    let counts = Arc::new(Counts::default());
+    let codec = HashidBuilder::new().with_salt("this is my salt").with_metrics(counts.clone()).build().unwrap();
+    let cached = hashids::CachedCodec::new(codec, NonZeroUsize::new(10).unwrap());
+    cached.encode(12345).unwrap();
+    cached.encode(12345).unwrap();
+    cached.decode_single("NkK9").unwrap();
+    cached.decode_single("NkK9").unwrap();
+    assert_eq!((count(&counts.hits), count(&counts.misses)), (2, 2));
+    assert_eq!((count(&counts.encoded), count(&counts.decoded)), (1, 1));
+  }
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_counters() {
+  use std::collections::BTreeMap;
+  use std::sync::atomic::{AtomicU64, Ordering};
+  use std::sync::{Arc, Mutex};
+  use hashids::HashidCounters;
+  use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+
+  /// Keeps the counters by name and labels.
+  #[derive(Default)]
+  struct Counters(Mutex<BTreeMap<String, Arc<AtomicU64>>>);
+
+  impl Recorder for Counters {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+      let labels: Vec<String> = key.labels().map(|label| format!("{}={}", label.key(), label.value())).collect();
+      let name = format!("{}{{{}}}", key.name(), labels.join(","));
+      Counter::from_arc(self.0.lock().unwrap().entry(name).or_default().clone())
+    }
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge { Gauge::noop() }
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram { Histogram::noop() }
+  }
+
+  let recorder = Counters::default();
+  metrics::with_local_recorder(&recorder, || {
+    let codec = HashidBuilder::new().with_salt("this is my salt").with_metrics(Arc::new(HashidCounters::new("users"))).build().unwrap();
+    codec.encode(12345).unwrap();
+    codec.decode("NkK9").unwrap();
+    assert!(codec.decode("NkK-").is_err());
+    assert!(codec.decode("NkK").is_err());
+  });
+
+  let counters: Vec<(String, u64)> = recorder.0.lock().unwrap().iter()
+    .map(|(name, value)| (name.clone(), value.load(Ordering::Relaxed)))
+    .collect();
+  assert_eq!(counters, [
+    ("hashids_cache_hits_total{codec=users}".to_string(), 0),
+    ("hashids_cache_misses_total{codec=users}".to_string(), 0),
+    ("hashids_decode_failures_total{codec=users,code=HASHIDS_INVALID_CHARACTER}".to_string(), 1),
+    ("hashids_decode_failures_total{codec=users,code=HASHIDS_INVALID_HASH}".to_string(), 1),
+    ("hashids_decoded_total{codec=users}".to_string(), 1),
+    ("hashids_encoded_total{codec=users}".to_string(), 1)
+  ]);
+}
+
+#[cfg(feature = "diesel")]
+#[test]
+fn diesel_round_trip() {
+  use diesel::prelude::*;
+  use hashids::HashidI64;
+
+  diesel::table! {
+    accounts (id) {
+      id -> BigInt,
+      name -> Text,
+    }
+  }
+
+  let mut conn = SqliteConnection::establish(":memory:").unwrap();
+  diesel::sql_query("CREATE TABLE accounts (id BIGINT PRIMARY KEY NOT NULL, name TEXT NOT NULL)").execute(&mut conn).unwrap();
+  diesel::insert_into(accounts::table)
+    .values((accounts::id.eq(HashidI64(12345)), accounts::name.eq("Jane")))
+    .execute(&mut conn).unwrap();
+
+  let id: HashidI64 = accounts::table.filter(accounts::name.eq("Jane")).select(accounts::id).first(&mut conn).unwrap();
+  assert_eq!(id, HashidI64(12345));
+  let name: String = accounts::table.filter(accounts::id.eq(id)).select(accounts::name).first(&mut conn).unwrap();
+  assert_eq!(name, "Jane");
+}
+
+#[cfg(feature = "diesel")]
+diesel::table! {
+  users (id) {
+    id -> BigInt,
+    name -> Text,
+  }
+}
+
+#[cfg(feature = "diesel")]
+#[allow(dead_code)]
+fn diesel_find_by_hashid<Conn>(conn: &mut Conn) -> diesel::QueryResult<(i64, String)>
+where diesel::dsl::Find<users::table, i64>: diesel::query_dsl::LoadQuery<'static, Conn, (i64, String)> {
+  use hashids::FindByHashid;
+
+  users::table.find_by_hashid(conn, "NkK9")
+}
+
+#[cfg(feature = "sqlx")]
+#[test]
+fn sqlx_round_trip() {
+  use hashids::HashidI64;
+  use sqlx::{Connection, SqliteConnection};
+
+  let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+  runtime.block_on(async {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)").execute(&mut conn).await.unwrap();
+    sqlx::query("INSERT INTO users (id, name) VALUES (?, ?)").bind(HashidI64(12345)).bind("Jane").execute(&mut conn).await.unwrap();
+
+    let (id,): (HashidI64,) = sqlx::query_as("SELECT id FROM users WHERE name = ?").bind("Jane").fetch_one(&mut conn).await.unwrap();
+    assert_eq!(id, HashidI64(12345));
+    let (name,): (String,) = sqlx::query_as("SELECT name FROM users WHERE id = ?").bind(id).fetch_one(&mut conn).await.unwrap();
+    assert_eq!(name, "Jane");
+  });
+}
+
+#[cfg(feature = "sea-orm")]
+#[test]
+fn sea_orm_values() {
+  use hashids::HashidI64;
+  use sea_orm::sea_query::{Nullable, ValueType};
+  use sea_orm::{ActiveValue, IntoActiveValue, Value};
+
+  assert_eq!(Value::from(HashidI64(12345)), Value::BigInt(Some(12345)));
+  assert_eq!(<HashidI64 as ValueType>::try_from(Value::BigInt(Some(12345))).ok(), Some(HashidI64(12345)));
+  assert!(<HashidI64 as ValueType>::try_from(Value::String(Some(Box::new("NkK9".to_string())))).is_err());
+  assert_eq!(HashidI64::null(), Value::BigInt(None));
+  assert_eq!(HashidI64(5).into_active_value(), ActiveValue::Set(HashidI64(5)));
+}
+
+#[cfg(feature = "rusqlite")]
+#[test]
+fn rusqlite_round_trip() {
+  use hashids::HashidI64;
+
+  let conn = rusqlite::Connection::open_in_memory().unwrap();
+  conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)", []).unwrap();
+  conn.execute("INSERT INTO users (id, name) VALUES (?1, ?2)", (HashidI64(12345), "Jane")).unwrap();
+
+  let id: HashidI64 = conn.query_row("SELECT id FROM users WHERE name = ?1", ["Jane"], |row| row.get(0)).unwrap();
+  assert_eq!(id, HashidI64(12345));
+  let name: String = conn.query_row("SELECT name FROM users WHERE id = ?1", [id], |row| row.get(0)).unwrap();
+  assert_eq!(name, "Jane");
+  assert!(conn.query_row("SELECT name FROM users", [], |row| row.get::<_, HashidI64>(0)).is_err());
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn postgres_round_trip() {
+  use hashids::HashidI64;
+  use postgres_types::{FromSql, ToSql, Type};
+
+  let mut buffer = bytes::BytesMut::new();
+  HashidI64(12345).to_sql_checked(&Type::INT8, &mut buffer).unwrap();
+  assert_eq!(HashidI64::from_sql(&Type::INT8, &buffer).unwrap(), HashidI64(12345));
+  assert!(HashidI64(12345).to_sql_checked(&Type::TEXT, &mut buffer).is_err());
+  assert!(!<HashidI64 as FromSql>::accepts(&Type::INT4));
+}
+
+/// Runs the futures of web frameworks on a single threaded runtime.
+#[cfg(any(feature = "axum", feature = "poem"))]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+  tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future)
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_extractor() {
+  use std::sync::Arc;
+  use axum::body::Body;
+  use axum::http::{Request, StatusCode};
+  use axum::routing::get;
+  use axum::{Extension, Router};
+  use hashids::Hashid;
+  use tower_service::Service;
+
+  struct User;
+  async fn show_user(id: Hashid<User>) -> String {
+    format!("user {}", id.id())
+  }
+
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let mut app: Router = Router::new().route("/users/{id}", get(show_user)).layer(Extension(Arc::new(codec)));
+  let mut request = |uri: &str| {
+    let response = block_on(app.call(Request::get(uri).body(Body::empty()).unwrap())).unwrap();
+    let status = response.status();
+    let body = block_on(axum::body::to_bytes(response.into_body(), 1024)).unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+  };
+
+  assert_eq!(request("/users/NkK9"), (StatusCode::OK, "user 12345".to_string()));
+  let (status, body) = request("/users/NkK-");
+  assert_eq!(status, StatusCode::NOT_FOUND);
+  assert!(body.starts_with("invalid character '-'"), "{}", body);
+  assert_eq!(request("/users/KgUO9").0, StatusCode::NOT_FOUND);
+  assert_eq!(axum::response::IntoResponse::into_response(Error::MissingSalt).status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[cfg(feature = "poem")]
+#[test]
+fn poem_extractor() {
+  use std::sync::Arc;
+  use hashids::Hashid;
+  use poem::http::StatusCode;
+  use poem::{get, handler, Endpoint, EndpointExt, Request, Route};
+  struct User;
+
+  #[handler]
+  fn show_user(id: Hashid<User>) -> String {
+    format!("user {}", id.id())
+  }
+
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  let app = Route::new().at("/users/:id", get(show_user)).data(Arc::new(codec));
+  let request = |uri: &str| {
+    let response = block_on(app.get_response(Request::builder().uri(uri.parse().unwrap()).finish()));
+    let status = response.status();
+    (status, block_on(response.into_body().into_string()).unwrap())
+  };
+
+  assert_eq!(request("/users/NkK9"), (StatusCode::OK, "user 12345".to_string()));
+  let (status, body) = request("/users/NkK-");
+  assert_eq!(status, StatusCode::NOT_FOUND);
+  assert!(body.starts_with("invalid character '-'"), "{}", body);
+  assert_eq!(request("/users/KgUO9").0, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn relay_global_ids() {
+  use hashids::GlobalId;
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+
+  // Every padding length of base64
+  for (type_name, global_id) in [("User", "VXNlcjpOa0s5"), ("Users", "VXNlcnM6TmtLOQ=="), ("Orders", "T3JkZXJzOk5rSzk=")] {
+    assert_eq!(codec.encode_global_id(type_name, 12345).unwrap(), global_id);
+    assert_eq!(codec.decode_global_id(global_id), Ok(GlobalId::new(type_name, 12345)));
+  }
+  assert_eq!(codec.decode_global_id_of("Order", "VXNlcjpOa0s5"), Err(Error::InvalidGlobalId));
+
+  assert_eq!(codec.encode_global_id("", 1), Err(Error::InvalidGlobalId));
+  assert_eq!(codec.encode_global_id("a:b", 1), Err(Error::InvalidGlobalId));
+  for invalid in ["", "VXNlcjpOa0s", "VXNlcjpOa0s!", "VX==cjpOa0s5", "TmtLOQ==", "Ok5rSzk="] {
+    assert_eq!(codec.decode_global_id(invalid), Err(Error::InvalidGlobalId), "{}", invalid);
+  }
+  // "User:NkK-"
+  assert_eq!(codec.decode_global_id("VXNlcjpOa0st"), Err(Error::InvalidCharacter { character: '-', position: 3 }));
+}
+
+#[test]
+fn hash_patterns() {
+  let codec = HashidBuilder::new().with_salt("this is my salt").build().unwrap();
+  assert_eq!(codec.hash_pattern(), "^[0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz]{4,}$");
+
+  let codec = HashidBuilder::new().with_salt("this is my salt")
+    .with_alphabet("abcdefghijklmnop ]^[-\\.*+?".to_string())
+    .with_length(0).with_suffix(".v1").build().unwrap();
+  assert_eq!(codec.hash_pattern(), r"^[ *+\-.?\[\\\]\^abcdefghijklmnop]{2,}\.v1$");
+}
+
+// Errors are JavaScript values, which only exist on wasm targets
+#[cfg(feature = "wasm")]
+#[test]
+fn wasm_codec() {
+  use hashids::wasm::JsHashidCodec;
+
+  let codec = JsHashidCodec::new("this is my salt", None, None).unwrap();
+  assert_eq!(codec.encode(12345.0).unwrap(), "NkK9");
+  assert_eq!(codec.decode("KgUO9").unwrap(), vec![42.0, 1337.0]);
+  let max = codec.encode(9007199254740991.0).unwrap();
+  assert_eq!(codec.decode(&max).unwrap(), vec![9007199254740991.0]);
+  // IDs above u32::MAX give the same hashes as natively
+  let above_u32 = u32::MAX as f64 + 1.0;
+  let hash = codec.encode(above_u32).unwrap();
+  assert_eq!(hash, HashidBuilder::new().with_salt("this is my salt").build().unwrap().encode(1u64 << 32).unwrap());
+  assert_eq!(codec.decode(&hash).unwrap(), vec![above_u32]);
+
+  let codec = JsHashidCodec::new("this is my salt", Some("0123456789abcdef".to_string()), Some(8)).unwrap();
+  let hash = codec.encode(1.0).unwrap();
+  assert_eq!(hash.len(), 8);
+  assert_eq!(codec.decode(&hash).unwrap(), vec![1.0]);
+}
+
+#[cfg(feature = "uniffi")]
+#[test]
+fn mobile_codec() {
+  use hashids::mobile::{HashidCodec, HashidsError};
+  use hashids::Error;
+
+  let codec = HashidCodec::new("this is my salt".to_string(), None, None).unwrap();
+  assert_eq!(codec.encode(12345).unwrap(), "NkK9");
+  assert_eq!(codec.decode("KgUO9").unwrap(), vec![42, 1337]);
+  assert!(matches!(codec.decode("NkK-"), Err(HashidsError::InvalidHash(Error::InvalidCharacter { .. }))));
+  assert_eq!(codec.decode("NkK-").unwrap_err().to_string(), "invalid character '-' in the hash at position 3");
+
+  let codec = HashidCodec::new("this is my salt".to_string(), Some("0123456789abcdef".to_string()), Some(8)).unwrap();
+  assert_eq!(codec.decode(&codec.encode(1).unwrap()).unwrap(), vec![1]);
+  let above_u32 = 1u64 << 32;
+  assert_eq!(codec.decode(&codec.encode(above_u32).unwrap()).unwrap(), vec![above_u32]);
+  assert!(matches!(HashidCodec::new("this is my salt".to_string(), Some("abc".to_string()), None),
+    Err(HashidsError::InvalidSettings(Error::InvalidAlphabetLength { .. }))));
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn c_interface() {
+  use std::ffi::{CStr, CString};
+  use std::ptr;
+  use hashids::ffi::*;
+
+  unsafe {
+    let salt = CString::new("this is my salt").unwrap();
+    let codec = hashids_new(salt.as_ptr(), ptr::null(), 4);
+    assert!(!codec.is_null());
+
+    let hash = hashids_encode(codec, 12345);
+    assert_eq!(CStr::from_ptr(hash).to_str(), Ok("NkK9"));
+    hashids_string_free(hash);
+
+    let mut numbers = [0u64; 1];
+    let hash = CString::new("KgUO9").unwrap();
+    // Only the first number fits
+    assert_eq!(hashids_decode(codec, hash.as_ptr(), numbers.as_mut_ptr(), 1), 2);
+    assert_eq!(numbers, [42]);
+    assert_eq!(hashids_decode(codec, hash.as_ptr(), ptr::null_mut(), 0), 2);
+
+    // IDs above u32::MAX aren't truncated on 32-bit targets
+    let hash = hashids_encode(codec, 1 << 32);
+    assert_eq!(hashids_decode(codec, hash, numbers.as_mut_ptr(), 1), 1);
+    assert_eq!(numbers, [1 << 32]);
+    hashids_string_free(hash);
+
+    let hash = CString::new("NkK-").unwrap();
+    assert_eq!(hashids_decode(codec, hash.as_ptr(), numbers.as_mut_ptr(), 1), -1);
+    assert_eq!(CStr::from_ptr(hashids_last_error()).to_str(), Ok("invalid character '-' in the hash at position 3"));
+    hashids_free(codec);
+
+    let alphabet = CString::new("abc").unwrap();
+    assert!(hashids_new(salt.as_ptr(), alphabet.as_ptr(), 4).is_null());
+    assert!(CStr::from_ptr(hashids_last_error()).to_str().unwrap().starts_with("the alphabet has 3 unique characters"));
+    assert!(hashids_encode(ptr::null(), 1).is_null());
+  }
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn c_header_is_current() {
+  let dir = env!("CARGO_MANIFEST_DIR");
+  let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", dir)).unwrap();
+  let mut header = Vec::new();
+  cbindgen::Builder::new().with_src(format!("{}/src/ffi.rs", dir)).with_config(config).generate().unwrap().write(&mut header);
+  let committed = std::fs::read_to_string(format!("{}/include/hashids.h", dir)).unwrap();
+  assert!(String::from_utf8(header).unwrap() == committed, "include/hashids.h is outdated, regenerate it as cbindgen.toml explains");
 }
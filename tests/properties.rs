@@ -0,0 +1,34 @@
+#![cfg(feature = "test-support")]
+use proptest::prelude::*;
+use hashids::test_support::{arb_codec, arb_id, arb_id_u128};
+
+proptest! {
+  #[test]
+  fn single_round_trip(codec in arb_codec(), id in arb_id()) {
+    let hash = codec.encode(id).unwrap();
+    prop_assert_eq!(codec.decode_single(&hash), Ok(id));
+  }
+
+  #[test]
+  fn u128_round_trip(codec in arb_codec(), id in arb_id_u128()) {
+    let hash = codec.encode(id).unwrap();
+    prop_assert_eq!(codec.decode_u128(&hash), Ok(vec![id]));
+  }
+
+  #[test]
+  fn tuple_round_trip(codec in arb_codec(), ids in (arb_id(), arb_id(), arb_id())) {
+    let hash = codec.encode_tuple(ids).unwrap();
+    prop_assert_eq!(codec.decode_tuple(&hash), Ok(ids));
+  }
+
+  #[test]
+  fn hex_round_trip(codec in arb_codec(), hex in "[0-9a-f]{1,40}") {
+    let hash = codec.encode_hex(&hex).unwrap();
+    prop_assert_eq!(codec.decode_hex(&hash), Ok(hex));
+  }
+
+  #[test]
+  fn decoding_never_panics(codec in arb_codec(), hash in "\\PC{0,24}") {
+    let _ = codec.decode(&hash);
+  }
+}